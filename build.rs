@@ -1,6 +1,7 @@
 use std::env;
 use std::fs;
 use std::path::Path;
+use std::process::Command;
 
 use clap::{CommandFactory, ValueEnum};
 use clap_complete::{generate_to, Shell};
@@ -26,5 +27,45 @@ fn main() -> std::io::Result<()> {
     // Tell Cargo to rerun this script if cli.rs changes
     println!("cargo:rerun-if-changed=src/cli.rs");
 
+    emit_build_info();
+
     Ok(())
 }
+
+/// Set GIT_HASH, BUILD_DATE and RUSTC_VERSION for `gwt --version --verbose`
+fn emit_build_info() {
+    let git_hash = command_output("git", &["rev-parse", "--short", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GIT_HASH={}", git_hash);
+
+    // Rerun when HEAD moves; the paths only exist when building from a git checkout
+    // (a missing path would make Cargo rerun this script on every build)
+    if Path::new(".git/HEAD").exists() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Some(head_ref) = command_output("git", &["symbolic-ref", "HEAD"]) {
+            let ref_path = Path::new(".git").join(&head_ref);
+            if ref_path.exists() {
+                println!("cargo:rerun-if-changed={}", ref_path.display());
+            }
+        }
+    }
+
+    // SOURCE_DATE_EPOCH keeps reproducible builds reproducible
+    let build_time = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .and_then(|epoch| chrono::DateTime::from_timestamp(epoch, 0))
+        .unwrap_or_else(chrono::Utc::now);
+    println!("cargo:rustc-env=BUILD_DATE={}", build_time.format("%Y-%m-%d"));
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = command_output(&rustc, &["--version"]).unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=RUSTC_VERSION={}", rustc_version);
+}
+
+/// Trimmed stdout of a successful command
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    (output.status.success() && !stdout.trim().is_empty()).then(|| stdout.trim().to_string())
+}
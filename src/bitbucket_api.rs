@@ -4,6 +4,7 @@ use std::collections::HashMap;
 
 use crate::bitbucket_auth::BitbucketAuth;
 use crate::error::{Error, Result};
+use crate::http;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct BitbucketUser {
@@ -52,17 +53,37 @@ pub struct BitbucketPullRequest {
 #[derive(Debug, Deserialize)]
 pub struct BitbucketPullRequestsResponse {
     pub values: Vec<BitbucketPullRequest>,
+    /// URL of the next page, absent on the last page
+    pub next: Option<String>,
 }
 
+/// Public Bitbucket Cloud REST API, used unless `bitbucketApiBase` is configured
+pub const DEFAULT_API_BASE: &str = "https://api.bitbucket.org/2.0";
+
 pub struct BitbucketClient {
     client: Client,
     auth: BitbucketAuth,
+    /// API root without a trailing slash (e.g. `https://api.bitbucket.org/2.0`)
+    api_base: String,
 }
 
 impl BitbucketClient {
-    pub fn new(auth: BitbucketAuth) -> Self {
-        let client = Client::new();
-        BitbucketClient { client, auth }
+    /// `api_base` overrides [`DEFAULT_API_BASE`], e.g. for an API proxy or mirror
+    pub fn new(auth: BitbucketAuth, api_base: Option<&str>) -> Self {
+        let client = http::client();
+        let api_base = api_base.unwrap_or(DEFAULT_API_BASE).trim_end_matches('/').to_string();
+        BitbucketClient { client, auth, api_base }
+    }
+
+    fn pull_requests_url(&self, workspace: &str, repo_slug: &str) -> String {
+        format!(
+            "{}/repositories/{}/{}/pullrequests",
+            self.api_base, workspace, repo_slug
+        )
+    }
+
+    fn user_url(&self) -> String {
+        format!("{}/user", self.api_base)
     }
 
     fn get_email(&self) -> String {
@@ -70,21 +91,51 @@ impl BitbucketClient {
         self.auth.email().unwrap_or_else(|| "user".to_string())
     }
 
-    pub async fn get_pull_requests(&self, workspace: &str, repo_slug: &str) -> Result<Vec<BitbucketPullRequest>> {
+    /// Open pull requests for the repository, at most `max` of them (all for `None`)
+    pub async fn get_pull_requests(
+        &self,
+        workspace: &str,
+        repo_slug: &str,
+        max: Option<usize>,
+    ) -> Result<Vec<BitbucketPullRequest>> {
         let token = self.auth.get_token()?;
-        let url = format!(
-            "https://api.bitbucket.org/2.0/repositories/{}/{}/pullrequests",
-            workspace, repo_slug
-        );
+        let mut url = Some(self.pull_requests_url(workspace, repo_slug));
 
-        let response = self
-            .client
-            .get(&url)
-            .basic_auth(self.get_email(), Some(&token))
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| Error::network(format!("Failed to send request to Bitbucket API: {}", e)))?;
+        // Follow `next` links until exhausted (or `max` pull requests are collected)
+        let mut pull_requests = Vec::new();
+        while let Some(page_url) = url.take() {
+            if max.is_some_and(|max| pull_requests.len() >= max) {
+                break;
+            }
+            let page = self
+                .get_pull_requests_page(&page_url, &token, workspace, repo_slug)
+                .await?;
+            pull_requests.extend(page.values);
+            url = page.next;
+        }
+
+        if let Some(max) = max {
+            pull_requests.truncate(max);
+        }
+        Ok(pull_requests)
+    }
+
+    /// Fetch a single page of pull requests, re-applying authentication
+    async fn get_pull_requests_page(
+        &self,
+        url: &str,
+        token: &str,
+        workspace: &str,
+        repo_slug: &str,
+    ) -> Result<BitbucketPullRequestsResponse> {
+        let response = http::send_with_retry(|| {
+            self.client
+                .get(url)
+                .basic_auth(self.get_email(), Some(token))
+                .header("Accept", "application/json")
+        })
+        .await
+        .map_err(|e| http::request_error("Failed to send request to Bitbucket API", e))?;
 
         if response.status().is_client_error() {
             let status = response.status();
@@ -107,26 +158,31 @@ impl BitbucketClient {
             }
         }
 
-        let pr_response: BitbucketPullRequestsResponse = response
+        if response.status().is_server_error() {
+            return Err(Error::network(format!(
+                "Bitbucket API request failed with status {} after retrying",
+                response.status()
+            )));
+        }
+
+        response
             .json()
             .await
-            .map_err(|e| Error::provider(format!("Failed to parse Bitbucket API response: {}", e)))?;
-
-        Ok(pr_response.values)
+            .map_err(|e| Error::provider(format!("Failed to parse Bitbucket API response: {}", e)))
     }
 
     pub async fn test_connection(&self) -> Result<()> {
         let token = self.auth.get_token()?;
-        let url = "https://api.bitbucket.org/2.0/user";
-
-        let response = self
-            .client
-            .get(url)
-            .basic_auth(self.get_email(), Some(&token))
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| Error::network(format!("Failed to test Bitbucket API connection: {}", e)))?;
+        let url = self.user_url();
+
+        let response = http::send_with_retry(|| {
+            self.client
+                .get(&url)
+                .basic_auth(self.get_email(), Some(&token))
+                .header("Accept", "application/json")
+        })
+        .await
+        .map_err(|e| http::request_error("Failed to test Bitbucket API connection", e))?;
 
         if response.status().is_success() {
             println!("✓ Bitbucket API connection successful");
@@ -152,9 +208,12 @@ pub fn extract_bitbucket_info_from_url(url: &str) -> Option<(String, String)> {
     // https://bitbucket.org/workspace/repo
     // git@bitbucket.org:workspace/repo.git
     // https://bitbucket.org/workspace/repo.git
+    // https://bitbucket.org/workspace/my.repo/?ref=x
+    // https://bitbucket.org/workspace/repo/src/main (web URLs with a path after the repo)
+    // Repo names may contain dots; only a trailing ".git" is stripped.
 
     if url.contains("bitbucket.org") {
-        if let Some(captures) = regex::Regex::new(r"bitbucket\.org[:/]([^/]+)/([^/\.]+)")
+        if let Some(captures) = regex::Regex::new(r"bitbucket\.org[:/]([^/]+)/([^/?#]+?)(?:\.git)?(?:[/?#].*)?$")
             .ok()?
             .captures(url)
         {
@@ -196,6 +255,81 @@ mod tests {
         assert_eq!(result, Some(("myworkspace".to_string(), "myrepo".to_string())));
     }
 
+    #[test]
+    fn test_extract_bitbucket_info_dotted_repo() {
+        let expected = Some(("ws".to_string(), "my.repo".to_string()));
+        assert_eq!(
+            extract_bitbucket_info_from_url("https://bitbucket.org/ws/my.repo.git"),
+            expected
+        );
+        assert_eq!(
+            extract_bitbucket_info_from_url("git@bitbucket.org:ws/my.repo.git"),
+            expected
+        );
+        assert_eq!(
+            extract_bitbucket_info_from_url("https://bitbucket.org/ws/my.repo"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_extract_bitbucket_info_trailing_slash_and_suffixes() {
+        let expected = Some(("ws".to_string(), "repo".to_string()));
+        assert_eq!(
+            extract_bitbucket_info_from_url("https://bitbucket.org/ws/repo/"),
+            expected
+        );
+        assert_eq!(
+            extract_bitbucket_info_from_url("https://bitbucket.org/ws/repo?ref=x"),
+            expected
+        );
+        assert_eq!(
+            extract_bitbucket_info_from_url("https://bitbucket.org/ws/repo.git/#readme"),
+            expected
+        );
+        assert_eq!(
+            extract_bitbucket_info_from_url("https://bitbucket.org/ws/repo/src/main"),
+            expected
+        );
+        assert_eq!(
+            extract_bitbucket_info_from_url("https://bitbucket.org/ws/repo/pull-requests/12"),
+            expected
+        );
+        assert_eq!(
+            extract_bitbucket_info_from_url("https://bitbucket.org/ws/repo.git/src/main"),
+            expected
+        );
+        assert_eq!(extract_bitbucket_info_from_url("https://bitbucket.org/ws/"), None);
+    }
+
+    #[test]
+    fn test_api_urls_from_custom_base() {
+        let auth = || BitbucketAuth::new("ws".to_string(), "repo".to_string(), None).unwrap();
+
+        let client = BitbucketClient::new(auth(), Some("https://proxy.example.com/bitbucket/2.0/"));
+        assert_eq!(
+            client.pull_requests_url("ws", "repo"),
+            "https://proxy.example.com/bitbucket/2.0/repositories/ws/repo/pullrequests"
+        );
+        assert_eq!(client.user_url(), "https://proxy.example.com/bitbucket/2.0/user");
+
+        let client = BitbucketClient::new(auth(), None);
+        assert_eq!(
+            client.pull_requests_url("ws", "repo"),
+            "https://api.bitbucket.org/2.0/repositories/ws/repo/pullrequests"
+        );
+    }
+
+    #[test]
+    fn test_pull_requests_response_next_link() {
+        let page: BitbucketPullRequestsResponse =
+            serde_json::from_str(r#"{"values": [], "next": "https://api.bitbucket.org/2.0/next?page=2"}"#).unwrap();
+        assert_eq!(page.next.as_deref(), Some("https://api.bitbucket.org/2.0/next?page=2"));
+
+        let last: BitbucketPullRequestsResponse = serde_json::from_str(r#"{"values": []}"#).unwrap();
+        assert!(last.next.is_none());
+    }
+
     #[test]
     fn test_extract_bitbucket_info_invalid() {
         let url = "https://github.com/user/repo";
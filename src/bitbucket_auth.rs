@@ -1,27 +1,34 @@
-use keyring::Entry;
 use std::env;
+use std::fs;
 
 use crate::error::{Error, Result};
+use crate::token_store::{TokenLocation, TokenStore};
 
 const SERVICE_NAME: &str = "git-worktree-cli-bitbucket";
 const EMAIL_ENV_VAR: &str = "BITBUCKET_CLOUD_EMAIL";
 const TOKEN_ENV_VAR: &str = "BITBUCKET_CLOUD_API_TOKEN";
+const TOKEN_FILE_ENV_VAR: &str = "BITBUCKET_CLOUD_API_TOKEN_FILE";
 
 pub struct BitbucketAuth {
     email: Option<String>,
-    token_entry: Entry,
+    store: TokenStore,
 }
 
 impl BitbucketAuth {
     pub fn new(workspace: String, repo: String, email: Option<String>) -> Result<Self> {
         // Use workspace/repo as the key identifier for better isolation
         let key_id = format!("{}/{}", workspace, repo);
-        let token_entry = Entry::new(SERVICE_NAME, &key_id)?;
+        let store = TokenStore::new(SERVICE_NAME, key_id);
 
-        Ok(BitbucketAuth { email, token_entry })
+        Ok(BitbucketAuth { email, store })
     }
 
     pub fn get_token(&self) -> Result<String> {
+        // A token file (e.g. a Docker secret) takes precedence
+        if let Some(token) = token_from_file(TOKEN_FILE_ENV_VAR) {
+            return token;
+        }
+
         // Check environment variable
         if let Ok(token) = env::var(TOKEN_ENV_VAR) {
             if !token.is_empty() {
@@ -29,8 +36,8 @@ impl BitbucketAuth {
             }
         }
 
-        // Then check keyring
-        self.token_entry.get_password().map_err(|_| {
+        // Then check the keyring (or token file)
+        self.store.get()?.map(|(token, _)| token).ok_or_else(|| {
             Error::auth(format!(
                 "No Bitbucket Cloud API token found. Please set the {} and {} environment variables.\n\
                 Run 'gwt auth bitbucket-cloud setup' for instructions.",
@@ -39,6 +46,11 @@ impl BitbucketAuth {
         })
     }
 
+    /// Store the token for this repository, returning where it went
+    pub fn store_token(&self, token: &str) -> Result<TokenLocation> {
+        self.store.set(token)
+    }
+
     pub fn email(&self) -> Option<String> {
         // First check environment variable
         if let Ok(email) = env::var(EMAIL_ENV_VAR) {
@@ -51,24 +63,62 @@ impl BitbucketAuth {
     }
 
     pub fn has_stored_token(&self) -> bool {
-        // Check env var first
-        if let Ok(token) = env::var(TOKEN_ENV_VAR) {
-            if !token.is_empty() {
-                return true;
-            }
+        self.token_source().is_some()
+    }
+
+    /// Where the token comes from (token file, environment, keyring, or gwt's token file), if there is one
+    pub fn token_source(&self) -> Option<String> {
+        // A token file or environment variable wins; a broken token file doesn't fall back
+        let source = env_token_source();
+        if source.is_some() || env::var_os(TOKEN_FILE_ENV_VAR).is_some() {
+            return source;
         }
 
-        // Then check keyring
-        self.token_entry.get_password().is_ok()
+        // Then check the keyring (or token file)
+        self.store.location().map(|location| location.to_string())
+    }
+}
+
+/// Where a token set outside the keyring comes from: the token file or the environment
+///
+/// The keyring entry is per repository, so this is all that can be checked outside a
+/// Bitbucket Cloud project.
+pub fn env_token_source() -> Option<String> {
+    if let Some(token) = token_from_file(TOKEN_FILE_ENV_VAR) {
+        return token.is_ok().then(|| format!("{} (file)", TOKEN_FILE_ENV_VAR));
     }
+
+    env::var(TOKEN_ENV_VAR)
+        .is_ok_and(|token| !token.is_empty())
+        .then(|| format!("{} (env)", TOKEN_ENV_VAR))
+}
+
+/// Read a token from the file named by `file_env_var`, if that variable is set
+///
+/// Lets secrets be mounted as files (Docker/Kubernetes secrets) instead of passed
+/// as environment variables. Surrounding whitespace is trimmed; a missing,
+/// unreadable, or empty file is an auth error rather than a silent fallback.
+pub(crate) fn token_from_file(file_env_var: &str) -> Option<Result<String>> {
+    let path = env::var(file_env_var).ok().filter(|path| !path.is_empty())?;
+    let token = fs::read_to_string(&path)
+        .map_err(|e| Error::auth(format!("Failed to read token file {} ({}): {}", path, file_env_var, e)))
+        .and_then(|content| {
+            let token = content.trim().to_string();
+            if token.is_empty() {
+                Err(Error::auth(format!("Token file {} ({}) is empty", path, file_env_var)))
+            } else {
+                Ok(token)
+            }
+        });
+    Some(token)
 }
 
 pub fn get_auth_from_config() -> Result<(String, String, Option<String>)> {
     use crate::bitbucket_api::extract_bitbucket_info_from_url;
     use crate::config::GitWorktreeConfig;
 
-    let (_, config) =
-        GitWorktreeConfig::find_config()?.ok_or_else(|| Error::config("No git-worktree-config.jsonc found"))?;
+    let (_, config) = GitWorktreeConfig::find_config()?
+        .ok_or_else(|| Error::config(format!("No {} found", crate::config::config_filename())))?;
 
     if !config.repository_url.contains("bitbucket.org") {
         return Err(Error::provider("This is not a Bitbucket repository"));
@@ -91,14 +141,23 @@ pub fn display_setup_instructions() {
     println!("4. Set environment variables:");
     println!("   export {}=your-email@example.com", EMAIL_ENV_VAR);
     println!("   export {}=YOUR_TOKEN", TOKEN_ENV_VAR);
+    println!(
+        "\n   or point {} at a file containing the token (e.g. a Docker secret)",
+        TOKEN_FILE_ENV_VAR
+    );
+    println!("\n   or, from inside the project, store it in the system keyring:");
+    println!("   gwt auth bitbucket-cloud setup");
+    println!("   or pipe it in: gwt auth bitbucket-cloud setup --token-stdin < token.txt");
     println!("\nNote: The email should match your Bitbucket account email.");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
+    #[serial]
     fn test_bitbucket_auth_creation() {
         // Temporarily remove environment variable for isolated testing
         env::remove_var(EMAIL_ENV_VAR);
@@ -121,4 +180,29 @@ mod tests {
         // The auth should be created successfully
         assert!(auth.email().is_none());
     }
+
+    #[test]
+    #[serial]
+    fn test_token_from_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let token_path = temp_dir.path().join("token");
+        std::fs::write(&token_path, "  secret-token\n").unwrap();
+        env::set_var(TOKEN_FILE_ENV_VAR, &token_path);
+
+        let auth = BitbucketAuth::new("workspace".to_string(), "file-token".to_string(), None).unwrap();
+        assert_eq!(auth.get_token().unwrap(), "secret-token");
+        assert!(auth.has_stored_token());
+        assert_eq!(
+            auth.token_source().as_deref(),
+            Some("BITBUCKET_CLOUD_API_TOKEN_FILE (file)")
+        );
+
+        env::set_var(TOKEN_FILE_ENV_VAR, temp_dir.path().join("missing"));
+        let err = auth.get_token().unwrap_err();
+        assert!(matches!(err, Error::Auth(_)));
+        assert!(err.to_string().contains(TOKEN_FILE_ENV_VAR));
+        assert!(!auth.has_stored_token());
+
+        env::remove_var(TOKEN_FILE_ENV_VAR);
+    }
 }
@@ -1,9 +1,11 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 
 use crate::bitbucket_data_center_auth::BitbucketDataCenterAuth;
 use crate::error::{Error, Result};
+use crate::http;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct BitbucketDataCenterUser {
@@ -111,15 +113,46 @@ pub struct BitbucketDataCenterPullRequest {
 #[derive(Debug, Deserialize)]
 pub struct BitbucketDataCenterPullRequestsResponse {
     pub values: Vec<BitbucketDataCenterPullRequest>,
-    #[allow(dead_code)]
     pub size: u32,
     #[allow(dead_code)]
     pub limit: u32,
     #[serde(rename = "isLastPage")]
-    #[allow(dead_code)]
     pub is_last_page: bool,
-    #[allow(dead_code)]
     pub start: u32,
+    #[serde(rename = "nextPageStart")]
+    pub next_page_start: Option<u32>,
+}
+
+/// Page size requested from the pull requests endpoint
+const PR_PAGE_LIMIT: u32 = 100;
+
+/// Fetch pages starting at `start = 0` until `isLastPage` or `max` values are collected
+async fn collect_pull_request_pages<F, Fut>(
+    mut fetch_page: F,
+    max: Option<usize>,
+) -> Result<Vec<BitbucketDataCenterPullRequest>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<BitbucketDataCenterPullRequestsResponse>>,
+{
+    let mut pull_requests = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let page = fetch_page(start).await?;
+        let next_start = page.next_page_start.unwrap_or(page.start + page.size);
+        let done = page.is_last_page || page.values.is_empty();
+        pull_requests.extend(page.values);
+        if done || max.is_some_and(|max| pull_requests.len() >= max) {
+            break;
+        }
+        start = next_start;
+    }
+
+    if let Some(max) = max {
+        pull_requests.truncate(max);
+    }
+    Ok(pull_requests)
 }
 
 pub struct BitbucketDataCenterClient {
@@ -130,14 +163,16 @@ pub struct BitbucketDataCenterClient {
 
 impl BitbucketDataCenterClient {
     pub fn new(auth: BitbucketDataCenterAuth, base_url: String) -> Self {
-        let client = Client::new();
+        let client = http::client();
         BitbucketDataCenterClient { client, auth, base_url }
     }
 
+    /// Open pull requests for the repository, at most `max` of them (all for `None`)
     pub async fn get_pull_requests(
         &self,
         project_key: &str,
         repo_slug: &str,
+        max: Option<usize>,
     ) -> Result<Vec<BitbucketDataCenterPullRequest>> {
         let token = self.auth.get_token()?;
         let url = format!(
@@ -147,14 +182,31 @@ impl BitbucketDataCenterClient {
             repo_slug
         );
 
-        let response = self
-            .client
-            .get(&url)
-            .bearer_auth(&token)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| Error::network(format!("Failed to send request to Bitbucket Data Center API: {}", e)))?;
+        collect_pull_request_pages(
+            |start| self.get_pull_requests_page(&url, &token, start, project_key, repo_slug),
+            max,
+        )
+        .await
+    }
+
+    /// Fetch a single page of pull requests beginning at `start`
+    async fn get_pull_requests_page(
+        &self,
+        url: &str,
+        token: &str,
+        start: u32,
+        project_key: &str,
+        repo_slug: &str,
+    ) -> Result<BitbucketDataCenterPullRequestsResponse> {
+        let page_url = format!("{}?start={}&limit={}", url, start, PR_PAGE_LIMIT);
+        let response = http::send_with_retry(|| {
+            self.client
+                .get(&page_url)
+                .bearer_auth(token)
+                .header("Accept", "application/json")
+        })
+        .await
+        .map_err(|e| http::request_error("Failed to send request to Bitbucket Data Center API", e))?;
 
         if response.status().is_client_error() {
             let status = response.status();
@@ -177,26 +229,31 @@ impl BitbucketDataCenterClient {
             }
         }
 
-        let pr_response: BitbucketDataCenterPullRequestsResponse = response
+        if response.status().is_server_error() {
+            return Err(Error::network(format!(
+                "Bitbucket Data Center API request failed with status {} after retrying",
+                response.status()
+            )));
+        }
+
+        response
             .json()
             .await
-            .map_err(|e| Error::provider(format!("Failed to parse Bitbucket Data Center API response: {}", e)))?;
-
-        Ok(pr_response.values)
+            .map_err(|e| Error::provider(format!("Failed to parse Bitbucket Data Center API response: {}", e)))
     }
 
     pub async fn test_connection(&self) -> Result<()> {
         let token = self.auth.get_token()?;
         let url = format!("{}/rest/api/1.0/users", self.base_url.trim_end_matches('/'));
 
-        let response = self
-            .client
-            .get(&url)
-            .bearer_auth(&token)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| Error::network(format!("Failed to test Bitbucket Data Center API connection: {}", e)))?;
+        let response = http::send_with_retry(|| {
+            self.client
+                .get(&url)
+                .bearer_auth(&token)
+                .header("Accept", "application/json")
+        })
+        .await
+        .map_err(|e| http::request_error("Failed to test Bitbucket Data Center API connection", e))?;
 
         if response.status().is_success() {
             println!("✓ Bitbucket Data Center API connection successful");
@@ -355,6 +412,80 @@ mod tests {
         );
     }
 
+    /// Minimal pull request JSON for pagination fixtures
+    fn pull_request_json(id: u64) -> String {
+        let repo =
+            r#"{"slug": "repo", "name": "repo", "id": 1, "project": {"key": "PROJ", "name": "Project", "id": 1}}"#;
+        let user = r#"{"name": "dev", "displayName": "Dev", "id": 1, "slug": "dev"}"#;
+        format!(
+            r#"{{"id": {id}, "version": 0, "title": "PR {id}", "state": "OPEN", "open": true, "closed": false,
+                "author": {{"user": {user}, "role": "AUTHOR", "approved": false, "status": "UNAPPROVED"}},
+                "fromRef": {{"id": "refs/heads/b{id}", "displayId": "b{id}", "latestCommit": "abc", "type": "BRANCH", "repository": {repo}}},
+                "toRef": {{"id": "refs/heads/main", "displayId": "main", "latestCommit": "def", "type": "BRANCH", "repository": {repo}}},
+                "createdDate": 0, "updatedDate": 0, "links": {{}}}}"#
+        )
+    }
+
+    fn page_json(ids: &[u64], start: u32, is_last_page: bool) -> String {
+        let values: Vec<String> = ids.iter().map(|id| pull_request_json(*id)).collect();
+        let next = if is_last_page {
+            String::new()
+        } else {
+            format!(r#", "nextPageStart": {}"#, start + ids.len() as u32)
+        };
+        format!(
+            r#"{{"values": [{}], "size": {}, "limit": 2, "isLastPage": {}, "start": {}{}}}"#,
+            values.join(","),
+            ids.len(),
+            is_last_page,
+            start,
+            next
+        )
+    }
+
+    #[tokio::test]
+    async fn test_collect_pull_request_pages_follows_start() {
+        let mut requested_starts = Vec::new();
+        let pull_requests = collect_pull_request_pages(
+            |start| {
+                requested_starts.push(start);
+                let json = match start {
+                    0 => page_json(&[1, 2], 0, false),
+                    2 => page_json(&[3, 4], 2, false),
+                    _ => page_json(&[5], 4, true),
+                };
+                async move { Ok(serde_json::from_str(&json).unwrap()) }
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(requested_starts, vec![0, 2, 4]);
+        let ids: Vec<u64> = pull_requests.iter().map(|pr| pr.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+
+        // Stops paging once `max` pull requests are collected
+        requested_starts.clear();
+        let pull_requests = collect_pull_request_pages(
+            |start| {
+                requested_starts.push(start);
+                let json = match start {
+                    0 => page_json(&[1, 2], 0, false),
+                    _ => page_json(&[3, 4], 2, false),
+                };
+                async move { Ok(serde_json::from_str(&json).unwrap()) }
+            },
+            Some(3),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(requested_starts, vec![0, 2]);
+        let ids: Vec<u64> = pull_requests.iter().map(|pr| pr.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
     #[test]
     fn test_extract_bitbucket_data_center_info_invalid() {
         let url = "https://github.com/user/repo";
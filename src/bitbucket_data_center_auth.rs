@@ -1,37 +1,77 @@
 use std::env;
 
+use crate::bitbucket_auth::token_from_file;
 use crate::error::{Error, Result};
+use crate::token_store::{TokenLocation, TokenStore};
 
 const TOKEN_ENV_VAR: &str = "BITBUCKET_DATA_CENTER_HTTP_ACCESS_TOKEN";
+const TOKEN_FILE_ENV_VAR: &str = "BITBUCKET_DATA_CENTER_HTTP_ACCESS_TOKEN_FILE";
+const SERVICE_NAME: &str = "git-worktree-cli-bitbucket-data-center";
 
-pub struct BitbucketDataCenterAuth;
+pub struct BitbucketDataCenterAuth {
+    store: TokenStore,
+}
 
 impl BitbucketDataCenterAuth {
-    pub fn new(_project_key: String, _repo_slug: String, _base_url: String) -> Result<Self> {
-        Ok(BitbucketDataCenterAuth)
+    pub fn new(_project_key: String, _repo_slug: String, base_url: String) -> Result<Self> {
+        // Access tokens are stored per server
+        let server = base_url.split("://").last().unwrap_or_default().trim_end_matches('/');
+        Ok(BitbucketDataCenterAuth {
+            store: TokenStore::new(SERVICE_NAME, server),
+        })
+    }
+
+    /// Store the token for this server, returning where it went
+    pub fn store_token(&self, token: &str) -> Result<TokenLocation> {
+        self.store.set(token)
+    }
+
+    /// Where the token comes from (token file, environment, keyring, or gwt's token file), if there is one
+    pub fn token_source(&self) -> Option<String> {
+        // Set variables win, even when they're broken, as in `get_token`
+        if env::var_os(TOKEN_FILE_ENV_VAR).is_some() || env::var_os(TOKEN_ENV_VAR).is_some() {
+            return token_source();
+        }
+        self.store.location().map(|location| location.to_string())
     }
 
     pub fn get_token(&self) -> Result<String> {
-        env::var(TOKEN_ENV_VAR)
-            .map_err(|_| {
+        // A token file (e.g. a Docker secret) takes precedence
+        if let Some(token) = token_from_file(TOKEN_FILE_ENV_VAR) {
+            return token;
+        }
+
+        match env::var(TOKEN_ENV_VAR) {
+            Ok(token) if token.is_empty() => Err(Error::auth(format!(
+                "Bitbucket Data Center access token is empty. Please set the {} environment variable.\n\
+                Run 'gwt auth bitbucket-data-center setup' for instructions.",
+                TOKEN_ENV_VAR
+            ))),
+            Ok(token) => Ok(token),
+            // Then check the keyring (or token file)
+            Err(_) => self.store.get()?.map(|(token, _)| token).ok_or_else(|| {
                 Error::auth(format!(
                     "No Bitbucket Data Center access token found. Please set the {} environment variable.\n\
                 Run 'gwt auth bitbucket-data-center setup' for instructions.",
                     TOKEN_ENV_VAR
                 ))
-            })
-            .and_then(|token| {
-                if token.is_empty() {
-                    Err(Error::auth(format!(
-                        "Bitbucket Data Center access token is empty. Please set the {} environment variable.\n\
-                        Run 'gwt auth bitbucket-data-center setup' for instructions.",
-                        TOKEN_ENV_VAR
-                    )))
-                } else {
-                    Ok(token)
-                }
-            })
+            }),
+        }
+    }
+}
+
+/// Where the access token comes from (token file or environment), if there is one
+///
+/// Stored tokens are per server, so this is all that can be checked outside a
+/// Bitbucket Data Center project.
+pub fn token_source() -> Option<String> {
+    if let Some(token) = token_from_file(TOKEN_FILE_ENV_VAR) {
+        return token.is_ok().then(|| format!("{} (file)", TOKEN_FILE_ENV_VAR));
     }
+
+    env::var(TOKEN_ENV_VAR)
+        .is_ok_and(|token| !token.is_empty())
+        .then(|| format!("{} (env)", TOKEN_ENV_VAR))
 }
 
 fn derive_api_base_url_from_repo_url(repo_url: &str) -> Option<String> {
@@ -58,14 +98,18 @@ fn derive_api_base_url_from_repo_url(repo_url: &str) -> Option<String> {
     None
 }
 
+/// Base URL, project key and repository slug for the current project
 pub fn get_auth_from_config() -> Result<(String, String, String)> {
-    use crate::bitbucket_data_center_api::extract_bitbucket_data_center_info_from_url;
     use crate::config::GitWorktreeConfig;
-    use crate::github;
 
-    let (_, config) =
-        GitWorktreeConfig::find_config()?.ok_or_else(|| Error::config("No git-worktree-config.jsonc found"))?;
+    let (_, config) = GitWorktreeConfig::find_config()?
+        .ok_or_else(|| Error::config(format!("No {} found", crate::config::config_filename())))?;
+    auth_from_config(&config)
+}
 
+/// `bitbucketBaseUrl`, `bitbucketProjectKey` and `bitbucketRepoSlug` take precedence
+/// over what's parsed from the repository URL; with all three set the URL isn't parsed.
+fn auth_from_config(config: &crate::config::GitWorktreeConfig) -> Result<(String, String, String)> {
     // Check sourceControl field instead of URL pattern
     if config.source_control != "bitbucket-data-center" {
         return Err(Error::provider(format!(
@@ -74,27 +118,42 @@ pub fn get_auth_from_config() -> Result<(String, String, String)> {
         )));
     }
 
-    let repo_url = &config.repository_url;
-
-    // First try to extract from actual Bitbucket Data Center URL
-    if let Some((base_url, project_key, repo_slug)) = extract_bitbucket_data_center_info_from_url(repo_url) {
-        return Ok((base_url, project_key, repo_slug));
+    if let (Some(base_url), Some(project_key), Some(repo_slug)) = (
+        &config.bitbucket_base_url,
+        &config.bitbucket_project_key,
+        &config.bitbucket_repo_slug,
+    ) {
+        return Ok((base_url.clone(), project_key.clone(), repo_slug.clone()));
     }
 
-    // If that fails, try to derive from other URL patterns (like GitHub URLs)
-    if let Some((owner, repo)) = github::GitHubClient::parse_github_url(repo_url) {
-        // For GitHub URLs with bitbucket-data-center config, derive API base URL from the domain
-        if let Some(base_url) = derive_api_base_url_from_repo_url(repo_url) {
-            return Ok((base_url, owner, repo));
-        }
-
-        return Err(Error::provider(format!(
-            "Failed to derive Bitbucket Data Center base URL from: {}",
+    let repo_url = &config.repository_url;
+    let (base_url, project_key, repo_slug) = parse_repository_url(repo_url).ok_or_else(|| {
+        Error::provider(format!(
+            "Failed to parse repository URL: {}\n\
+             Set bitbucketBaseUrl, bitbucketProjectKey and bitbucketRepoSlug with 'gwt config set' instead.",
             repo_url
-        )));
+        ))
+    })?;
+    Ok((
+        config.bitbucket_base_url.clone().unwrap_or(base_url),
+        config.bitbucket_project_key.clone().unwrap_or(project_key),
+        config.bitbucket_repo_slug.clone().unwrap_or(repo_slug),
+    ))
+}
+
+fn parse_repository_url(repo_url: &str) -> Option<(String, String, String)> {
+    use crate::bitbucket_data_center_api::extract_bitbucket_data_center_info_from_url;
+    use crate::github;
+
+    // First try to extract from actual Bitbucket Data Center URL
+    if let Some(info) = extract_bitbucket_data_center_info_from_url(repo_url) {
+        return Some(info);
     }
 
-    Err(Error::provider(format!("Failed to parse repository URL: {}", repo_url)))
+    // If that fails, try to derive from other URL patterns (like GitHub URLs):
+    // the API base URL comes from the domain
+    let (owner, repo) = github::GitHubClient::parse_github_url(repo_url)?;
+    derive_api_base_url_from_repo_url(repo_url).map(|base_url| (base_url, owner, repo))
 }
 
 pub fn display_setup_instructions() {
@@ -110,6 +169,13 @@ pub fn display_setup_instructions() {
     println!("3. Copy the generated token\n");
     println!("4. Set the environment variable:");
     println!("   export {}=YOUR_TOKEN", TOKEN_ENV_VAR);
+    println!(
+        "\n   or point {} at a file containing the token (e.g. a Docker secret)",
+        TOKEN_FILE_ENV_VAR
+    );
+    println!("\n   or, from inside the project, store it in the system keyring:");
+    println!("   gwt auth bitbucket-data-center setup");
+    println!("   or pipe it in: gwt auth bitbucket-data-center setup --token-stdin < token.txt");
     println!("\nExample usage:");
     println!("   curl -H \"Authorization: Bearer ${}\" \\", TOKEN_ENV_VAR);
     println!("        \"https://git.acmeorg.com/rest/api/1.0/projects/PROJECT/repos/REPO/pull-requests\"");
@@ -118,6 +184,7 @@ pub fn display_setup_instructions() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
     fn test_bitbucket_data_center_auth_creation() {
@@ -138,4 +205,72 @@ mod tests {
         );
         assert!(auth.is_ok());
     }
+
+    #[test]
+    #[serial]
+    fn test_token_from_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let token_path = temp_dir.path().join("token");
+        std::fs::write(&token_path, "dc-token\n").unwrap();
+        env::set_var(TOKEN_FILE_ENV_VAR, &token_path);
+
+        let auth = BitbucketDataCenterAuth::new(
+            "PROJ".to_string(),
+            "repo".to_string(),
+            "https://git.example.com".to_string(),
+        )
+        .unwrap();
+        assert_eq!(auth.get_token().unwrap(), "dc-token");
+
+        std::fs::write(&token_path, "  \n").unwrap();
+        assert!(matches!(auth.get_token(), Err(Error::Auth(_))));
+
+        env::remove_var(TOKEN_FILE_ENV_VAR);
+    }
+
+    #[test]
+    fn test_auth_from_config_overrides() {
+        use crate::cli::Provider;
+        use crate::config::GitWorktreeConfig;
+
+        // A mirror URL that matches none of the Data Center patterns
+        let mut config = GitWorktreeConfig::new(
+            "https://mirror.example.com/git/team-repo".to_string(),
+            "main".to_string(),
+            Provider::BitbucketDataCenter,
+            None,
+            None,
+        );
+        assert!(matches!(auth_from_config(&config), Err(Error::Provider(_))));
+
+        config.bitbucket_base_url = Some("https://bitbucket.example.com".to_string());
+        config.bitbucket_project_key = Some("TEAM".to_string());
+        config.bitbucket_repo_slug = Some("repo".to_string());
+        assert_eq!(
+            auth_from_config(&config).unwrap(),
+            (
+                "https://bitbucket.example.com".to_string(),
+                "TEAM".to_string(),
+                "repo".to_string()
+            )
+        );
+
+        // A single field overrides just its part of a parseable URL
+        let mut config = GitWorktreeConfig::new(
+            "https://git.acmeorg.com/scm/PROJ/repo.git".to_string(),
+            "main".to_string(),
+            Provider::BitbucketDataCenter,
+            None,
+            None,
+        );
+        config.bitbucket_project_key = Some("OTHER".to_string());
+        assert_eq!(
+            auth_from_config(&config).unwrap(),
+            (
+                "https://git.acmeorg.com".to_string(),
+                "OTHER".to_string(),
+                "repo".to_string()
+            )
+        );
+    }
 }
@@ -0,0 +1,157 @@
+//! Small on-disk cache for pull request lookups made by `gwt list`
+//!
+//! Entries are stored as JSON under the global config directory, keyed by
+//! repository and branch, and expire after a configurable TTL. The cache is
+//! best-effort: an unreadable or corrupt file is treated as empty and write
+//! failures are ignored.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::GitWorktreeConfig;
+
+/// Default time-to-live for cached PR results
+pub const DEFAULT_TTL_SECS: u64 = 60;
+
+const CACHE_FILENAME: &str = "pull-requests.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    entries: HashMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    cached_at: DateTime<Utc>,
+    value: serde_json::Value,
+}
+
+pub struct PrCache {
+    path: PathBuf,
+    ttl: Duration,
+    file: CacheFile,
+    dirty: bool,
+}
+
+impl PrCache {
+    /// Load the cache from the global config directory
+    ///
+    /// Returns `None` if the config directory can't be determined.
+    pub fn load(ttl_secs: u64) -> Option<Self> {
+        let dir = GitWorktreeConfig::global_config_dir().ok()?.join("cache");
+        Some(Self::load_from(&dir.join(CACHE_FILENAME), ttl_secs))
+    }
+
+    /// Load the cache from a specific file, dropping expired entries
+    pub fn load_from(path: &Path, ttl_secs: u64) -> Self {
+        let ttl = Duration::seconds(i64::try_from(ttl_secs).unwrap_or(i64::MAX / 1000));
+        let mut file: CacheFile = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        let now = Utc::now();
+        let before = file.entries.len();
+        file.entries.retain(|_, entry| now - entry.cached_at < ttl);
+        let dirty = file.entries.len() != before;
+
+        Self {
+            path: path.to_path_buf(),
+            ttl,
+            file,
+            dirty,
+        }
+    }
+
+    /// Cache key for a repository-scoped entry (e.g. `branch:feature/x` or `open`)
+    pub fn key(platform: &str, owner_or_workspace: &str, repo: &str, entry: &str) -> String {
+        format!("{}:{}/{}:{}", platform, owner_or_workspace, repo, entry)
+    }
+
+    /// Get an unexpired value, or `None` if missing, expired, or of the wrong shape
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let entry = self.file.entries.get(key)?;
+        if Utc::now() - entry.cached_at >= self.ttl {
+            return None;
+        }
+        serde_json::from_value(entry.value.clone()).ok()
+    }
+
+    /// Store a value, replacing any existing entry
+    pub fn put<T: Serialize>(&mut self, key: String, value: &T) {
+        if let Ok(value) = serde_json::to_value(value) {
+            self.file.entries.insert(
+                key,
+                CacheEntry {
+                    cached_at: Utc::now(),
+                    value,
+                },
+            );
+            self.dirty = true;
+        }
+    }
+
+    /// Write the cache back to disk if it changed (errors are ignored)
+    pub fn save(&self) {
+        if !self.dirty {
+            return;
+        }
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string(&self.file) {
+            let _ = fs::write(&self.path, json);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_cache_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(CACHE_FILENAME);
+        let key = PrCache::key("github", "owner", "repo", "branch:feature/x");
+
+        let mut cache = PrCache::load_from(&path, 60);
+        cache.put(key.clone(), &Some("https://example.com/pull/1".to_string()));
+        cache.save();
+
+        let cache = PrCache::load_from(&path, 60);
+        assert_eq!(
+            cache.get::<Option<String>>(&key),
+            Some(Some("https://example.com/pull/1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_cache_expired_entries_are_ignored() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(CACHE_FILENAME);
+
+        let mut cache = PrCache::load_from(&path, 0);
+        cache.put("key".to_string(), &1);
+        assert_eq!(cache.get::<i32>("key"), None);
+    }
+
+    #[test]
+    fn test_cache_corrupt_file_is_ignored() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(CACHE_FILENAME);
+        fs::write(&path, "{ not json").unwrap();
+
+        let mut cache = PrCache::load_from(&path, 60);
+        assert_eq!(cache.get::<i32>("key"), None);
+
+        cache.put("key".to_string(), &1);
+        cache.save();
+        assert_eq!(PrCache::load_from(&path, 60).get::<i32>("key"), Some(1));
+    }
+}
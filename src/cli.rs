@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(
@@ -45,19 +46,41 @@ EXAMPLES:
 CONFIG:
   Global: ~/.config/git-worktree-cli/projects/<repo>.jsonc
   Local:  ./git-worktree-config.jsonc (with gwt init --local)
+  Override the global directory with GWT_CONFIG_DIR or XDG_CONFIG_HOME,
+  and the local filename with GWT_CONFIG_FILENAME
 
-  Config supports hooks (postAdd, preRemove, postRemove) that run
-  shell commands automatically. Variables: ${branchName}, ${worktreePath}
+  Config supports hooks (preAdd, postAdd, preRemove, postRemove) that run
+  shell commands automatically. Variables: ${branchName}, ${worktreePath},
+  ${projectRoot}, ${mainBranch}, ${repositoryUrl}, ${sourceControl}
+  Pass --no-hooks to skip them for a single command.
 
 PROVIDERS:
   GitHub (via gh CLI), Bitbucket Cloud, Bitbucket Data Center
+  Gitea / Forgejo (gwt init --provider gitea; Codeberg is detected), with a
+  token from 'gwt auth gitea setup' or GITEA_TOKEN
   Run 'gwt auth <provider>' to set up PR integration for 'gwt list'.",
     disable_version_flag = true
 )]
 pub struct Cli {
-    /// Print version
-    #[arg(short = 'v', long = "version", action = clap::ArgAction::Version)]
-    pub version: (),
+    /// Print version (with --verbose, also the commit, build date and rustc version)
+    #[arg(short = 'v', long = "version")]
+    pub version: bool,
+
+    /// Skip all configured hooks for this invocation
+    #[arg(long, global = true)]
+    pub no_hooks: bool,
+
+    /// Only print errors, warnings, and prompts
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Print each git command before running it
+    #[arg(long, global = true)]
+    pub verbose: bool,
+
+    /// When to use colors (auto honors NO_COLOR and whether stdout is a terminal)
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t)]
+    pub color: ColorMode,
 
     #[command(subcommand)]
     pub command: Option<Commands>,
@@ -77,12 +100,21 @@ pub enum CompletionAction {
         #[arg(value_enum)]
         shell: Option<clap_complete::Shell>,
     },
+    /// Remove completions installed with 'install'
+    Uninstall {
+        /// Shell to remove completions for (auto-detected if not specified)
+        #[arg(value_enum)]
+        shell: Option<clap_complete::Shell>,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum AuthAction {
     /// Authenticate with GitHub
-    Github,
+    Github {
+        #[command(subcommand)]
+        action: Option<GithubAuthAction>,
+    },
     /// Authenticate with Bitbucket Cloud
     BitbucketCloud {
         #[command(subcommand)]
@@ -93,24 +125,163 @@ pub enum AuthAction {
         #[command(subcommand)]
         action: Option<BitbucketDataCenterAuthAction>,
     },
+    /// Authenticate with Gitea or Forgejo
+    Gitea {
+        #[command(subcommand)]
+        action: Option<GiteaAuthAction>,
+    },
+    /// Show which providers have credentials and where they come from
+    Status,
+}
+
+#[derive(Subcommand)]
+pub enum PrAction {
+    /// Create a pull request for the current worktree's branch
+    Create {
+        /// Pull request title (prompted for if omitted)
+        #[arg(long)]
+        title: Option<String>,
+        /// Branch to merge into (defaults to mainBranch from config)
+        #[arg(long)]
+        base: Option<String>,
+        /// Open the pull request as a draft
+        #[arg(long)]
+        draft: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum GithubAuthAction {
+    /// Show setup instructions and store a GitHub token in the keyring
+    Setup {
+        /// Read the token from the first line of stdin instead of prompting for it
+        #[arg(long)]
+        token_stdin: bool,
+    },
+    /// Test the authentication connection
+    Test,
 }
 
 #[derive(Subcommand)]
 pub enum BitbucketCloudAuthAction {
-    /// Show setup instructions
-    Setup,
+    /// Show setup instructions and store an API token for the current repository
+    Setup {
+        /// Read the token from the first line of stdin instead of prompting for it
+        #[arg(long)]
+        token_stdin: bool,
+    },
     /// Test the authentication connection
     Test,
 }
 
 #[derive(Subcommand)]
 pub enum BitbucketDataCenterAuthAction {
-    /// Show setup instructions
-    Setup,
+    /// Show setup instructions and store an access token for the current server
+    Setup {
+        /// Read the token from the first line of stdin instead of prompting for it
+        #[arg(long)]
+        token_stdin: bool,
+    },
     /// Test the authentication connection
     Test,
 }
 
+#[derive(Subcommand)]
+pub enum GiteaAuthAction {
+    /// Show setup instructions and store a Gitea token in the keyring
+    Setup {
+        /// Read the token from the first line of stdin instead of prompting for it
+        #[arg(long)]
+        token_stdin: bool,
+    },
+    /// Test the authentication connection
+    Test,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the value of a config key
+    Get {
+        /// Config key (e.g. mainBranch, sourceControl, bitbucketEmail)
+        key: String,
+    },
+    /// Set a config key (refused when the file has comments, which the rewrite would drop)
+    Set {
+        /// Config key (e.g. mainBranch, sourceControl, bitbucketEmail)
+        key: String,
+        /// New value
+        value: String,
+    },
+    /// Print the path of the config file
+    Path,
+    /// Open the config file in $EDITOR
+    Edit,
+    /// List the configured hooks with their numbers
+    Hooks,
+    /// Append a command to a hook type
+    AddHook {
+        /// Hook type
+        #[arg(value_parser = ["preAdd", "postAdd", "preRemove", "postRemove"])]
+        hook_type: String,
+        /// Shell command (variables like ${branchName} are substituted when it runs)
+        command: String,
+    },
+    /// Remove a hook command by the number shown in 'gwt config hooks'
+    RemoveHook {
+        /// Hook type
+        #[arg(value_parser = ["preAdd", "postAdd", "preRemove", "postRemove"])]
+        hook_type: String,
+        /// Number of the command to remove (starting at 1)
+        index: usize,
+    },
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Color when stdout is a terminal and NO_COLOR is unset
+    #[default]
+    Auto,
+    /// Always color, even when piped
+    Always,
+    /// Never color
+    Never,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SyncStrategy {
+    /// Rebase the branch onto the remote main branch
+    #[default]
+    Rebase,
+    /// Merge the remote main branch into the branch
+    Merge,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListSort {
+    /// Branch name
+    Name,
+    /// PR status (open, draft, merged, closed, none)
+    Status,
+    /// Worktree path (remote-only PRs last)
+    Path,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListGroupBy {
+    /// The branch each worktree's PR merges into
+    Base,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListFilter {
+    /// Only entries with a pull request
+    HasPr,
+    /// Only entries without a pull request
+    NoPr,
+    /// Only entries with a draft pull request
+    Draft,
+}
+
 #[derive(clap::ValueEnum, Clone, Debug)]
 pub enum Provider {
     /// GitHub repository
@@ -119,6 +290,8 @@ pub enum Provider {
     BitbucketCloud,
     /// Bitbucket Data Center repository
     BitbucketDataCenter,
+    /// Gitea or Forgejo repository
+    Gitea,
 }
 
 #[derive(Subcommand)]
@@ -128,38 +301,223 @@ pub enum Commands {
 Initialize git-worktree-cli for an existing repository.
 
 Run this once inside a git repository that has a remote origin. gwt will:
-  - Detect the provider (GitHub, Bitbucket Cloud, Bitbucket Data Center)
+  - Detect the provider (GitHub, Bitbucket Cloud, Codeberg) from the
+    remote's URL, or use the one given with --provider
   - Detect the default branch from the remote
   - Derive the worktrees path (<repo-name>-worktrees/ as a sibling directory)
   - Save configuration globally (~/.config/git-worktree-cli/projects/)
 
 Use --local to save config as git-worktree-config.jsonc next to the repo instead.
+Use --remote <name> when the remote you fetch and push to isn't called origin
+(e.g. upstream on a fork); it is saved as defaultRemote and used by add and status.
 
-The config file can be edited to add hooks (postAdd, preRemove, postRemove)
+Self-hosted providers can't be recognized from the URL alone: pass
+--provider bitbucket-data-center or --provider gitea. Gitea and Forgejo API
+requests go to the repository's host; set giteaBaseUrl with 'gwt config set'
+when the instance is served from somewhere else.
+
+The existing checkout is reused; nothing is cloned. Pass the repository URL to
+verify that the checkout's origin points at the repository you expect.
+
+Use --branch <name> when the integration branch isn't the remote's default (e.g.
+CI merges into release while main is protected); it is recorded as mainBranch
+and must exist on the remote. With --bare, it is the branch checked out.
+
+init refuses to run inside a project or its worktrees, whether its config is
+local (git-worktree-config.jsonc in the current directory or an ancestor) or
+global (its projectPath contains the current directory), since a second
+config would shadow or be shadowed by the first. Pass --force to
+re-initialize that project from its own repository, or --nested to create a
+separate project inside it.
+
+With --bare <url>, the repository is cloned instead into <repo-name>/.bare (a bare
+repository, with a .git file pointing at it) and the main branch is checked out as
+a linked worktree at <repo-name>/<main-branch>/. New worktrees go next to it.
+
+The config file can be edited to add hooks (preAdd, postAdd, preRemove, postRemove)
 that run automatically when creating or removing worktrees.")]
     Init {
+        /// Expected origin URL (init fails if the repository's origin differs)
+        repo_url: Option<String>,
         /// Write config to project directory instead of global location
         #[arg(long)]
         local: bool,
+        /// Remote to detect the provider from and use by default (defaults to origin)
+        #[arg(long, value_name = "NAME")]
+        remote: Option<String>,
+        /// Clone the URL into a bare repository (.bare) with the main branch as a linked worktree
+        #[arg(long, requires = "repo_url")]
+        bare: bool,
+        /// Provider to record instead of detecting it from the URL (needed for self-hosted ones)
+        #[arg(long, value_enum)]
+        provider: Option<Provider>,
+        /// Branch to record as mainBranch instead of the remote's default (must exist on the remote)
+        #[arg(long, value_name = "NAME")]
+        branch: Option<String>,
+        /// Re-initialize this project, overwriting its existing config
+        #[arg(long, conflicts_with = "nested")]
+        force: bool,
+        /// Initialize a separate project nested inside an existing one
+        #[arg(long)]
+        nested: bool,
+    },
+
+    /// Clone a repository and initialize git-worktree-cli for it
+    #[command(long_about = "\
+Clone a repository and initialize git-worktree-cli for it.
+
+Clones <url> into <dir>/<repo-name>/ (the current directory when <dir> is
+omitted; it is created if missing), then writes the same configuration as
+'gwt init' run inside the clone: provider and default branch detected from the
+URL and remote, worktrees under <dir>/<repo-name>-worktrees/.
+
+Use --bare for the layout from 'gwt init --bare' (<repo-name>/.bare plus the
+main branch as a linked worktree), --local to keep the config next to the
+repository, --remote <name> to name the cloned remote something other
+than origin, --branch <name> to check out and record a branch other than the
+remote's default, and --provider for self-hosted Bitbucket Data Center or Gitea.")]
+    Clone {
+        /// Repository URL to clone
+        repo_url: String,
+        /// Parent directory for the clone (created if missing; defaults to the current directory)
+        dir: Option<PathBuf>,
+        /// Write config to project directory instead of global location
+        #[arg(long)]
+        local: bool,
+        /// Name for the cloned remote (defaults to origin)
+        #[arg(long, value_name = "NAME")]
+        remote: Option<String>,
+        /// Clone into a bare repository (.bare) with the main branch as a linked worktree
+        #[arg(long)]
+        bare: bool,
+        /// Provider to record instead of detecting it from the URL (needed for self-hosted ones)
+        #[arg(long, value_enum)]
+        provider: Option<Provider>,
+        /// Branch to check out and record as mainBranch instead of the remote's default
+        #[arg(long, value_name = "NAME")]
+        branch: Option<String>,
     },
 
     /// Add a new worktree for a branch
     #[command(long_about = "\
 Add a new worktree for a branch.
 
-Creates a git worktree at <repo>-worktrees/<branch-name>/. If the branch
-exists locally or on the remote, it checks out that branch. Otherwise,
-creates a new branch from origin/<main-branch>.
-
-The command fetches from origin first to ensure the latest remote state.
-After creating the worktree, any postAdd hooks from the config are executed
-in the new worktree directory.
+Creates a git worktree at <repo>-worktrees/<branch-name>/, or at --path <dir>
+when given (the directory must not exist yet). If the branch exists locally or on the remote, it checks out that branch. Otherwise,
+creates a new branch from origin/<main-branch>, or from --from <ref> when given.
+The remote is origin unless defaultRemote is set in the config; a branch that
+only exists on another remote is checked out from that remote.
+
+Branches checked out from the remote track <remote>/<branch> so ahead/behind is
+reported; new branches are created without an upstream. Use --track or
+--no-track to override.
+
+The command fetches from the remote first to ensure the latest remote state.
+Any preAdd hooks run from the project root before the worktree is created;
+a failing preAdd hook aborts the add. After creating the worktree, any postAdd
+hooks from the config are executed in the new worktree directory.
+
+Use --dry-run to print the target path, the exact git commands, and the hooks
+that would run without fetching or creating anything. A missing --from ref
+still fails.
+
+Use --print-path to print only the new worktree's path on stdout, with all
+other output (including git and hooks) on stderr:
+  cd \"$(gwt add feature/x --print-path)\"
+
+Use --detach <commit> instead of a branch name for a throwaway worktree with
+that commit (or tag/ref) checked out and no branch. The directory is named
+after --name, or the commit's short SHA, and ${branchName} in hooks is that
+name. gwt remove leaves branches alone for detached worktrees.
+
+Use --checkout-pr <n> to review a pull request: its head is fetched from the
+remote and checked out as a detached HEAD in pr-<n>. Add --merge-ref to check
+out GitHub's merge ref (refs/pull/<n>/merge) in pr-<n>-merge instead, the
+result of merging the PR into its base, to test the post-merge state. GitHub
+only publishes it for open PRs without conflicts. Gitea and Bitbucket Data
+Center PR heads can be checked out too, but they (like Bitbucket Cloud) have
+no merge ref, so --merge-ref fails there; Bitbucket Cloud exposes no PR refs.
+
+Use --install-deps to install dependencies in the new worktree before postAdd
+hooks run. The package manager is picked from the files present:
+pnpm-lock.yaml, yarn.lock or package-lock.json (pnpm/yarn/npm install),
+poetry.lock or requirements.txt (poetry install / pip install -r), and
+Cargo.toml (cargo fetch). A failed install is reported but keeps the worktree.
+
+Use --push to push a newly created branch with 'git push -u <remote> <branch>'
+right after the worktree is created (set pushOnAdd to true in the config to do
+this by default, and --no-push to skip it once). Branches that already exist
+locally or on a remote are never pushed. A failed push is reported but keeps
+the worktree.
 
 Branch names can include slashes (e.g., feature/user-auth, bugfix/fix-123).
-The directory structure mirrors the branch name.")]
+The directory structure mirrors the branch name. Names are checked against
+git's rules up front (no spaces, '..', '~', '^', ':' or leading '-'), and a
+pasted refs/heads/ prefix is dropped.")]
     Add {
         /// Branch name (can include slashes like feature/branch-name)
-        branch_name: String,
+        #[arg(required_unless_present_any = ["detach", "checkout_pr"])]
+        branch_name: Option<String>,
+
+        /// Base ref (branch, tag, or commit) for a new branch instead of origin/<main-branch>
+        #[arg(long, value_name = "REF")]
+        from: Option<String>,
+
+        /// Set upstream tracking (default for branches that exist on the remote)
+        #[arg(long, conflicts_with = "no_track")]
+        track: bool,
+
+        /// Don't set upstream tracking (default for new branches)
+        #[arg(long)]
+        no_track: bool,
+
+        /// Create the worktree at this directory instead of under the worktrees path
+        #[arg(long, value_name = "DIR")]
+        path: Option<PathBuf>,
+
+        /// Print the git commands, target path, and hooks without running anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Print only the new worktree's path on stdout (messages go to stderr)
+        #[arg(long, conflicts_with = "dry_run")]
+        print_path: bool,
+
+        /// Check out this commit (or tag/ref) as a detached HEAD instead of a branch
+        #[arg(
+            long,
+            value_name = "COMMIT",
+            conflicts_with_all = ["branch_name", "from", "track", "no_track"]
+        )]
+        detach: Option<String>,
+
+        /// Directory name for a --detach worktree (defaults to the short commit SHA)
+        #[arg(long, requires = "detach")]
+        name: Option<String>,
+
+        /// Check out pull request N as a detached HEAD in pr-<n> for review
+        #[arg(
+            long,
+            value_name = "N",
+            conflicts_with_all = ["branch_name", "from", "track", "no_track", "detach"]
+        )]
+        checkout_pr: Option<u64>,
+
+        /// With --checkout-pr, check out the PR merged into its base (GitHub's merge ref) in pr-<n>-merge
+        #[arg(long, requires = "checkout_pr")]
+        merge_ref: bool,
+
+        /// Install dependencies with the package manager detected in the new worktree
+        #[arg(long)]
+        install_deps: bool,
+
+        /// Push a new branch and set its upstream (git push -u; overrides pushOnAdd)
+        #[arg(long, conflicts_with_all = ["no_push", "detach", "checkout_pr"])]
+        push: bool,
+
+        /// Don't push a new branch (overrides pushOnAdd)
+        #[arg(long, conflicts_with_all = ["detach", "checkout_pr"])]
+        no_push: bool,
     },
 
     /// List all worktrees in the current project
@@ -173,13 +531,120 @@ closed) and PR URLs for each branch.
 Additionally shows open pull requests that have no local worktree,
 making it easy to check out branches that need review.
 
-Use --local to skip fetching remote PR information (faster, offline).
+Use --local to skip the open PRs that have no local worktree (local worktrees
+are still annotated with their PRs), or --remote-only to show just those PRs.
+
+Use --no-pr (or set showPullRequests to false in the config, for teams that
+don't use the PR integration) to skip all PR work: no provider setup, no PR
+lookups for local worktrees, and no open PR list.
+
+Use --offline (or set GWT_OFFLINE=1) to list local worktrees immediately
+without touching the network. Unlike --local, it also skips provider
+authentication and client setup.
+
+Use --with-authors to show who opened each PR next to its status.
+
+Use --show-commit to show the short SHA and subject of each worktree's
+checked-out commit under its branch, read from the worktree itself (useful
+when branch names are terse or PR info is unavailable).
+
+Use --hide-drafts to leave draft PRs out of the open pull requests section
+(its summary line still counts them) and dim worktrees whose PR is a draft.
+--json ignores it and marks each entry with a draft boolean instead.
+
+Use --limit <n> to change how many open PRs without a local worktree are
+shown (30 by default, 0 for all); a \"… and N more\" line counts the rest.
+Only enough PRs to fill the limit are fetched, so the count reads \"at least
+N\" when the provider has more. --json always fetches and includes every PR.
+
+Use --since <when> to only show open PRs without a local worktree that were
+updated on or after a date (YYYY-MM-DD, UTC) or within a duration such as 7d,
+24h or 2w. PRs whose update time is unknown are left out. It applies before
+--limit and to --json; local worktrees are not affected.
+
+Use --json for machine-readable output: a JSON array of objects with
+branch, path, head, bare, draft and pullRequest (url, status, title, author, base). Open PRs
+without a local worktree are included with a null path unless --local
+is given.
+
+PR results are cached for 60 seconds (configurable with prCacheTtlSecs,
+0 disables). Use --refresh to bypass and overwrite the cache.
+
+Use --format <template> for custom shell-friendly output: one line per local
+worktree (open PRs without a worktree are not included), no colors or
+headers. Placeholders: {branch}, {path}, {head}, {pr_url}, {pr_status},
+{pr_title}; missing values are empty, \\t and \\n are a tab and a newline, and
+anything else is printed as-is.
+
+Use --sort name|status|path to order entries (status groups open, draft,
+merged, closed, then branches without a PR) and --filter has-pr|no-pr|draft
+to narrow them. Both apply to remote-only PRs and to --json output.
+
+Use --stale <days> to mark worktrees whose last commit is at least that old
+(\"⏳ stale (N days)\", staleDays in --json); they are listed after the rest.
+
+Use --group-by base to list local worktrees under a heading for the branch
+their PR merges into (handy with stacked PRs), bases in alphabetical order,
+with worktrees without a PR in an \"Ungrouped\" section at the end. Each PR's
+base is also in --json as pullRequest.base.
 
 Can be run from the main repository or from any worktree directory.")]
     List {
-        /// Show only local worktrees (skip remote PRs)
+        /// Skip open PRs without a local worktree (local worktrees still show their PRs)
         #[arg(short, long)]
         local: bool,
+        /// Show only open PRs without a local worktree (skip local worktrees)
+        #[arg(long, conflicts_with = "local")]
+        remote_only: bool,
+        /// Output as JSON (for scripting)
+        #[arg(long)]
+        json: bool,
+        /// Print only PR URLs, one per line (e.g. for `xargs open`)
+        #[arg(long, conflicts_with = "json")]
+        urls_only: bool,
+        /// Print one line per local worktree from a template, e.g. '{branch}\t{pr_status}\t{pr_url}'
+        #[arg(
+            long,
+            value_name = "TEMPLATE",
+            conflicts_with_all = ["json", "urls_only", "group_by", "remote_only"]
+        )]
+        format: Option<String>,
+        /// Ignore cached PR results and fetch fresh ones
+        #[arg(long)]
+        refresh: bool,
+        /// Sort entries by branch name, PR status, or worktree path
+        #[arg(long, value_enum)]
+        sort: Option<ListSort>,
+        /// Only show entries with a PR, without a PR, or with a draft PR
+        #[arg(long, value_enum)]
+        filter: Option<ListFilter>,
+        /// Group local worktrees under headings by their PR's base branch
+        #[arg(long, value_enum, value_name = "KEY", conflicts_with_all = ["json", "urls_only"])]
+        group_by: Option<ListGroupBy>,
+        /// Mark worktrees whose last commit is at least this many days old (listed last)
+        #[arg(long, value_name = "DAYS")]
+        stale: Option<u64>,
+        /// Show who opened each PR
+        #[arg(long)]
+        with_authors: bool,
+        /// Show the subject of each worktree's last commit under its branch
+        #[arg(long)]
+        show_commit: bool,
+        /// Leave draft PRs out of the open pull requests and dim worktrees whose PR is a draft
+        #[arg(long)]
+        hide_drafts: bool,
+        /// Skip provider setup and PR lookups entirely (also GWT_OFFLINE=1)
+        #[arg(long)]
+        offline: bool,
+        /// Skip all PR work: no provider setup, PR lookups or open PR list (also showPullRequests: false)
+        #[arg(long, conflicts_with = "remote_only")]
+        no_pr: bool,
+        /// Show at most this many open PRs without a local worktree (0 for all)
+        #[arg(long, value_name = "N", default_value_t = 30)]
+        limit: usize,
+        /// Only show open PRs without a local worktree updated since a date (YYYY-MM-DD) or for a duration (7d, 24h)
+        #[arg(long, value_name = "WHEN")]
+        since: Option<String>,
     },
 
     /// Remove a worktree
@@ -187,26 +652,238 @@ Can be run from the main repository or from any worktree directory.")]
 Remove a worktree.
 
 Removes the worktree directory and deletes the branch (unless it is a
-protected branch: main, master, dev, develop). Asks for confirmation
-before proceeding unless --force is used.
+protected branch: main, master, dev, develop, or the names and globs like
+release/* listed in protectedBranches, plus the configured mainBranch).
+Asks for confirmation before proceeding unless --force is used.
+
+Set deleteBranchOnRemove to false in the config to keep branches by default.
+--keep-branch and --delete-branch override the config for one command.
 
 If the branch has unmerged changes, asks again before force-deleting
-the branch. Use --force to skip all confirmation prompts.
+the branch. Use -y/--yes to skip all confirmation prompts; unmerged branches
+are then kept. --force also skips them, force-deletes unmerged branches, and
+removes locked worktrees.
+
+If git can't remove the directory (e.g. it contains submodules) or leaves it
+behind, its contents are listed and you are asked whether to delete it. With
+--prune-dir it is deleted without asking; --yes or --force alone leaves it in
+place.
 
 If no branch name is given, removes the worktree for the current
 directory. Also handles orphaned worktrees with stale git references.
 
-Runs preRemove hooks before removal and postRemove hooks after.
+Several branch names remove several worktrees, listed and confirmed once.
+Names that can't be removed (unknown, orphaned, or locked without --force)
+are reported and skipped; the rest are removed, and the command exits with
+an error if anything failed.
+
+Runs preRemove hooks before removal and postRemove hooks after. A failing
+preRemove hook aborts the removal.
 
-NOTE: --force is required for non-interactive (AI agent) usage.")]
+Use --all-merged to remove every worktree whose branch is fully merged into
+the main branch (protected branches and the worktree you are in are skipped).
+Everything is listed and confirmed once; with --dry-run, the list and the
+branches that would be deleted are printed without prompting.
+
+Use --dry-run to show the worktree, whether its branch would be deleted, and
+the hooks that would run, without removing anything.
+
+NOTE: -y/--yes (or --force) is required for non-interactive (AI agent) usage.")]
     Remove {
-        /// Branch name to remove (current worktree if not specified)
+        /// Branch names to remove (current worktree if none given)
+        branch_names: Vec<String>,
+        /// Skip prompts, force-delete unmerged branches, and remove locked worktrees
+        #[arg(short, long)]
+        force: bool,
+        /// Skip confirmation prompts (unmerged branches are kept)
+        #[arg(short, long)]
+        yes: bool,
+        /// Remove every worktree whose branch is merged into the main branch
+        #[arg(long, conflicts_with = "branch_names")]
+        all_merged: bool,
+        /// Show what would be removed and which hooks would run, without removing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Keep the branch (overrides deleteBranchOnRemove)
+        #[arg(long, conflicts_with = "delete_branch")]
+        keep_branch: bool,
+        /// Delete the branch (overrides deleteBranchOnRemove)
+        #[arg(long)]
+        delete_branch: bool,
+        /// Delete the worktree directory if git can't remove it (submodules, leftover build files)
+        #[arg(long)]
+        prune_dir: bool,
+    },
+
+    /// Lock a worktree so it can't be pruned or removed by accident
+    #[command(long_about = "\
+Lock a worktree so it can't be pruned or removed by accident.
+
+Runs 'git worktree lock'. 'gwt remove' refuses to remove a locked worktree
+(showing the reason) unless --force is given, 'gwt remove --all-merged' skips
+it, and git won't prune it. Locks the current worktree if no branch is given.")]
+    Lock {
+        /// Branch name of the worktree to lock (current worktree if not specified)
+        branch_name: Option<String>,
+        /// Why the worktree is locked (shown when removal is refused)
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Unlock a worktree locked with 'gwt lock'
+    Unlock {
+        /// Branch name of the worktree to unlock (current worktree if not specified)
         branch_name: Option<String>,
-        /// Skip confirmation prompts
+    },
+
+    /// Rename a branch and move its worktree directory
+    #[command(long_about = "\
+Rename a branch and move its worktree directory.
+
+Runs 'git branch -m <old> <new>' and then 'git worktree move' so the worktree
+directory matches the new branch name (following the configured layout).
+Uncommitted changes in the worktree are kept.
+
+Protected branches (main, master, dev, develop, or the protectedBranches
+config) cannot be renamed, and the command fails if the new worktree directory already exists.")]
+    Rename {
+        /// Current branch name
+        old_branch: String,
+        /// New branch name
+        new_branch: String,
+    },
+
+    /// Move a worktree to another directory
+    #[command(long_about = "\
+Move a worktree to another directory, e.g. onto a bigger disk.
+
+Runs 'git worktree move', keeping the branch, uncommitted changes, and
+untracked files. If <new-path> is an existing directory the worktree is
+moved inside it, like mv. Missing parent directories are created.
+
+The main repository can't be moved, and a locked worktree has to be
+unlocked first ('gwt unlock <branch>'). git also refuses to move
+worktrees that contain submodules.")]
+    Move {
+        /// Branch name of the worktree to move
+        branch_name: String,
+        /// Where to move the worktree
+        new_path: PathBuf,
+    },
+
+    /// Show uncommitted changes and ahead/behind state for each worktree
+    #[command(long_about = "\
+Show uncommitted changes and ahead/behind state for each worktree.
+
+For every worktree, prints the branch, the number of modified and untracked
+files, and how many commits it is ahead (↑) and behind (↓) its upstream.
+Branches without an upstream show a dash.
+
+Use --fetch to fetch from the configured remote (defaultRemote, origin by
+default) first so ahead/behind counts are current.")]
+    Status {
+        /// Fetch from the configured remote before computing ahead/behind
+        #[arg(long)]
+        fetch: bool,
+    },
+
+    /// Rebase or merge the current branch onto the updated main branch
+    #[command(long_about = "\
+Rebase or merge the current worktree's branch onto the updated main branch.
+
+Fetches the configured remote (origin unless defaultRemote is set), then runs
+'git rebase <remote>/<main-branch>' in the current worktree, or 'git merge'
+with --strategy merge.
+
+Refuses to run when the worktree has uncommitted changes unless --autostash is
+given, which stashes them around the rebase or merge. If git stops on
+conflicts, resolve them, run 'git rebase --continue' (or 'git merge
+--continue'), and re-run gwt sync.")]
+    Sync {
+        /// How to bring in the main branch's changes
+        #[arg(long, value_enum, default_value_t)]
+        strategy: SyncStrategy,
+        /// Stash uncommitted changes before syncing and restore them afterwards
+        #[arg(long)]
+        autostash: bool,
+    },
+
+    /// Fetch all remotes once for every worktree
+    #[command(long_about = "\
+Fetch all remotes once for every worktree.
+
+Worktrees share the repository's object store, so fetching in any one of them
+updates all of them. This runs 'git fetch --all' from a valid worktree and
+reports how many remote-tracking refs changed.
+
+Use --prune to also delete remote-tracking refs for branches removed on the remote.")]
+    Fetch {
+        /// Remove remote-tracking refs that no longer exist on the remote
+        #[arg(long)]
+        prune: bool,
+    },
+
+    /// Prune stale worktrees whose directories were deleted
+    #[command(long_about = "\
+Prune stale worktrees whose directories were deleted.
+
+If a worktree directory is removed manually (e.g. with rm -rf), git still
+tracks it as a prunable worktree. This command lists those entries, runs
+'git worktree prune' to clean up the stale references, and offers to
+delete branches that no longer have any worktree.
+
+Use --dry-run to only report what would be pruned, or -y/--yes to delete
+those branches without asking. Branches with unmerged work are deleted only
+with --force (after asking, unless --yes is also given); otherwise they are
+kept.")]
+    Prune {
+        /// Only report what would be pruned
+        #[arg(long)]
+        dry_run: bool,
+        /// Delete branches left without a worktree without asking (unmerged ones are kept)
+        #[arg(short, long)]
+        yes: bool,
+        /// Also delete branches with unmerged work
         #[arg(short, long)]
         force: bool,
     },
 
+    /// Work with pull requests for the current worktree
+    #[command(long_about = "\
+Work with pull requests for the current worktree.
+
+'gwt pr create' opens a pull request for the branch checked out in the
+current worktree, targeting --base (mainBranch from config by default).
+The title is prompted for unless --title is given; --draft opens it as a
+draft. Push the branch first.
+
+Currently supported for GitHub (via the gh CLI).")]
+    Pr {
+        #[command(subcommand)]
+        action: PrAction,
+    },
+
+    /// Diagnose common setup problems
+    #[command(long_about = "\
+Diagnose common setup problems.
+
+Checks that git is installed, the project config can be found and parsed,
+the provider CLI and credentials are available for the configured source
+control, and every worktree git knows about still has its directory.
+
+Prints a ✓ or ✗ per check with a hint for fixing failures. Exits non-zero
+when a critical check (git, config, project) fails.")]
+    Doctor,
+
+    /// Print version information
+    #[command(long_about = "\
+Print version information.
+
+Prints the same 'gwt <version>' line as --version. With --verbose, also
+prints the git commit, build date and rustc version gwt was built from,
+which helps in bug reports.")]
+    Version,
+
     /// Manage authentication for external services
     #[command(long_about = "\
 Manage authentication for external services.
@@ -214,12 +891,23 @@ Manage authentication for external services.
 Authentication enables PR status display in 'gwt list'. Each provider
 has its own setup flow:
 
-  github                - Uses the gh CLI. Run 'gh auth login' to set up.
+  github                - Uses the gh CLI ('gh auth login'), or a token in the
+                          system keychain / GITHUB_TOKEN when gh isn't available.
   bitbucket-cloud       - Uses app passwords stored in the system keychain.
   bitbucket-data-center - Uses personal access tokens in the system keychain.
-
-Use 'gwt auth <provider> setup' for setup instructions and
-'gwt auth <provider> test' to verify the connection.")]
+  gitea                 - Uses an access token in the system keychain or GITEA_TOKEN
+                          (also works for Forgejo and Codeberg).
+
+'gwt auth <provider> setup' shows setup instructions and prompts for a
+token without echoing it; scripts can pipe one in with --token-stdin. Tokens
+go to the system keychain. Where there is none (e.g. headless CI without a
+secret service), they go to a file readable only by you under
+~/.config/git-worktree-cli/tokens/ instead. Set GWT_TOKEN_STORE=file or
+GWT_TOKEN_STORE=keyring to always use one of them.
+
+Use 'gwt auth <provider> test' to verify the connection. 'gwt auth status'
+shows which providers have credentials (gh, environment, token file, or
+keyring), highlighting the one the current project uses.")]
     Auth {
         #[command(subcommand)]
         action: AuthAction,
@@ -232,7 +920,8 @@ Generate or install shell completions.
 Without a subcommand, checks whether completions are installed for
 your current shell. Supports bash, zsh, fish, powershell, and elvish.
 
-Use 'gwt completions install' to auto-install for your detected shell.
+Use 'gwt completions install' to auto-install for your detected shell, and
+'gwt completions uninstall' to remove what it added.
 Use 'gwt completions generate <shell>' to output the completion script
 to stdout (useful for piping or manual installation).")]
     Completions {
@@ -241,14 +930,55 @@ to stdout (useful for piping or manual installation).")]
         action: Option<CompletionAction>,
     },
 
-    /// Open the project config file
+    /// List the projects initialized with a global config
+    #[command(long_about = "\
+List the projects initialized with a global config.
+
+Reads every config in ~/.config/git-worktree-cli/projects/ (or under
+GWT_CONFIG_DIR) and shows each project's repository URL, main branch, and
+whether its recorded projectPath still exists on disk. Projects initialized
+with 'gwt init --local' keep their config next to the repository and are
+not listed.
+
+Use --prune to remove the configs of projects whose projectPath no longer
+exists. It asks for confirmation first (-y/--yes skips it), since a path on
+an unmounted drive looks missing too, and renames each config to
+<name>.jsonc.bak instead of deleting it; rename it back to restore the project.")]
+    Projects {
+        /// Remove configs whose projectPath no longer exists (kept as <name>.jsonc.bak)
+        #[arg(long)]
+        prune: bool,
+        /// Prune without asking for confirmation
+        #[arg(short, long, requires = "prune")]
+        yes: bool,
+    },
+
+    /// View or edit the project config
     #[command(long_about = "\
-Open the project config file in the default application.
+View or edit the project config.
 
-Finds the config file (local or global) for the current repository
-and opens it with the system default application (e.g., your text editor).
+Finds the config file (local or global) for the current repository.
+Without a subcommand, opens it with the system default application.
+
+  gwt config get <key>          Print a value
+  gwt config set <key> <value>  Change a value (validated before saving)
+  gwt config path               Print the config file location
+  gwt config edit               Open the config file in $EDITOR
+
+Keys: repositoryUrl, mainBranch, sourceControl, projectPath, worktreesPath,
+worktreeLayout, bitbucketEmail, bitbucketApiBase, bitbucketBaseUrl, bitbucketProjectKey,
+bitbucketRepoSlug, giteaBaseUrl, defaultRemote, hooksTimeoutSecs, hooksFailFast,
+hookEnvFile, prCacheTtlSecs, deleteBranchOnRemove, pushOnAdd, initSubmodules,
+showPullRequests
+
+'set', 'add-hook' and 'remove-hook' rewrite the whole file as plain JSON, so
+they refuse to touch a config that contains comments; use 'gwt config edit'
+for those.
 
 Local config:  ./git-worktree-config.jsonc
 Global config: ~/.config/git-worktree-cli/projects/<repo>.jsonc")]
-    Config,
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
 }
@@ -1,161 +1,1049 @@
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
 
-use crate::config::GitWorktreeConfig;
-use crate::core::project::{find_existing_worktree, find_project_root};
+use crate::config::{GitWorktreeConfig, WorktreeLayout};
+use crate::constants::DEFAULT_REMOTE;
+use crate::core::project::{clean_branch_name, Project};
 use crate::error::{Error, Result};
-use crate::git;
+use crate::git::{self, WorktreeAddOptions};
 use crate::hooks;
+use crate::{message, output, status};
+
+/// Flags of `gwt add` shared by [`run`], [`run_detached`] and [`run_pull_request`]
+///
+/// `from`, `track` and `push` only apply to branches; clap rejects them with `--detach`
+/// and `--checkout-pr`.
+#[derive(Debug, Default)]
+pub struct AddOptions {
+    /// Base ref for a new branch instead of `<remote>/<main-branch>`
+    pub from: Option<String>,
+    /// Overrides upstream tracking: by default branches checked out from the remote
+    /// track it and brand-new branches don't
+    pub track: Option<bool>,
+    /// Explicit location instead of under the worktrees path
+    pub path: Option<PathBuf>,
+    /// Print the plan; nothing is fetched, created, or run
+    pub dry_run: bool,
+    /// Stdout carries only the new worktree's path; everything else goes to stderr
+    pub print_path: bool,
+    /// Install dependencies with the detected package manager before postAdd hooks
+    pub install_deps: bool,
+    /// Overrides `pushOnAdd`: whether a brand-new branch is pushed with `git push -u`
+    pub push: Option<bool>,
+}
+
+/// Create a worktree for `branch_name`
+pub fn run(branch_name: &str, options: &AddOptions) -> Result<()> {
+    let AddOptions {
+        track,
+        dry_run,
+        print_path,
+        install_deps,
+        push,
+        ..
+    } = *options;
+    let from = options.from.as_deref();
+    let path = options.path.as_deref();
+    if print_path {
+        output::reserve_stdout();
+    }
 
-pub fn run(branch_name: &str) -> Result<()> {
     if branch_name.is_empty() {
-        return Err(Error::msg(
-            "Error: Branch name is required\nUsage: gwt add <branch-name>",
-        ));
+        return Err(Error::msg("Branch name is required\nUsage: gwt add <branch-name>"));
     }
 
+    // Accept names pasted as full refs (refs/heads/feature/x)
+    let branch_name = clean_branch_name(branch_name);
+    git::validate_branch_name(branch_name)?;
+
     // Determine git root and target path
-    let (git_working_dir, target_path, project_root) = determine_paths(branch_name)?;
+    let project = Project::find()?;
+    let project_root = project.root.clone();
+    let git_working_dir = project.bare_repo_dir()?;
+    ensure_not_checked_out(&git_working_dir, branch_name)?;
+    let target_path = determine_target_path(&project_root, branch_name, path, dry_run)?;
+    let hook_variables = [
+        ("branchName", branch_name),
+        ("worktreePath", target_path.to_str().unwrap()),
+        ("projectRoot", project_root.to_str().unwrap()),
+    ];
+    let push = match push {
+        Some(push) => push,
+        None => GitWorktreeConfig::find_config()?.is_some_and(|(_, config)| config.push_on_add()),
+    };
 
-    println!(
+    if dry_run {
+        print_dry_run(
+            &git_working_dir,
+            &target_path,
+            &project_root,
+            branch_name,
+            options,
+            push,
+            &hook_variables,
+        )?;
+        if install_deps {
+            print_planned_install(&git_working_dir);
+        }
+        return Ok(());
+    }
+
+    status!(
         "{}",
         format!("Preparing worktree (new branch '{}')", branch_name).cyan()
     );
 
-    // Get main branch from config
-    let main_branch = get_main_branch(&project_root)?;
+    // Execute pre-add hooks before anything is created (a failure aborts the add)
+    hooks::execute_hooks("preAdd", &project_root, &hook_variables)?;
 
-    // Fetch latest changes from origin to ensure we have the latest remote state
-    println!("{}", "Fetching latest changes from origin...".cyan());
-    git::execute_streaming(&["fetch", "origin"], Some(&git_working_dir))?;
+    // Get main branch and remote from config
+    let (main_branch, remote) = main_branch_and_remote(&project_root)?;
 
-    // Check if branch exists locally or remotely
-    let (local_exists, remote_exists) = git::branch_exists(&git_working_dir, branch_name)?;
+    // Fetch latest changes from the remote to ensure we have the latest remote state
+    status!("{}", format!("Fetching latest changes from {}...", remote).cyan());
+    git::execute_streaming(&["fetch", &remote], Some(&git_working_dir))?;
 
-    // Create worktree based on branch existence
-    if local_exists {
+    let plan = plan_worktree_add(
+        &git_working_dir,
+        &target_path,
+        branch_name,
+        from,
+        track,
+        &main_branch,
+        &remote,
+    )?;
+    status!("{}", plan.description);
+    plan.execute(&git_working_dir)?;
+
+    // Success messages
+    status!(
+        "{}",
+        format!("✓ Worktree created at: {}", target_path.display()).green()
+    );
+    status!("{}", format!("✓ Branch: {}", branch_name).green());
+
+    if push {
+        if plan.creates_branch {
+            push_new_branch(&target_path, &remote, branch_name);
+        } else {
+            status!(
+                "{}",
+                format!("Branch '{}' already existed, not pushing it", branch_name).dimmed()
+            );
+        }
+    }
+
+    finish_add(
+        &git_working_dir,
+        &target_path,
+        &project_root,
+        &main_branch,
+        &hook_variables,
+        print_path,
+        install_deps,
+    )
+}
+
+/// Create a worktree with `commit` checked out as a detached HEAD, without a branch
+///
+/// The directory is named `name`, or the commit's short SHA when it isn't given, and
+/// that name is also the `branchName` hook variable.
+pub fn run_detached(commit: &str, name: Option<&str>, options: &AddOptions) -> Result<()> {
+    let AddOptions {
+        dry_run,
+        print_path,
+        install_deps,
+        ..
+    } = *options;
+    let path = options.path.as_deref();
+    if print_path {
+        output::reserve_stdout();
+    }
+
+    let project = Project::find()?;
+    let project_root = project.root.clone();
+    let git_working_dir = project.bare_repo_dir()?;
+    let (main_branch, remote) = main_branch_and_remote(&project_root)?;
+
+    // Fetch first so refs like origin/main resolve to the latest commit
+    if !dry_run {
+        status!("{}", format!("Fetching latest changes from {}...", remote).cyan());
+        git::execute_streaming(&["fetch", &remote], Some(&git_working_dir))?;
+    }
+    let short_sha = git::short_commit(&git_working_dir, commit).ok_or_else(|| {
+        Error::git(format!(
+            "Commit '{}' does not exist (check the name or fetch it first)",
+            commit
+        ))
+    })?;
+
+    let name = name.unwrap_or(&short_sha);
+    validate_worktree_name(name)?;
+    let target_path = determine_target_path(&project_root, name, path, dry_run)?;
+    let hook_variables = [
+        ("branchName", name),
+        ("worktreePath", target_path.to_str().unwrap()),
+        ("projectRoot", project_root.to_str().unwrap()),
+    ];
+
+    let plan = AddPlan::new(
+        format!("Checking out '{}' ({}) as a detached HEAD...", commit, short_sha).cyan(),
+        WorktreeAddOptions {
+            path: target_path.clone(),
+            start_point: Some(commit.to_string()),
+            detach: true,
+            ..Default::default()
+        },
+    );
+
+    if dry_run {
+        print_plan(
+            &target_path,
+            ("Commit", format!("{} (detached)", short_sha).green()),
+            &plan,
+            &remote,
+            &hook_variables,
+        )?;
         println!(
             "{}",
+            format!("(refs resolved as of the last fetch from {})", remote).dimmed()
+        );
+        if install_deps {
+            print_planned_install(&git_working_dir);
+        }
+        return Ok(());
+    }
+
+    // Execute pre-add hooks before anything is created (a failure aborts the add)
+    hooks::execute_hooks("preAdd", &project_root, &hook_variables)?;
+
+    status!("{}", plan.description);
+    plan.execute(&git_working_dir)?;
+
+    status!(
+        "{}",
+        format!("✓ Worktree created at: {}", target_path.display()).green()
+    );
+    status!("{}", format!("✓ Detached HEAD at: {}", short_sha).green());
+
+    finish_add(
+        &git_working_dir,
+        &target_path,
+        &project_root,
+        &main_branch,
+        &hook_variables,
+        print_path,
+        install_deps,
+    )
+}
+
+/// Create a detached worktree at pull request `number` for review (`--checkout-pr`)
+///
+/// The PR's head is fetched from the remote, or with `merge_ref` the preview of it merged
+/// into its base (GitHub's `pull/<n>/merge`). The directory is named `pr-<n>` (or
+/// `pr-<n>-merge`), which is also the `branchName` hook variable.
+pub fn run_pull_request(number: u64, merge_ref: bool, options: &AddOptions) -> Result<()> {
+    let AddOptions {
+        dry_run,
+        print_path,
+        install_deps,
+        ..
+    } = *options;
+    let path = options.path.as_deref();
+    if print_path {
+        output::reserve_stdout();
+    }
+
+    let project = Project::find()?;
+    let project_root = project.root.clone();
+    let git_working_dir = project.bare_repo_dir()?;
+    let (main_branch, remote) = main_branch_and_remote(&project_root)?;
+    let (_, config) = GitWorktreeConfig::find_config()?
+        .ok_or_else(|| Error::config("Config not found. Run 'gwt init' to set up the provider first."))?;
+    let refspec = pull_request_ref(&config.source_control, number, merge_ref)?;
+
+    let name = pull_request_dir_name(number, merge_ref);
+    let target_path = determine_target_path(&project_root, &name, path, dry_run)?;
+    let hook_variables = [
+        ("branchName", name.as_str()),
+        ("worktreePath", target_path.to_str().unwrap()),
+        ("projectRoot", project_root.to_str().unwrap()),
+    ];
+    let kind = if merge_ref { "merge ref" } else { "head" };
+
+    if dry_run {
+        let plan = AddPlan::new(
+            format!("Checking out PR #{} ({}) as a detached HEAD...", number, kind).cyan(),
+            WorktreeAddOptions {
+                path: target_path.clone(),
+                start_point: Some("FETCH_HEAD".to_string()),
+                detach: true,
+                ..Default::default()
+            },
+        );
+        print_plan(
+            &target_path,
+            ("Pull request", format!("#{} ({}, detached)", number, kind).green()),
+            &plan,
+            &format!("{} {}", remote, refspec),
+            &hook_variables,
+        )?;
+        if install_deps {
+            print_planned_install(&git_working_dir);
+        }
+        return Ok(());
+    }
+
+    status!("{}", format!("Fetching {} from {}...", refspec, remote).cyan());
+    git::execute_streaming(&["fetch", &remote, &refspec], Some(&git_working_dir)).map_err(|e| {
+        let hint = if merge_ref {
+            " (GitHub only publishes a merge ref for open PRs without conflicts)"
+        } else {
+            ""
+        };
+        let reason = match e {
+            Error::Git(reason) => reason,
+            other => other.to_string(),
+        };
+        Error::git(format!(
+            "Could not fetch {} from {} ({}): check that PR #{} exists{}",
+            refspec, remote, reason, number, hint
+        ))
+    })?;
+    // Pin the full SHA right away: FETCH_HEAD moves with the next fetch (e.g. in a hook)
+    let sha = git::commit_sha(&git_working_dir, "FETCH_HEAD")
+        .ok_or_else(|| Error::git(format!("Fetching {} did not return a commit", refspec)))?;
+    let short_sha = git::short_commit(&git_working_dir, &sha).unwrap_or_else(|| sha.clone());
+
+    let plan = AddPlan::new(
+        format!(
+            "Checking out PR #{} ({}, {}) as a detached HEAD...",
+            number, kind, short_sha
+        )
+        .cyan(),
+        WorktreeAddOptions {
+            path: target_path.clone(),
+            start_point: Some(sha),
+            detach: true,
+            ..Default::default()
+        },
+    );
+
+    // Execute pre-add hooks before anything is created (a failure aborts the add)
+    hooks::execute_hooks("preAdd", &project_root, &hook_variables)?;
+
+    status!("{}", plan.description);
+    plan.execute(&git_working_dir)?;
+
+    status!(
+        "{}",
+        format!("✓ Worktree created at: {}", target_path.display()).green()
+    );
+    status!("{}", format!("✓ Detached HEAD at: {}", short_sha).green());
+
+    finish_add(
+        &git_working_dir,
+        &target_path,
+        &project_root,
+        &main_branch,
+        &hook_variables,
+        print_path,
+        install_deps,
+    )
+}
+
+/// The ref to fetch for pull request `number` from a `sourceControl` provider
+///
+/// GitHub publishes both a PR's head and its merge preview; Gitea and Bitbucket Data
+/// Center only a head. Bitbucket Cloud doesn't expose pull request refs at all.
+fn pull_request_ref(source_control: &str, number: u64, merge_ref: bool) -> Result<String> {
+    match (source_control, merge_ref) {
+        ("github", false) => Ok(format!("pull/{}/head", number)),
+        ("github", true) => Ok(format!("pull/{}/merge", number)),
+        ("gitea", false) => Ok(format!("pull/{}/head", number)),
+        ("bitbucket-data-center", false) => Ok(format!("refs/pull-requests/{}/from", number)),
+        ("bitbucket-cloud", _) => Err(Error::provider(
+            "Bitbucket Cloud doesn't expose pull request refs; check out the PR's source branch with 'gwt add <branch>'",
+        )),
+        (_, true) => Err(Error::provider(format!(
+            "--merge-ref is only supported on GitHub: {} has no merge ref for pull requests \
+             (check out the head with --checkout-pr {} and merge the base branch locally)",
+            source_control, number
+        ))),
+        (_, false) => Err(Error::provider(format!(
+            "Checking out pull requests is not supported for '{}'",
+            source_control
+        ))),
+    }
+}
+
+/// Directory name for a `--checkout-pr` worktree: `pr-<n>`, or `pr-<n>-merge` for the merge ref
+fn pull_request_dir_name(number: u64, merge_ref: bool) -> String {
+    if merge_ref {
+        format!("pr-{}-merge", number)
+    } else {
+        format!("pr-{}", number)
+    }
+}
+
+/// A `--name` for a detached worktree must stay inside the worktrees path
+fn validate_worktree_name(name: &str) -> Result<()> {
+    let path = Path::new(name);
+    if name.is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(Error::msg(format!(
+            "'{}' is not a valid worktree name: use a relative path without '.' or '..'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Initialize submodules (`initSubmodules`), copy `copyOnAdd` files, install dependencies
+/// for `--install-deps`, run the postAdd hooks, and print the path for `--print-path`
+fn finish_add(
+    git_working_dir: &Path,
+    target_path: &Path,
+    project_root: &Path,
+    main_branch: &str,
+    hook_variables: &[(&str, &str)],
+    print_path: bool,
+    install_deps: bool,
+) -> Result<()> {
+    let config = GitWorktreeConfig::find_config()?.map(|(_, config)| config);
+
+    // git worktree add leaves submodule directories empty, which breaks builds in postAdd hooks
+    if target_path.join(".gitmodules").is_file() {
+        if config.as_ref().is_some_and(GitWorktreeConfig::init_submodules) {
+            init_submodules(target_path);
+        } else {
+            status!(
+                "{}",
+                "Tip: this repository has submodules; set initSubmodules to true to initialize them in new worktrees"
+                    .dimmed()
+            );
+        }
+    }
+
+    // Copy untracked setup files (copyOnAdd) before postAdd hooks so they can rely on them
+    if let Some(patterns) = config.and_then(|config| config.copy_on_add) {
+        let source_dir = main_worktree_path(git_working_dir, main_branch).unwrap_or_else(|| project_root.to_path_buf());
+        copy_on_add(&patterns, &source_dir, target_path)?;
+    }
+
+    if install_deps {
+        install_dependencies(target_path);
+    }
+
+    // Execute post-add hooks
+    hooks::execute_hooks("postAdd", target_path, hook_variables)?;
+
+    if print_path {
+        println!("{}", target_path.display());
+    }
+
+    Ok(())
+}
+
+/// Initialize and update the new worktree's submodules, streaming git's output
+///
+/// Like a failed push, a failure is reported without undoing the add.
+fn init_submodules(worktree_path: &Path) {
+    status!("{}", "Initializing submodules...".cyan());
+    match git::execute_streaming(&["submodule", "update", "--init", "--recursive"], Some(worktree_path)) {
+        Ok(()) => status!("{}", "✓ Submodules initialized".green()),
+        Err(e) => message!(
+            "{}",
+            format!(
+                "⚠️  Submodule update failed ({}); retry in the worktree with: git submodule update --init --recursive",
+                e
+            )
+            .yellow()
+        ),
+    }
+}
+
+/// Push a brand-new branch from its worktree and set the upstream, streaming git's output
+///
+/// The worktree already exists at this point, so a failed push (e.g. no network) is
+/// reported but doesn't fail the add.
+fn push_new_branch(worktree_path: &Path, remote: &str, branch_name: &str) {
+    status!("{}", format!("Pushing '{}' to {}...", branch_name, remote).cyan());
+    match git::execute_streaming(&["push", "-u", remote, branch_name], Some(worktree_path)) {
+        Ok(()) => status!("{}", format!("✓ Pushed, tracking {}/{}", remote, branch_name).green()),
+        Err(e) => message!(
+            "{}",
+            format!(
+                "⚠️  Push failed ({}); push later with: git push -u {} {}",
+                e, remote, branch_name
+            )
+            .yellow()
+        ),
+    }
+}
+
+/// Refuse a branch that another worktree already has checked out
+///
+/// git would refuse too, but without saying where; pointing at the existing
+/// worktree covers the common case of forgetting it was already created.
+fn ensure_not_checked_out(git_working_dir: &Path, branch_name: &str) -> Result<()> {
+    let worktrees = git::list_worktrees(Some(git_working_dir))?;
+    let existing = worktrees
+        .iter()
+        .find(|wt| wt.branch.as_deref().map(clean_branch_name) == Some(branch_name));
+    match existing {
+        Some(existing) => Err(Error::branch(format!(
+            "'{}' is already checked out at {}\nSwitch to it with: cd {}",
+            branch_name,
+            existing.path.display(),
+            existing.path.display()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// How the worktree gets created, and what that does
+struct AddPlan {
+    description: ColoredString,
+    worktree: WorktreeAddOptions,
+    /// Git commands run after the worktree exists (e.g. setting an upstream)
+    follow_up: Vec<Vec<String>>,
+    /// Whether the branch exists nowhere yet, locally or on a remote (what `--push` pushes)
+    creates_branch: bool,
+}
+
+impl AddPlan {
+    fn new(description: ColoredString, worktree: WorktreeAddOptions) -> Self {
+        Self {
+            description,
+            worktree,
+            follow_up: Vec::new(),
+            creates_branch: false,
+        }
+    }
+
+    /// Every git command in order, as printed by `--dry-run`
+    fn commands(&self) -> Vec<Vec<String>> {
+        let mut commands = vec![git::worktree_add_args(&self.worktree)];
+        commands.extend(self.follow_up.iter().cloned());
+        commands
+    }
+
+    fn execute(&self, git_working_dir: &Path) -> Result<()> {
+        git::add_worktree(git_working_dir, &self.worktree)?;
+        for args in &self.follow_up {
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            git::execute_streaming(&args, Some(git_working_dir))?;
+        }
+        Ok(())
+    }
+}
+
+/// Decide how to create the worktree from the branches currently known locally
+/// and on the remotes (read-only, so `--dry-run` can use it too)
+fn plan_worktree_add(
+    git_working_dir: &Path,
+    target_path: &Path,
+    branch_name: &str,
+    from: Option<&str>,
+    track: Option<bool>,
+    main_branch: &str,
+    remote: &str,
+) -> Result<AddPlan> {
+    // Check if branch exists locally or remotely, falling back to other remotes
+    let (local_exists, on_default_remote) = git::branch_exists(git_working_dir, branch_name, remote)?;
+    let branch_remote = if on_default_remote {
+        Some(remote.to_string())
+    } else {
+        git::find_remote_with_branch(git_working_dir, branch_name)
+    };
+    let checkout = |branch: &str| WorktreeAddOptions {
+        path: target_path.to_path_buf(),
+        start_point: Some(branch.to_string()),
+        ..Default::default()
+    };
+    let create = |start_point: String, track: bool| WorktreeAddOptions {
+        path: target_path.to_path_buf(),
+        new_branch: Some(branch_name.to_string()),
+        start_point: Some(start_point),
+        track: Some(track),
+        ..Default::default()
+    };
+
+    if local_exists {
+        let mut plan = AddPlan::new(
             format!(
                 "Branch '{}' exists locally, checking out existing branch...",
                 branch_name
             )
-            .yellow()
+            .yellow(),
+            checkout(branch_name),
         );
-        git::execute_streaming(
-            &["worktree", "add", target_path.to_str().unwrap(), branch_name],
-            Some(&git_working_dir),
-        )?;
-    } else if remote_exists {
+        if let (Some(true), Some(branch_remote)) = (track, &branch_remote) {
+            plan.follow_up.push(vec![
+                "branch".to_string(),
+                format!("--set-upstream-to={}/{}", branch_remote, branch_name),
+                branch_name.to_string(),
+            ]);
+        }
+        return Ok(plan);
+    }
+
+    if let Some(branch_remote) = &branch_remote {
         // Check for case-insensitive local branch match (macOS compatibility)
-        if let Some(existing_local) = git::find_local_branch_case_insensitive(&git_working_dir, branch_name)? {
-            println!(
-                "{}",
+        if let Some(existing_local) = git::find_local_branch_case_insensitive(git_working_dir, branch_name)? {
+            return Ok(AddPlan::new(
                 format!(
                     "Branch '{}' exists locally (as '{}'), checking out existing branch...",
                     branch_name, existing_local
                 )
-                .yellow()
-            );
-            git::execute_streaming(
-                &["worktree", "add", target_path.to_str().unwrap(), &existing_local],
-                Some(&git_working_dir),
-            )?;
-        } else {
-            println!(
-                "{}",
-                format!(
-                    "Branch '{}' exists remotely, checking out remote branch...",
-                    branch_name
-                )
-                .yellow()
-            );
-            git::execute_streaming(
-                &[
-                    "worktree",
-                    "add",
-                    target_path.to_str().unwrap(),
-                    "-b",
-                    branch_name,
-                    &format!("origin/{}", branch_name),
-                ],
-                Some(&git_working_dir),
-            )?;
+                .yellow(),
+                checkout(&existing_local),
+            ));
         }
-    } else {
+
+        // Track the remote branch so ahead/behind is reported against it
+        return Ok(AddPlan::new(
+            format!(
+                "Branch '{}' exists on {}, checking out remote branch...",
+                branch_name, branch_remote
+            )
+            .yellow(),
+            create(format!("{}/{}", branch_remote, branch_name), track.unwrap_or(true)),
+        ));
+    }
+
+    let base_ref = match from {
+        Some(base) => {
+            if !git::ref_exists(git_working_dir, base) {
+                return Err(Error::git(format!(
+                    "Base ref '{}' does not exist (check the name or fetch it first)",
+                    base
+                )));
+            }
+            base.to_string()
+        }
+        None => format!("{}/{}", remote, main_branch),
+    };
+
+    // A new branch doesn't track its base unless --track is given
+    let mut plan = AddPlan::new(
+        format!("Creating new branch '{}' from '{}'...", branch_name, base_ref).cyan(),
+        create(base_ref, track.unwrap_or(false)),
+    );
+    plan.creates_branch = true;
+    Ok(plan)
+}
+
+/// Print what `gwt add` would do without fetching, creating, or running anything
+fn print_dry_run(
+    git_working_dir: &Path,
+    target_path: &Path,
+    project_root: &Path,
+    branch_name: &str,
+    options: &AddOptions,
+    push: bool,
+    hook_variables: &[(&str, &str)],
+) -> Result<()> {
+    let (main_branch, remote) = main_branch_and_remote(project_root)?;
+    let plan = plan_worktree_add(
+        git_working_dir,
+        target_path,
+        branch_name,
+        options.from.as_deref(),
+        options.track,
+        &main_branch,
+        &remote,
+    )?;
+
+    print_plan(
+        target_path,
+        ("Branch", branch_name.green()),
+        &plan,
+        &remote,
+        hook_variables,
+    )?;
+    if push && plan.creates_branch {
         println!(
-            "{}",
-            format!("Creating new branch '{}' from 'origin/{}'...", branch_name, main_branch).cyan()
+            "  {}: git push -u {} {} (in the new worktree)",
+            "Would run".dimmed(),
+            remote,
+            branch_name
         );
-        git::execute_streaming(
-            &[
-                "worktree",
-                "add",
-                "--no-track",
-                target_path.to_str().unwrap(),
-                "-b",
-                branch_name,
-                &format!("origin/{}", main_branch),
-            ],
-            Some(&git_working_dir),
-        )?;
     }
-
-    // Success messages
     println!(
         "{}",
-        format!("✓ Worktree created at: {}", target_path.display()).green()
+        format!("(branches checked against {} as of the last fetch)", remote).dimmed()
     );
-    println!("{}", format!("✓ Branch: {}", branch_name).green());
+    Ok(())
+}
 
-    // Execute post-add hooks
-    hooks::execute_hooks(
-        "postAdd",
-        &target_path,
-        &[
-            ("branchName", branch_name),
-            ("worktreePath", target_path.to_str().unwrap()),
-        ],
-    )?;
+/// Print the target, the git commands, the hooks and `copyOnAdd` for a `--dry-run`
+fn print_plan(
+    target_path: &Path,
+    (label, value): (&str, ColoredString),
+    plan: &AddPlan,
+    remote: &str,
+    hook_variables: &[(&str, &str)],
+) -> Result<()> {
+    println!("{}", "Dry run, nothing will be changed:".cyan().bold());
+    println!("  {}: {}", "Path".dimmed(), target_path.display());
+    println!("  {}: {}", label.dimmed(), value);
+    println!("  {}", plan.description);
+    println!("  {}: git fetch {}", "Would run".dimmed(), remote);
+    for args in plan.commands() {
+        println!("  {}: git {}", "Would run".dimmed(), args.join(" "));
+    }
+    hooks::print_planned_hooks("preAdd", hook_variables)?;
+    hooks::print_planned_hooks("postAdd", hook_variables)?;
+    if let Some(patterns) = GitWorktreeConfig::find_config()?.and_then(|(_, config)| config.copy_on_add) {
+        println!("  {}: {}", "copyOnAdd".dimmed(), patterns.join(", "));
+    }
+    Ok(())
+}
+
+/// Ecosystem, the lock or manifest file that identifies its package manager, and the
+/// install command. Checked in order and only the first match per ecosystem runs, so a
+/// lockfile wins over a bare manifest (yarn.lock over npm, poetry.lock over requirements.txt).
+const DEPENDENCY_INSTALLERS: &[(&str, &str, &[&str])] = &[
+    ("node", "pnpm-lock.yaml", &["pnpm", "install"]),
+    ("node", "yarn.lock", &["yarn", "install"]),
+    ("node", "package-lock.json", &["npm", "install"]),
+    ("python", "poetry.lock", &["poetry", "install"]),
+    (
+        "python",
+        "requirements.txt",
+        &["pip", "install", "-r", "requirements.txt"],
+    ),
+    ("rust", "Cargo.toml", &["cargo", "fetch"]),
+];
+
+/// Install commands to run in `dir` with the file that selected each, at most one per ecosystem
+fn detect_dependency_installers(dir: &Path) -> Vec<(&'static str, &'static [&'static str])> {
+    let mut ecosystems = Vec::new();
+    let mut installers = Vec::new();
+    for &(ecosystem, marker, command) in DEPENDENCY_INSTALLERS {
+        if !ecosystems.contains(&ecosystem) && dir.join(marker).is_file() {
+            ecosystems.push(ecosystem);
+            installers.push((marker, command));
+        }
+    }
+    installers
+}
+
+/// Run the detected install commands in the new worktree, streaming their output
+///
+/// The worktree already exists at this point, so a failed install is reported but
+/// doesn't fail the add.
+fn install_dependencies(worktree_path: &Path) {
+    let installers = detect_dependency_installers(worktree_path);
+    if installers.is_empty() {
+        status!(
+            "{}",
+            "No lockfile or manifest recognized, skipping dependency install".dimmed()
+        );
+        return;
+    }
 
+    for (marker, args) in installers {
+        let command = args.join(" ");
+        status!(
+            "{}",
+            format!("📦 Installing dependencies ({} found): {}", marker, command).cyan()
+        );
+        let result = Command::new(args[0])
+            .args(&args[1..])
+            .current_dir(worktree_path)
+            .stdout(output::child_stdout())
+            .stderr(Stdio::inherit())
+            .status();
+        match result {
+            Ok(exit) if exit.success() => status!("{}", "✓ Dependencies installed".green()),
+            Ok(exit) => message!(
+                "{}",
+                format!("⚠️  '{}' failed with exit code {:?}", command, exit.code()).yellow()
+            ),
+            Err(e) => message!("{}", format!("⚠️  Could not run '{}': {}", command, e).yellow()),
+        }
+    }
+}
+
+/// Print the install commands `--install-deps` would run, detected from the existing checkout
+fn print_planned_install(git_working_dir: &Path) {
+    let installers = detect_dependency_installers(git_working_dir);
+    if installers.is_empty() {
+        println!("  {}: none detected", "Install deps".dimmed());
+    }
+    for (_, args) in installers {
+        println!("  {}: {}", "Would run".dimmed(), args.join(" "));
+    }
+}
+
+/// Path of the worktree that has the main branch checked out
+fn main_worktree_path(git_dir: &Path, main_branch: &str) -> Option<PathBuf> {
+    git::list_worktrees(Some(git_dir))
+        .ok()?
+        .into_iter()
+        .find(|wt| wt.branch.as_deref() == Some(format!("refs/heads/{}", main_branch).as_str()))
+        .map(|wt| wt.path)
+}
+
+/// Copy files matching the `copyOnAdd` globs from `source_dir` into `target_dir`
+///
+/// Patterns are relative to `source_dir`; matching directories are copied recursively.
+/// Symlinks are recreated as links rather than followed, so a link pointing back up the
+/// tree can't recurse forever. Files that already exist in the target are left alone.
+fn copy_on_add(patterns: &[String], source_dir: &Path, target_dir: &Path) -> Result<()> {
+    let source = source_dir.to_str().ok_or_else(|| {
+        Error::config(format!(
+            "Cannot apply copyOnAdd: '{}' is not valid UTF-8",
+            source_dir.display()
+        ))
+    })?;
+    let escaped_source = glob::Pattern::escape(source);
+    for pattern in patterns {
+        let full_pattern = format!("{}/{}", escaped_source, pattern.trim_end_matches('/'));
+        let matches = glob::glob(&full_pattern)
+            .map_err(|e| Error::config(format!("Invalid copyOnAdd pattern '{}': {}", pattern, e)))?;
+        for path in matches.flatten() {
+            copy_path(&path, source_dir, target_dir)?;
+        }
+    }
     Ok(())
 }
 
-fn determine_paths(branch_name: &str) -> Result<(PathBuf, PathBuf, PathBuf)> {
-    let project_root = find_project_root()?;
-    let git_working_dir = find_existing_worktree(&project_root)?;
+fn copy_path(path: &Path, source_dir: &Path, target_dir: &Path) -> Result<()> {
+    let file_type = fs::symlink_metadata(path)?.file_type();
+    if file_type.is_dir() {
+        for entry in fs::read_dir(path)?.flatten() {
+            copy_path(&entry.path(), source_dir, target_dir)?;
+        }
+        return Ok(());
+    }
 
-    // Get worktrees_path from config, or derive it from project_root
-    let worktrees_path = if let Some((_config_path, config)) = GitWorktreeConfig::find_config()? {
-        config
-            .get_worktrees_path()
-            .unwrap_or_else(|| GitWorktreeConfig::derive_worktrees_path(&project_root))
-    } else {
-        GitWorktreeConfig::derive_worktrees_path(&project_root)
+    let Ok(relative) = path.strip_prefix(source_dir) else {
+        return Ok(());
     };
+    let destination = target_dir.join(relative);
+    // symlink_metadata also sees a dangling link, which exists() reports as missing
+    if fs::symlink_metadata(&destination).is_ok() {
+        return Ok(());
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if file_type.is_symlink() {
+        copy_symlink(path, &destination)?;
+    } else {
+        fs::copy(path, &destination)?;
+    }
+    status!("{}", format!("  Copied {}", relative.display()).dimmed());
+    Ok(())
+}
+
+/// Create a link at `destination` with the same target as the link at `path`
+fn copy_symlink(path: &Path, destination: &Path) -> std::io::Result<()> {
+    let link_target = fs::read_link(path)?;
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&link_target, destination)
+    }
+    #[cfg(windows)]
+    {
+        if path.is_dir() {
+            std::os::windows::fs::symlink_dir(&link_target, destination)
+        } else {
+            std::os::windows::fs::symlink_file(&link_target, destination)
+        }
+    }
+}
+
+/// Where the worktree for `branch_name` goes: `path` if given, else under the worktrees path
+fn determine_target_path(
+    project_root: &Path,
+    branch_name: &str,
+    path: Option<&Path>,
+    dry_run: bool,
+) -> Result<PathBuf> {
+    // An explicit --path bypasses the worktrees path; git records where it lives
+    if let Some(path) = path {
+        let target_path = std::env::current_dir()?.join(path);
+        if target_path.exists() {
+            return Err(Error::msg(format!(
+                "Target path '{}' already exists",
+                target_path.display()
+            )));
+        }
+        if let Some(parent) = target_path.parent() {
+            ensure_no_case_collision(parent, &target_path)?;
+        }
+        return Ok(target_path);
+    }
+
+    let (worktrees_path, layout) = worktrees_path_and_layout(project_root)?;
 
     // Create worktrees directory if it doesn't exist
-    if !worktrees_path.exists() {
+    if !worktrees_path.exists() && !dry_run {
         fs::create_dir_all(&worktrees_path)
             .map_err(|e| Error::Other(format!("Failed to create worktrees directory: {}", e)))?;
     }
 
-    let target_path = worktrees_path.join(branch_name);
+    let target_path = layout.worktree_path(&worktrees_path, branch_name);
+    ensure_no_case_collision(&worktrees_path, &target_path)?;
 
-    Ok((git_working_dir, target_path, project_root))
+    Ok(target_path)
 }
 
-fn get_main_branch(_project_root: &Path) -> Result<String> {
-    // Try to find config (local or global)
-    if let Some((_config_path, config)) = GitWorktreeConfig::find_config()? {
-        return Ok(config.main_branch);
+/// Fail if a directory on the way from `base` to `target` exists under a name that
+/// differs only by case (`Feature` vs `feature`)
+///
+/// Case-insensitive filesystems (macOS, Windows) treat those as the same directory, so
+/// git would fail halfway or two worktrees would share a folder. The names are compared
+/// by listing each parent directory, which works the same on every filesystem.
+fn ensure_no_case_collision(base: &Path, target: &Path) -> Result<()> {
+    let Ok(relative) = target.strip_prefix(base) else {
+        return Ok(());
+    };
+
+    let mut dir = base.to_path_buf();
+    for component in relative.components() {
+        let Some(name) = component.as_os_str().to_str() else {
+            return Ok(());
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(());
+        };
+        let names: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        if let Some(existing) = case_collision(name, &names) {
+            return Err(Error::msg(format!(
+                "'{}' already exists and differs from '{}' only by case; case-insensitive filesystems \
+                 (macOS, Windows) treat them as the same directory. Choose a name that differs by more \
+                 than case, or pass --path.",
+                dir.join(existing).display(),
+                dir.join(name).display()
+            )));
+        }
+        dir.push(name);
+    }
+    Ok(())
+}
+
+/// The entry in `existing` that differs from `name` only by case, unless `name` itself exists
+fn case_collision<'a>(name: &str, existing: &'a [String]) -> Option<&'a str> {
+    if existing.iter().any(|entry| entry == name) {
+        return None;
     }
+    let lowercase = name.to_lowercase();
+    existing
+        .iter()
+        .find(|entry| entry.to_lowercase() == lowercase)
+        .map(String::as_str)
+}
 
-    // Fallback to detecting from remote if no config
-    if let Some(git_root) = git::get_git_root()? {
-        Ok(git::get_remote_default_branch(&git_root)?)
+/// Get worktrees_path and layout from config, or derive them from project_root
+pub fn worktrees_path_and_layout(project_root: &Path) -> Result<(PathBuf, WorktreeLayout)> {
+    if let Some((_config_path, config)) = GitWorktreeConfig::find_config()? {
+        let worktrees_path = config
+            .get_worktrees_path()
+            .unwrap_or_else(|| GitWorktreeConfig::derive_worktrees_path(project_root));
+        Ok((worktrees_path, config.layout()))
     } else {
-        Ok("main".to_string())
+        Ok((
+            GitWorktreeConfig::derive_worktrees_path(project_root),
+            WorktreeLayout::default(),
+        ))
+    }
+}
+
+/// Get the main branch and default remote from config, or detect them from the repository
+pub fn main_branch_and_remote(project_root: &Path) -> Result<(String, String)> {
+    // Honor the configured mainBranch and defaultRemote (local or global config)
+    if let Some((_config_path, config)) = GitWorktreeConfig::find_config()? {
+        let remote = config.remote().to_string();
+        return Ok((config.main_branch, remote));
+    }
+
+    // Fallback to detecting from the default remote if no config
+    let main_branch =
+        git::get_remote_default_branch(project_root, DEFAULT_REMOTE).unwrap_or_else(|_| "main".to_string());
+    Ok((main_branch, DEFAULT_REMOTE.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn detected(files: &[&str]) -> Vec<&'static str> {
+        let dir = tempdir().unwrap();
+        for file in files {
+            fs::write(dir.path().join(file), "").unwrap();
+        }
+        detect_dependency_installers(dir.path())
+            .iter()
+            .map(|(_, args)| args[0])
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_dependency_installers() {
+        assert_eq!(detected(&["package-lock.json"]), ["npm"]);
+        assert_eq!(detected(&["yarn.lock"]), ["yarn"]);
+        assert_eq!(detected(&["pnpm-lock.yaml"]), ["pnpm"]);
+        assert_eq!(detected(&["Cargo.toml"]), ["cargo"]);
+        assert_eq!(detected(&["requirements.txt"]), ["pip"]);
+        assert_eq!(detected(&["poetry.lock"]), ["poetry"]);
+        assert!(detected(&["README.md"]).is_empty());
+
+        // One installer per ecosystem, lockfiles first
+        assert_eq!(detected(&["package-lock.json", "yarn.lock"]), ["yarn"]);
+        assert_eq!(detected(&["requirements.txt", "poetry.lock"]), ["poetry"]);
+        assert_eq!(detected(&["package-lock.json", "Cargo.toml"]), ["npm", "cargo"]);
+    }
+
+    #[test]
+    fn test_pull_request_ref() {
+        assert_eq!(pull_request_ref("github", 42, false).unwrap(), "pull/42/head");
+        assert_eq!(pull_request_ref("github", 42, true).unwrap(), "pull/42/merge");
+        assert_eq!(pull_request_ref("gitea", 7, false).unwrap(), "pull/7/head");
+        assert_eq!(
+            pull_request_ref("bitbucket-data-center", 7, false).unwrap(),
+            "refs/pull-requests/7/from"
+        );
+        // Only GitHub has a merge ref
+        for provider in ["gitea", "bitbucket-data-center", "bitbucket-cloud"] {
+            assert!(pull_request_ref(provider, 7, true).is_err(), "{}", provider);
+        }
+        assert!(pull_request_ref("bitbucket-cloud", 7, false).is_err());
+
+        assert_eq!(pull_request_dir_name(42, false), "pr-42");
+        assert_eq!(pull_request_dir_name(42, true), "pr-42-merge");
+    }
+
+    #[test]
+    fn test_case_collision() {
+        let entries: Vec<String> = ["feature", "Bugfix", "main"].iter().map(|e| e.to_string()).collect();
+        assert_eq!(case_collision("Feature", &entries), Some("feature"));
+        assert_eq!(case_collision("bugfix", &entries), Some("Bugfix"));
+        assert_eq!(case_collision("MAIN", &entries), Some("main"));
+        // An exact match is an ordinary existing directory, not a case collision
+        assert_eq!(case_collision("feature", &entries), None);
+        assert_eq!(case_collision("hotfix", &entries), None);
+        assert_eq!(case_collision("feature", &[]), None);
+    }
+
+    #[test]
+    fn test_ensure_no_case_collision() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("feature").join("login")).unwrap();
+
+        assert!(ensure_no_case_collision(dir.path(), &dir.path().join("feature").join("signup")).is_ok());
+        assert!(ensure_no_case_collision(dir.path(), &dir.path().join("Feature").join("signup")).is_err());
+        let err = ensure_no_case_collision(dir.path(), &dir.path().join("feature").join("Login")).unwrap_err();
+        assert!(err.to_string().contains("only by case"), "{}", err);
     }
 }
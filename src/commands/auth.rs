@@ -1,33 +1,88 @@
+use colored::Colorize;
+use std::io::{self, IsTerminal};
+
 use crate::bitbucket_api::BitbucketClient;
 use crate::bitbucket_auth::{self, BitbucketAuth};
 use crate::bitbucket_data_center_api::BitbucketDataCenterClient;
 use crate::bitbucket_data_center_auth::{self, BitbucketDataCenterAuth};
-use crate::error::Result;
+use crate::config::GitWorktreeConfig;
+use crate::error::{Error, Result};
+use crate::gitea::GiteaClient;
+use crate::gitea_auth::{self, GiteaAuth};
 use crate::github::GitHubClient;
+use crate::github_auth::{self, GitHubAuth};
 
-pub fn run() -> Result<()> {
-    let client = GitHubClient::new();
-    if client.has_auth() {
-        println!("✓ You are already authenticated with GitHub via gh CLI");
-        println!("Run 'gh auth logout' to remove credentials if needed");
-    } else {
-        println!("Please authenticate with GitHub using: gh auth login");
+use crate::cli::{BitbucketCloudAuthAction, BitbucketDataCenterAuthAction, GiteaAuthAction, GithubAuthAction};
+
+#[tokio::main]
+pub async fn run_github(action: Option<GithubAuthAction>) -> Result<()> {
+    match action {
+        None => {
+            let client = GitHubClient::new();
+            if client.uses_gh() {
+                println!("✓ You are already authenticated with GitHub via gh CLI");
+                println!("Run 'gh auth logout' to remove credentials if needed");
+            } else if client.has_auth() {
+                println!("✓ Using a stored GitHub token (gh CLI is not authenticated)");
+                println!("Run 'gwt auth github test' to verify it");
+            } else {
+                println!("Please authenticate with GitHub using: gh auth login");
+                println!("Or store a token with: gwt auth github setup");
+            }
+        }
+        Some(GithubAuthAction::Setup { token_stdin }) => {
+            if !token_stdin {
+                github_auth::display_setup_instructions();
+            }
+            let Some(token) = read_token(token_stdin)? else {
+                return Ok(());
+            };
+            let location = GitHubAuth::new()?.store_token(&token)?;
+            println!("✓ GitHub token stored in the {}", location);
+            GitHubClient::with_token(token).test_connection().await?;
+        }
+        Some(GithubAuthAction::Test) => {
+            let client = GitHubClient::new();
+            if !client.uses_gh() {
+                print_token_source(GitHubAuth::new()?.token_source());
+            }
+            client.test_connection().await?;
+        }
     }
     Ok(())
 }
 
-use crate::cli::{BitbucketCloudAuthAction, BitbucketDataCenterAuthAction};
-
 #[tokio::main]
 pub async fn run_bitbucket_cloud(action: Option<BitbucketCloudAuthAction>) -> Result<()> {
     match action {
-        None | Some(BitbucketCloudAuthAction::Setup) => {
+        None => {
             bitbucket_auth::display_setup_instructions();
         }
+        Some(BitbucketCloudAuthAction::Setup { token_stdin }) => {
+            // Tokens are stored per repository, so outside a project only the instructions apply
+            let repository = bitbucket_auth::get_auth_from_config();
+            if !token_stdin {
+                bitbucket_auth::display_setup_instructions();
+                if repository.is_err() {
+                    return Ok(());
+                }
+            }
+            let (workspace, repo, email) = repository?;
+            let Some(token) = read_token(token_stdin)? else {
+                return Ok(());
+            };
+            let location = BitbucketAuth::new(workspace.clone(), repo.clone(), email)?.store_token(&token)?;
+            println!(
+                "✓ Bitbucket Cloud token for {}/{} stored in the {}",
+                workspace, repo, location
+            );
+        }
         Some(BitbucketCloudAuthAction::Test) => {
             let (workspace, repo, email) = bitbucket_auth::get_auth_from_config()?;
             let auth = BitbucketAuth::new(workspace, repo, email)?;
-            let client = BitbucketClient::new(auth);
+            print_token_source(auth.token_source());
+            let api_base = GitWorktreeConfig::find_config()?.and_then(|(_, config)| config.bitbucket_api_base);
+            let client = BitbucketClient::new(auth, api_base.as_deref());
             client.test_connection().await?;
         }
     }
@@ -37,15 +92,195 @@ pub async fn run_bitbucket_cloud(action: Option<BitbucketCloudAuthAction>) -> Re
 #[tokio::main]
 pub async fn run_bitbucket_data_center(action: Option<BitbucketDataCenterAuthAction>) -> Result<()> {
     match action {
-        None | Some(BitbucketDataCenterAuthAction::Setup) => {
+        None => {
             bitbucket_data_center_auth::display_setup_instructions();
         }
+        Some(BitbucketDataCenterAuthAction::Setup { token_stdin }) => {
+            // Tokens are stored per server, so outside a project only the instructions apply
+            let server = bitbucket_data_center_auth::get_auth_from_config();
+            if !token_stdin {
+                bitbucket_data_center_auth::display_setup_instructions();
+                if server.is_err() {
+                    return Ok(());
+                }
+            }
+            let (base_url, project_key, repo_slug) = server?;
+            let Some(token) = read_token(token_stdin)? else {
+                return Ok(());
+            };
+            let location =
+                BitbucketDataCenterAuth::new(project_key, repo_slug, base_url.clone())?.store_token(&token)?;
+            println!(
+                "✓ Bitbucket Data Center token for {} stored in the {}",
+                base_url, location
+            );
+        }
         Some(BitbucketDataCenterAuthAction::Test) => {
             let (base_url, project_key, repo_slug) = bitbucket_data_center_auth::get_auth_from_config()?;
             let auth = BitbucketDataCenterAuth::new(project_key, repo_slug, base_url.clone())?;
+            print_token_source(auth.token_source());
             let client = BitbucketDataCenterClient::new(auth, base_url);
             client.test_connection().await?;
         }
     }
     Ok(())
 }
+
+#[tokio::main]
+pub async fn run_gitea(action: Option<GiteaAuthAction>) -> Result<()> {
+    match action {
+        None => {
+            gitea_auth::display_setup_instructions();
+        }
+        Some(GiteaAuthAction::Setup { token_stdin }) => {
+            if !token_stdin {
+                gitea_auth::display_setup_instructions();
+            }
+            let Some(token) = read_token(token_stdin)? else {
+                return Ok(());
+            };
+            let location = GiteaAuth::new()?.store_token(&token)?;
+            println!("✓ Gitea token stored in the {}", location);
+            // Only verifiable from inside a Gitea project, where the instance URL is known
+            if let Ok((base_url, _, _)) = gitea_auth::get_auth_from_config() {
+                GiteaClient::new(token, base_url).test_connection().await?;
+            }
+        }
+        Some(GiteaAuthAction::Test) => {
+            let (base_url, _, _) = gitea_auth::get_auth_from_config()?;
+            let auth = GiteaAuth::new()?;
+            print_token_source(auth.token_source());
+            let token = auth.get_token()?;
+            GiteaClient::new(token, base_url).test_connection().await?;
+        }
+    }
+    Ok(())
+}
+
+/// The token for `auth <provider> setup`
+///
+/// With `--token-stdin` it is the first line of stdin. Otherwise it is typed at a prompt
+/// that doesn't echo, so it stays out of shell history and the terminal; `None` when
+/// stdin isn't a terminal or nothing was entered.
+fn read_token(token_stdin: bool) -> Result<Option<String>> {
+    let stdin = io::stdin();
+    let token = if token_stdin {
+        let mut line = String::new();
+        stdin.read_line(&mut line)?;
+        if line.trim().is_empty() {
+            return Err(Error::auth("No token on stdin"));
+        }
+        line
+    } else if stdin.is_terminal() {
+        println!();
+        rpassword::prompt_password("Paste the token to store (input hidden, empty to skip): ")?
+    } else {
+        return Ok(None);
+    };
+
+    let token = token.trim();
+    Ok((!token.is_empty()).then(|| token.to_string()))
+}
+
+/// Say which store a token is read from before `auth ... test` tries it
+fn print_token_source(source: Option<String>) {
+    if let Some(source) = source {
+        println!("{}", format!("Using token from {}", source).dimmed());
+    }
+}
+
+/// A provider row in `gwt auth status`
+struct ProviderStatus {
+    /// `sourceControl` value (also the `gwt auth` subcommand)
+    source_control: &'static str,
+    name: &'static str,
+    /// Where the credentials come from, `None` when there are none
+    source: Option<String>,
+}
+
+/// Show which providers have credentials, highlighting the current project's
+pub fn run_status() -> Result<()> {
+    let current = GitWorktreeConfig::find_config()?.map(|(_, config)| config.source_control);
+    let providers = [
+        ProviderStatus {
+            source_control: "github",
+            name: "GitHub",
+            source: github_auth_source(),
+        },
+        ProviderStatus {
+            source_control: "bitbucket-cloud",
+            name: "Bitbucket Cloud",
+            source: bitbucket_cloud_auth_source(),
+        },
+        ProviderStatus {
+            source_control: "bitbucket-data-center",
+            name: "Bitbucket Data Center",
+            source: bitbucket_data_center_auth_source(),
+        },
+        ProviderStatus {
+            source_control: "gitea",
+            name: "Gitea",
+            source: GiteaAuth::new().ok().and_then(|auth| auth.token_source()),
+        },
+    ];
+
+    println!("{}", format!("{:<24}{:<6}{}", "Provider", "Auth", "Source").bold());
+    for provider in &providers {
+        let is_current = current.as_deref() == Some(provider.source_control);
+        let name = format!("{:<24}", provider.name);
+        let mark = match provider.source {
+            Some(_) => format!("{:<6}", "✓").green(),
+            None => format!("{:<6}", "✗").red(),
+        };
+        let source = provider.source.as_deref().unwrap_or("-");
+        if is_current {
+            println!("{}{}{}  {}", name.bold(), mark, source, "← this project".cyan());
+        } else {
+            println!("{}{}{}", name, mark, source.dimmed());
+        }
+    }
+
+    let missing = providers
+        .iter()
+        .find(|provider| current.as_deref() == Some(provider.source_control) && provider.source.is_none());
+    if let Some(provider) = missing {
+        println!(
+            "\n{}",
+            format!(
+                "No {} credentials, so gwt list can't show pull requests for this project.\nRun 'gwt auth {} setup' for instructions.",
+                provider.name, provider.source_control
+            )
+            .yellow()
+        );
+    }
+    Ok(())
+}
+
+fn github_auth_source() -> Option<String> {
+    let client = GitHubClient::new();
+    if client.uses_gh() {
+        return Some("gh CLI".to_string());
+    }
+    if !client.has_auth() {
+        return None;
+    }
+    GitHubAuth::new().ok().and_then(|auth| auth.token_source())
+}
+
+fn bitbucket_cloud_auth_source() -> Option<String> {
+    match bitbucket_auth::get_auth_from_config()
+        .and_then(|(workspace, repo, email)| BitbucketAuth::new(workspace, repo, email))
+    {
+        Ok(auth) => auth.token_source(),
+        Err(_) => bitbucket_auth::env_token_source(),
+    }
+}
+
+fn bitbucket_data_center_auth_source() -> Option<String> {
+    match bitbucket_data_center_auth::get_auth_from_config()
+        .and_then(|(base_url, project_key, repo_slug)| BitbucketDataCenterAuth::new(project_key, repo_slug, base_url))
+    {
+        Ok(auth) => auth.token_source(),
+        Err(_) => bitbucket_data_center_auth::token_source(),
+    }
+}
@@ -1,10 +1,194 @@
-use crate::config::GitWorktreeConfig;
+use colored::Colorize;
+use serde_json::Value;
+use std::path::Path;
+
+use crate::cli::ConfigAction;
+use crate::config::{ConfigLock, GitWorktreeConfig, Hooks, HOOK_TYPES};
 use crate::error::{Error, Result};
 
-pub fn run() -> Result<()> {
-    let (config_path, _config) = GitWorktreeConfig::find_config()?
+/// How a settable key's value is parsed from the command line
+#[derive(Clone, Copy)]
+enum KeyKind {
+    Text,
+    Number,
+    Bool,
+}
+
+/// Top-level keys that `gwt config get/set` operate on
+const KEYS: &[(&str, KeyKind)] = &[
+    ("repositoryUrl", KeyKind::Text),
+    ("mainBranch", KeyKind::Text),
+    ("sourceControl", KeyKind::Text),
+    ("projectPath", KeyKind::Text),
+    ("worktreesPath", KeyKind::Text),
+    ("worktreeLayout", KeyKind::Text),
+    ("bitbucketEmail", KeyKind::Text),
+    ("bitbucketApiBase", KeyKind::Text),
+    ("bitbucketBaseUrl", KeyKind::Text),
+    ("bitbucketProjectKey", KeyKind::Text),
+    ("bitbucketRepoSlug", KeyKind::Text),
+    ("giteaBaseUrl", KeyKind::Text),
+    ("defaultRemote", KeyKind::Text),
+    ("hooksTimeoutSecs", KeyKind::Number),
+    ("hooksFailFast", KeyKind::Bool),
+    ("hookEnvFile", KeyKind::Text),
+    ("prCacheTtlSecs", KeyKind::Number),
+    ("deleteBranchOnRemove", KeyKind::Bool),
+    ("pushOnAdd", KeyKind::Bool),
+    ("initSubmodules", KeyKind::Bool),
+    ("showPullRequests", KeyKind::Bool),
+];
+
+const SOURCE_CONTROLS: &[&str] = &["github", "bitbucket-cloud", "bitbucket-data-center", "gitea"];
+
+pub fn run(action: Option<ConfigAction>) -> Result<()> {
+    let (config_path, config) = GitWorktreeConfig::find_config()?
         .ok_or_else(|| Error::config("Config not found. Run 'gwt init' from your project directory to create one."))?;
 
+    match action {
+        None => open(&config_path),
+        Some(ConfigAction::Path) => {
+            println!("{}", config_path.display());
+            Ok(())
+        }
+        Some(ConfigAction::Get { key }) => {
+            let value = get_value(&config, &key)?
+                .ok_or_else(|| Error::config(format!("'{}' is not set in {}", key, config_path.display())))?;
+            println!("{}", value);
+            Ok(())
+        }
+        Some(ConfigAction::Set { key, value }) => {
+            // Re-read under the lock so a concurrent `gwt` write isn't lost
+            let _lock = ConfigLock::acquire(&config_path)?;
+            let config = GitWorktreeConfig::load_for_update(&config_path)?;
+            let updated = set_value(&config, &key, &value)?;
+            updated.save(&config_path)?;
+            println!("{}", format!("✓ Set {} = {}", key, value).green());
+            Ok(())
+        }
+        Some(ConfigAction::Edit) => edit(&config_path),
+        Some(ConfigAction::Hooks) => {
+            print_hooks(config.hooks.as_ref());
+            Ok(())
+        }
+        Some(ConfigAction::AddHook { hook_type, command }) => update_hooks(&config_path, |hooks| {
+            hooks
+                .get_mut(&hook_type)
+                .ok_or_else(|| unknown_hook_type(&hook_type))?
+                .push(command.clone());
+            Ok(format!("✓ Added {} hook: {}", hook_type, command))
+        }),
+        Some(ConfigAction::RemoveHook { hook_type, index }) => update_hooks(&config_path, |hooks| {
+            let commands = hooks.get_mut(&hook_type).ok_or_else(|| unknown_hook_type(&hook_type))?;
+            if index == 0 || index > commands.len() {
+                return Err(Error::config(format!(
+                    "No {} hook #{} ({} configured). Run 'gwt config hooks' to see the numbers.",
+                    hook_type,
+                    index,
+                    commands.len()
+                )));
+            }
+            let removed = commands.remove(index - 1);
+            Ok(format!("✓ Removed {} hook #{}: {}", hook_type, index, removed))
+        }),
+    }
+}
+
+fn unknown_hook_type(hook_type: &str) -> Error {
+    Error::config(format!(
+        "Unknown hook type '{}'. Valid types: {}",
+        hook_type,
+        HOOK_TYPES.join(", ")
+    ))
+}
+
+/// Change the hooks with `update` under the config lock, save, and show the result
+fn update_hooks<F>(config_path: &Path, update: F) -> Result<()>
+where
+    F: FnOnce(&mut Hooks) -> Result<String>,
+{
+    let _lock = ConfigLock::acquire(config_path)?;
+    let mut config = GitWorktreeConfig::load_for_update(config_path)?;
+    let message = update(config.hooks.get_or_insert_with(Hooks::empty))?;
+    config.save(config_path)?;
+
+    println!("{}", message.green());
+    println!();
+    print_hooks(config.hooks.as_ref());
+    Ok(())
+}
+
+/// Print every hook type with its commands numbered from 1
+fn print_hooks(hooks: Option<&Hooks>) {
+    for hook_type in HOOK_TYPES {
+        println!("{}", hook_type.cyan().bold());
+        match hooks.and_then(|hooks| hooks.get(hook_type)) {
+            Some(commands) if !commands.is_empty() => {
+                for (i, command) in commands.iter().enumerate() {
+                    println!("  {}. {}", i + 1, command);
+                }
+            }
+            _ => println!("  {}", "(none)".dimmed()),
+        }
+    }
+}
+
+fn key_kind(key: &str) -> Result<KeyKind> {
+    KEYS.iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, kind)| *kind)
+        .ok_or_else(|| {
+            let valid: Vec<&str> = KEYS.iter().map(|(name, _)| *name).collect();
+            Error::config(format!(
+                "Unknown config key '{}'. Valid keys: {}",
+                key,
+                valid.join(", ")
+            ))
+        })
+}
+
+/// Read a top-level key as display text (`None` if unset)
+fn get_value(config: &GitWorktreeConfig, key: &str) -> Result<Option<String>> {
+    key_kind(key)?;
+    let json = serde_json::to_value(config)?;
+    Ok(match json.get(key) {
+        None | Some(Value::Null) => None,
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(other) => Some(other.to_string()),
+    })
+}
+
+/// Return a copy of the config with `key` set, validated by deserializing it again
+fn set_value(config: &GitWorktreeConfig, key: &str, value: &str) -> Result<GitWorktreeConfig> {
+    let parsed = match key_kind(key)? {
+        KeyKind::Text => Value::String(value.to_string()),
+        KeyKind::Number => value
+            .parse::<u64>()
+            .map(Value::from)
+            .map_err(|_| Error::config(format!("'{}' must be a non-negative number", key)))?,
+        KeyKind::Bool => value
+            .parse::<bool>()
+            .map(Value::Bool)
+            .map_err(|_| Error::config(format!("'{}' must be true or false", key)))?,
+    };
+
+    if key == "sourceControl" && !SOURCE_CONTROLS.contains(&value) {
+        return Err(Error::config(format!(
+            "Invalid sourceControl '{}'. Valid values: {}",
+            value,
+            SOURCE_CONTROLS.join(", ")
+        )));
+    }
+
+    let mut json = serde_json::to_value(config)?;
+    if let Value::Object(ref mut map) = json {
+        map.insert(key.to_string(), parsed);
+    }
+    serde_json::from_value(json).map_err(|e| Error::config(format!("Invalid value for '{}': {}", key, e)))
+}
+
+/// Open the config file with the system default application
+fn open(config_path: &Path) -> Result<()> {
     println!("Opening config: {}", config_path.display());
 
     #[cfg(target_os = "macos")]
@@ -15,9 +199,35 @@ pub fn run() -> Result<()> {
     let cmd = "start";
 
     std::process::Command::new(cmd)
-        .arg(&config_path)
+        .arg(config_path)
         .spawn()
         .map_err(|e| Error::config(format!("Failed to open config file: {}", e)))?;
 
     Ok(())
 }
+
+/// Open the config file in $VISUAL / $EDITOR and wait for it to exit
+fn edit(config_path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .ok_or_else(|| Error::config("No editor configured. Set $EDITOR (or $VISUAL) and try again."))?;
+
+    // Allow editors with arguments, e.g. EDITOR="code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(config_path)
+        .status()
+        .map_err(|e| Error::config(format!("Failed to launch editor '{}': {}", editor, e)))?;
+
+    if !status.success() {
+        return Err(Error::config(format!("Editor exited with status {:?}", status.code())));
+    }
+
+    // Catch syntax errors right away rather than on the next command
+    GitWorktreeConfig::load(config_path)?;
+    Ok(())
+}
@@ -0,0 +1,260 @@
+use colored::Colorize;
+use std::process::Command;
+
+use crate::{
+    bitbucket_auth::{self, BitbucketAuth},
+    bitbucket_data_center_auth::{self, BitbucketDataCenterAuth},
+    config::GitWorktreeConfig,
+    core::project::find_git_directory,
+    error::{Error, Result},
+    git,
+    gitea_auth::GiteaAuth,
+    github::GitHubClient,
+};
+
+struct Check {
+    name: String,
+    ok: bool,
+    /// A failing critical check makes `gwt doctor` exit non-zero
+    critical: bool,
+    detail: String,
+    hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: true,
+            critical: false,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: false,
+            critical: false,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn critical(mut self) -> Self {
+        self.critical = true;
+        self
+    }
+}
+
+/// Diagnose common setup problems and print a ✓/✗ line per check
+pub fn run() -> Result<()> {
+    let mut checks = vec![check_git()];
+
+    let config = match GitWorktreeConfig::find_config() {
+        Ok(Some((path, config))) => {
+            checks.push(Check::pass("Config", path.display().to_string()));
+            Some(config)
+        }
+        Ok(None) => {
+            checks.push(
+                Check::fail(
+                    "Config",
+                    "no config found for this project",
+                    "Run 'gwt init' inside the repository",
+                )
+                .critical(),
+            );
+            None
+        }
+        Err(e) => {
+            checks.push(
+                Check::fail(
+                    "Config",
+                    e.to_string(),
+                    "Fix the file shown by 'gwt config path' or re-run 'gwt init'",
+                )
+                .critical(),
+            );
+            None
+        }
+    };
+
+    if let Some(config) = &config {
+        let unknown = config.unknown_keys();
+        if !unknown.is_empty() {
+            checks.push(Check::fail(
+                "Config keys",
+                format!("unknown key(s) ignored: {}", unknown.join(", ")),
+                "Check for typos; 'gwt config set' lists the valid keys when given an unknown one",
+            ));
+        }
+        checks.extend(check_provider(&config.source_control));
+    }
+
+    checks.push(check_worktrees());
+
+    for check in &checks {
+        print_check(check);
+    }
+
+    let failed_critical = checks.iter().filter(|c| !c.ok && c.critical).count();
+    let failed = checks.iter().filter(|c| !c.ok).count();
+
+    println!();
+    if failed_critical > 0 {
+        return Err(Error::msg(format!("{} critical check(s) failed", failed_critical)));
+    }
+    if failed > 0 {
+        println!("{}", format!("{} check(s) need attention", failed).yellow());
+    } else {
+        println!("{}", "✓ Everything looks good".green());
+    }
+    Ok(())
+}
+
+fn print_check(check: &Check) {
+    if check.ok {
+        println!("{} {} {}", "✓".green(), check.name.bold(), check.detail.dimmed());
+    } else {
+        println!("{} {} {}", "✗".red(), check.name.bold(), check.detail);
+        if let Some(hint) = &check.hint {
+            println!("    {}", hint.yellow());
+        }
+    }
+}
+
+fn command_version(program: &str) -> Option<String> {
+    Command::new(program)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|stdout| stdout.lines().next().map(|line| line.trim().to_string()))
+}
+
+fn check_git() -> Check {
+    match command_version("git") {
+        Some(version) => Check::pass("Git", version),
+        None => Check::fail(
+            "Git",
+            "git not found on PATH",
+            "Install git and make sure it is on your PATH",
+        )
+        .critical(),
+    }
+}
+
+fn check_provider(source_control: &str) -> Vec<Check> {
+    match source_control {
+        "github" => {
+            let client = GitHubClient::new();
+            let cli = match command_version("gh") {
+                Some(version) => Check::pass("GitHub CLI", version),
+                None if client.has_auth() => Check::pass("GitHub CLI", "not installed (using GitHub token)"),
+                None => Check::fail(
+                    "GitHub CLI",
+                    "gh not found on PATH",
+                    "Install it from https://cli.github.com to see PR status",
+                ),
+            };
+            let auth = if client.uses_gh() {
+                Check::pass("GitHub auth", "gh is authenticated")
+            } else if client.has_auth() {
+                Check::pass("GitHub auth", "GitHub token found")
+            } else {
+                Check::fail(
+                    "GitHub auth",
+                    "neither gh nor a GitHub token is configured",
+                    "Run 'gh auth login' or 'gwt auth github setup'",
+                )
+            };
+            vec![cli, auth]
+        }
+        "bitbucket-cloud" => {
+            let authenticated = bitbucket_auth::get_auth_from_config()
+                .and_then(|(workspace, repo, email)| BitbucketAuth::new(workspace, repo, email))
+                .map(|auth| auth.has_stored_token())
+                .unwrap_or(false);
+            vec![if authenticated {
+                Check::pass("Bitbucket Cloud auth", "API token found")
+            } else {
+                Check::fail(
+                    "Bitbucket Cloud auth",
+                    "no API token found",
+                    "Run 'gwt auth bitbucket-cloud setup' for instructions",
+                )
+            }]
+        }
+        "bitbucket-data-center" => {
+            let authenticated = bitbucket_data_center_auth::get_auth_from_config()
+                .and_then(|(base_url, project_key, repo_slug)| {
+                    BitbucketDataCenterAuth::new(project_key, repo_slug, base_url)
+                })
+                .and_then(|auth| auth.get_token())
+                .is_ok();
+            vec![if authenticated {
+                Check::pass("Bitbucket Data Center auth", "access token found")
+            } else {
+                Check::fail(
+                    "Bitbucket Data Center auth",
+                    "no access token found",
+                    "Run 'gwt auth bitbucket-data-center setup' for instructions",
+                )
+            }]
+        }
+        "gitea" => {
+            let authenticated = GiteaAuth::new().and_then(|auth| auth.get_token()).is_ok();
+            vec![if authenticated {
+                Check::pass("Gitea auth", "access token found")
+            } else {
+                Check::fail(
+                    "Gitea auth",
+                    "no access token found",
+                    "Run 'gwt auth gitea setup' for instructions",
+                )
+            }]
+        }
+        other => vec![Check::fail(
+            "Provider",
+            format!("unknown sourceControl '{}'", other),
+            "Use github, bitbucket-cloud, bitbucket-data-center, or gitea",
+        )
+        .critical()],
+    }
+}
+
+fn check_worktrees() -> Check {
+    let worktrees = match find_git_directory().and_then(|git_dir| git::list_worktrees(Some(&git_dir))) {
+        Ok(worktrees) => worktrees,
+        Err(e) => {
+            return Check::fail(
+                "Worktrees",
+                e.to_string(),
+                "Run gwt from inside the project or one of its worktrees",
+            )
+            .critical()
+        }
+    };
+
+    let missing: Vec<String> = worktrees
+        .iter()
+        .filter(|wt| !wt.path.exists())
+        .map(|wt| wt.path.display().to_string())
+        .collect();
+
+    if missing.is_empty() {
+        Check::pass(
+            "Worktrees",
+            format!("{} worktree(s), all directories present", worktrees.len()),
+        )
+    } else {
+        Check::fail(
+            "Worktrees",
+            format!("missing directories: {}", missing.join(", ")),
+            "Run 'gwt prune' to clean up stale worktrees",
+        )
+    }
+}
@@ -0,0 +1,45 @@
+use colored::Colorize;
+
+use crate::{
+    core::project::{find_existing_worktree, find_project_root},
+    error::Result,
+    git,
+};
+
+/// Fetch all remotes once for every worktree
+///
+/// Worktrees share one object store, so a single fetch from any of them updates all.
+pub fn run(prune: bool) -> Result<()> {
+    let project_root = find_project_root()?;
+    let git_dir = find_existing_worktree(&project_root)?;
+
+    let before = git::remote_refs(&git_dir)?;
+
+    println!("{}", "Fetching all remotes...".cyan());
+    let mut args = vec!["fetch", "--all"];
+    if prune {
+        args.push("--prune");
+    }
+    git::execute_streaming(&args, Some(&git_dir))?;
+
+    let after = git::remote_refs(&git_dir)?;
+
+    let updated = after
+        .iter()
+        .filter(|(name, sha)| before.get(*name) != Some(*sha))
+        .count();
+    let deleted = before.keys().filter(|name| !after.contains_key(*name)).count();
+
+    if updated == 0 && deleted == 0 {
+        println!("{}", "✓ Already up to date".green());
+    } else if deleted == 0 {
+        println!("{}", format!("✓ Updated {} remote ref(s)", updated).green());
+    } else {
+        println!(
+            "{}",
+            format!("✓ Updated {} remote ref(s), pruned {}", updated, deleted).green()
+        );
+    }
+
+    Ok(())
+}
@@ -1,53 +1,210 @@
 use colored::Colorize;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 use crate::cli::Provider;
-use crate::config::{generate_config_filename, GitWorktreeConfig, CONFIG_FILENAME};
+use crate::config::{config_filename, generate_config_filename, repository_urls_match, GitWorktreeConfig};
+use crate::constants::DEFAULT_REMOTE;
+use crate::core::project::BARE_DIR;
+use crate::core::utils::get_repo_name_from_url;
 use crate::error::{Error, Result};
 use crate::git;
-use crate::{bitbucket_api, github};
+use crate::status;
+use crate::{bitbucket_api, gitea, github};
+
+/// What init does when the current directory already belongs to a project
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExistingProject {
+    /// Fail (the default)
+    Refuse,
+    /// Overwrite the config, but only when this repository is that project (`--force`)
+    Reinitialize,
+    /// Create a separate project inside it (`--nested`)
+    Nest,
+}
+
+/// Repository details gathered before the config is written
+struct Setup {
+    repo_url: String,
+    provider: Provider,
+    main_branch: String,
+    project_path: PathBuf,
+    worktrees_path: PathBuf,
+    /// Where `--local` puts the config file
+    local_config_dir: PathBuf,
+}
 
 /// Initialize git-worktree-cli for an existing repository
-pub fn run(local: bool) -> Result<()> {
-    // Check if we're in a git repository
-    let git_root = git::get_git_root()?
-        .ok_or_else(|| Error::git("Not in a git repository. Please run this command from inside a git repository."))?;
+///
+/// The existing checkout is reused as-is; nothing is cloned. When `expected_url`
+/// is given, the repository's remote must point at the same repository. `remote`
+/// selects which remote to read (origin by default). With `bare`, `expected_url`
+/// is cloned instead into a bare-repository layout (see [`clone_bare`]).
+/// `provider` overrides detection from the URL, and `branch` the main branch
+/// detected from the remote. `existing` decides what happens inside a directory that
+/// already belongs to a project.
+pub fn run(
+    local: bool,
+    expected_url: Option<&str>,
+    remote: Option<&str>,
+    bare: bool,
+    provider: Option<Provider>,
+    branch: Option<&str>,
+    existing: ExistingProject,
+) -> Result<()> {
+    let enclosing = match existing {
+        ExistingProject::Nest => None,
+        _ => enclosing_project()?,
+    };
+    if let Some((config_path, _)) = &enclosing {
+        if existing == ExistingProject::Refuse || bare {
+            return Err(already_in_project(config_path));
+        }
+    }
+    let remote = remote.unwrap_or(DEFAULT_REMOTE);
 
-    // Get the remote URL
-    let repo_url = git::get_remote_origin_url(&git_root)
-        .ok_or_else(|| Error::git("No remote 'origin' found. Please add a remote first."))?;
+    let setup = if bare {
+        let repo_url = expected_url.ok_or_else(|| Error::msg("--bare needs the repository URL to clone"))?;
+        clone_bare(repo_url, remote, &std::env::current_dir()?, provider, branch)?
+    } else {
+        detect_existing(expected_url, remote, provider, branch)?
+    };
+    if let Some((config_path, config)) = &enclosing {
+        let same_project = config
+            .project_path
+            .as_ref()
+            .is_some_and(|path| path.canonicalize().unwrap_or_else(|_| path.clone()) == setup.project_path);
+        if !same_project {
+            return Err(Error::config(format!(
+                "{} belongs to another gwt project (config: {}); --force only re-initializes that \
+                 project from its own repository. Pass --nested to create a separate project here.",
+                setup.project_path.display(),
+                config_path.display()
+            )));
+        }
+    }
+    write_config(setup, local, remote)
+}
 
-    // Detect the repository provider
-    let detected_provider = detect_provider_from_url(&repo_url).ok_or_else(|| create_provider_error(&repo_url))?;
+/// The config of the project whose repository or worktrees contain the current directory
+///
+/// A local config counts when its project or worktrees path contains the current
+/// directory; without recorded paths, its own directory does. A `--local` config sits
+/// in the parent directory, so sibling repositories next to the project are not inside
+/// it. Global configs count by their recorded paths alone: the one matched by remote
+/// URL may belong to a different clone.
+fn enclosing_project() -> Result<Option<(PathBuf, GitWorktreeConfig)>> {
+    let current_dir = std::env::current_dir()?;
+    let current_dir = current_dir.canonicalize().unwrap_or(current_dir);
+    let contains_current_dir = |config: &GitWorktreeConfig, fallback: Option<&Path>| {
+        let mut covered: Vec<PathBuf> = [config.project_path.clone(), config.worktrees_path.clone()]
+            .into_iter()
+            .flatten()
+            .collect();
+        if covered.is_empty() {
+            covered.extend(fallback.map(Path::to_path_buf));
+        }
+        covered
+            .into_iter()
+            .any(|dir| current_dir.starts_with(dir.canonicalize().unwrap_or(dir)))
+    };
 
-    println!("{}", format!("✓ Detected provider: {:?}", detected_provider).green());
+    let projects_dir = GitWorktreeConfig::projects_config_dir()?;
+    if let Some((config_path, config)) = GitWorktreeConfig::find_config()? {
+        let fallback = if config_path.starts_with(&projects_dir) {
+            None
+        } else {
+            config_path.parent()
+        };
+        if contains_current_dir(&config, fallback) {
+            return Ok(Some((config_path, config)));
+        }
+    }
 
-    // Get the default branch name from the remote
-    let current_branch = git::get_remote_default_branch(&git_root)
-        .map_err(|e| Error::git(format!("Failed to detect default branch: {}", e)))?;
+    let Ok(entries) = fs::read_dir(&projects_dir) else {
+        return Ok(None);
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonc"))
+        .collect();
+    paths.sort();
+    Ok(paths.into_iter().find_map(|path| {
+        let config = GitWorktreeConfig::load(&path).ok()?;
+        contains_current_dir(&config, None).then_some((path, config))
+    }))
+}
 
-    // Use the git root as the project path
-    let project_path = git_root.canonicalize().unwrap_or_else(|_| git_root.clone());
+fn already_in_project(config_path: &Path) -> Error {
+    Error::config(format!(
+        "Already inside a gwt project (config: {}).\n\
+         Run gwt commands from here, pass --force to re-initialize it from its repository, \
+         or --nested to create a separate project inside it.",
+        config_path.display()
+    ))
+}
 
-    // Derive the worktrees path (repo-name -> repo-name-worktrees)
-    let worktrees_path = GitWorktreeConfig::derive_worktrees_path(&project_path);
+/// Clone `repo_url` into `<dir>/<repo-name>` and initialize it (`gwt clone`)
+///
+/// `dir` defaults to the current directory and is created if missing. With `bare`,
+/// the bare-repository layout from `gwt init --bare` is used instead of a regular clone.
+/// `branch` is checked out and recorded instead of the remote's default branch.
+pub fn run_clone(
+    repo_url: &str,
+    dir: Option<&Path>,
+    local: bool,
+    remote: Option<&str>,
+    bare: bool,
+    provider: Option<Provider>,
+    branch: Option<&str>,
+) -> Result<()> {
+    let remote = remote.unwrap_or(DEFAULT_REMOTE);
+
+    let parent_dir = match dir {
+        Some(dir) => {
+            let dir = std::env::current_dir()?.join(dir);
+            fs::create_dir_all(&dir)
+                .map_err(|e| Error::msg(format!("Failed to create directory '{}': {}", dir.display(), e)))?;
+            dir.canonicalize().unwrap_or(dir)
+        }
+        None => std::env::current_dir()?,
+    };
+
+    let setup = if bare {
+        clone_bare(repo_url, remote, &parent_dir, provider, branch)?
+    } else {
+        clone_regular(repo_url, remote, &parent_dir, provider, branch)?
+    };
+    write_config(setup, local, remote)
+}
+
+/// Save the config for a detected or freshly cloned repository and report it
+fn write_config(setup: Setup, local: bool, remote: &str) -> Result<()> {
+    let Setup {
+        repo_url,
+        provider,
+        main_branch,
+        project_path,
+        worktrees_path,
+        local_config_dir,
+    } = setup;
 
     // Create configuration
-    let config = GitWorktreeConfig::new(
+    let mut config = GitWorktreeConfig::new(
         repo_url.clone(),
-        current_branch.clone(),
-        detected_provider,
+        main_branch.clone(),
+        provider,
         Some(project_path.clone()),
         Some(worktrees_path.clone()),
     );
+    if remote != DEFAULT_REMOTE {
+        config.default_remote = Some(remote.to_string());
+    }
 
     // Determine config location
     let config_path = if local {
-        // For local, put config in the parent directory (next to the repo)
-        project_path
-            .parent()
-            .map(|p| p.join(CONFIG_FILENAME))
-            .unwrap_or_else(|| project_path.join(CONFIG_FILENAME))
+        local_config_dir.join(config_filename())
     } else {
         let projects_dir = GitWorktreeConfig::projects_config_dir()?;
         fs::create_dir_all(&projects_dir)
@@ -61,24 +218,253 @@ pub fn run(local: bool) -> Result<()> {
         .map_err(|e| Error::config(format!("Failed to save configuration: {}", e)))?;
 
     // Print success messages
-    println!("{}", format!("✓ Repository: {}", repo_url).green());
-    println!("{}", format!("✓ Main branch: {}", current_branch).green());
-    println!("{}", format!("✓ Project path: {}", project_path.display()).green());
-    println!("{}", format!("✓ Worktrees path: {}", worktrees_path.display()).green());
-    println!("{}", format!("✓ Config saved to: {}", config_path.display()).green());
+    status!("{}", format!("✓ Repository: {}", repo_url).green());
+    if remote != DEFAULT_REMOTE {
+        status!("{}", format!("✓ Remote: {}", remote).green());
+    }
+    status!("{}", format!("✓ Main branch: {}", main_branch).green());
+    status!("{}", format!("✓ Project path: {}", project_path.display()).green());
+    status!("{}", format!("✓ Worktrees path: {}", worktrees_path.display()).green());
+    status!("{}", format!("✓ Config saved to: {}", config_path.display()).green());
 
     if !local {
-        println!("{}", "  (Use --local to store config in project directory)".dimmed());
+        status!("{}", "  (Use --local to store config in project directory)".dimmed());
     }
 
     Ok(())
 }
 
+/// Reuse the checkout the command runs in
+fn detect_existing(
+    expected_url: Option<&str>,
+    remote: &str,
+    provider: Option<Provider>,
+    branch: Option<&str>,
+) -> Result<Setup> {
+    // Check if we're in a git repository
+    let git_root = git::get_git_root()?
+        .ok_or_else(|| Error::git("Not in a git repository. Please run this command from inside a git repository."))?;
+
+    // Get the remote URL
+    let repo_url = git::get_remote_url(&git_root, remote)
+        .ok_or_else(|| Error::git(format!("No remote '{}' found. Please add a remote first.", remote)))?;
+
+    if let Some(expected_url) = expected_url {
+        if !repository_urls_match(&repo_url, expected_url) {
+            return Err(Error::git(format!(
+                "Existing repository's {} ({}) does not match {}",
+                remote, repo_url, expected_url
+            )));
+        }
+    }
+
+    // Detect the repository provider
+    let detected_provider = resolve_provider(&repo_url, provider)?;
+
+    // Use the requested branch, or get the default branch name from the remote
+    let main_branch = match branch {
+        Some(branch) => {
+            ensure_remote_branch(Some(&git_root), remote, branch)?;
+            branch.to_string()
+        }
+        None => git::get_remote_default_branch(&git_root, remote)
+            .map_err(|e| Error::git(format!("Failed to detect default branch: {}", e)))?,
+    };
+
+    // Use the git root as the project path
+    let project_path = git_root.canonicalize().unwrap_or_else(|_| git_root.clone());
+
+    // Derive the worktrees path (repo-name -> repo-name-worktrees)
+    let worktrees_path = GitWorktreeConfig::derive_worktrees_path(&project_path);
+
+    // For local, put config in the parent directory (next to the repo)
+    let local_config_dir = project_path.parent().unwrap_or(&project_path).to_path_buf();
+
+    Ok(Setup {
+        repo_url,
+        provider: detected_provider,
+        main_branch,
+        project_path,
+        worktrees_path,
+        local_config_dir,
+    })
+}
+
+/// Clone `repo_url` into `<parent_dir>/<repo-name>` as a regular checkout
+///
+/// Same layout as running `gwt init` in an existing clone: worktrees go to the
+/// sibling `<repo-name>-worktrees/`, and `--local` config goes next to the repo.
+fn clone_regular(
+    repo_url: &str,
+    remote: &str,
+    parent_dir: &Path,
+    provider: Option<Provider>,
+    branch: Option<&str>,
+) -> Result<Setup> {
+    let provider = resolve_provider(repo_url, provider)?;
+
+    let project_path = new_project_path(repo_url, parent_dir)?;
+    if let Some(branch) = branch {
+        ensure_remote_branch(None, repo_url, branch)?;
+    }
+    git::clone(repo_url, &project_path, remote, branch)?;
+
+    let main_branch = match branch {
+        Some(branch) => branch.to_string(),
+        None => git::get_remote_default_branch(&project_path, remote)
+            .map_err(|e| Error::git(format!("Failed to detect default branch: {}", e)))?,
+    };
+
+    let project_path = project_path.canonicalize().unwrap_or(project_path);
+    Ok(Setup {
+        repo_url: repo_url.to_string(),
+        provider,
+        main_branch,
+        worktrees_path: GitWorktreeConfig::derive_worktrees_path(&project_path),
+        local_config_dir: parent_dir.to_path_buf(),
+        project_path,
+    })
+}
+
+/// `<parent_dir>/<repo-name>`, which must not exist yet
+fn new_project_path(repo_url: &str, parent_dir: &Path) -> Result<PathBuf> {
+    let repo_name = get_repo_name_from_url(repo_url)
+        .ok_or_else(|| Error::msg(format!("Could not determine repository name from URL: {}", repo_url)))?;
+    let project_path = parent_dir.join(&repo_name);
+    if project_path.exists() {
+        return Err(Error::msg(format!(
+            "Directory '{}' already exists",
+            project_path.display()
+        )));
+    }
+    Ok(project_path)
+}
+
+/// Clone `repo_url` into a bare-repository layout under `parent_dir`
+///
+/// ```text
+/// <repo-name>/
+/// ├── .bare/      # git clone --bare
+/// ├── .git        # file: "gitdir: ./.bare"
+/// └── <main>/     # linked worktree for the main branch
+/// ```
+///
+/// Every branch, including the main one, is a linked worktree inside the project
+/// directory, so the project directory doubles as the worktrees path.
+fn clone_bare(
+    repo_url: &str,
+    remote: &str,
+    parent_dir: &Path,
+    provider: Option<Provider>,
+    branch: Option<&str>,
+) -> Result<Setup> {
+    let provider = resolve_provider(repo_url, provider)?;
+
+    let project_path = new_project_path(repo_url, parent_dir)?;
+    if let Some(branch) = branch {
+        ensure_remote_branch(None, repo_url, branch)?;
+    }
+    fs::create_dir_all(&project_path)?;
+
+    status!("{}", format!("Cloning {} (bare)...", repo_url).cyan());
+    let bare_dir = project_path.join(BARE_DIR);
+    git::execute_streaming(
+        &[
+            "clone",
+            "--bare",
+            "--origin",
+            remote,
+            repo_url,
+            bare_dir.to_str().unwrap(),
+        ],
+        None,
+    )?;
+    fs::write(project_path.join(".git"), format!("gitdir: ./{}\n", BARE_DIR))?;
+
+    // A bare clone has no remote-tracking refs; configure them so ahead/behind and
+    // `gwt add` see the remote branches
+    configure_remote_tracking(&project_path, remote)?;
+
+    let main_branch = match branch {
+        Some(branch) => branch.to_string(),
+        None => git::get_remote_default_branch(&project_path, remote)
+            .map_err(|e| Error::git(format!("Failed to detect default branch: {}", e)))?,
+    };
+    add_main_worktree(&project_path, &main_branch, remote)?;
+
+    let project_path = project_path.canonicalize().unwrap_or(project_path);
+    Ok(Setup {
+        repo_url: repo_url.to_string(),
+        provider,
+        main_branch,
+        worktrees_path: project_path.clone(),
+        local_config_dir: project_path.clone(),
+        project_path,
+    })
+}
+
+/// Fail unless `remote` (a remote name or URL) has `branch`, before anything is cloned or saved
+fn ensure_remote_branch(cwd: Option<&Path>, remote: &str, branch: &str) -> Result<()> {
+    git::validate_branch_name(branch)?;
+    let exists = git::remote_has_branch(cwd, remote, branch)
+        .map_err(|e| Error::git(format!("Failed to list branches on {}: {}", remote, e)))?;
+    if !exists {
+        return Err(Error::branch(format!(
+            "Branch '{}' does not exist on {}",
+            branch, remote
+        )));
+    }
+    Ok(())
+}
+
+fn configure_remote_tracking(project_path: &Path, remote: &str) -> Result<()> {
+    let refspec = format!("+refs/heads/*:refs/remotes/{}/*", remote);
+    git::execute_capture(
+        &["config", &format!("remote.{}.fetch", remote), &refspec],
+        Some(project_path),
+    )?;
+    git::execute_streaming(&["fetch", remote], Some(project_path))
+}
+
+fn add_main_worktree(project_path: &Path, main_branch: &str, remote: &str) -> Result<()> {
+    let worktree_path = project_path.join(main_branch);
+    git::execute_streaming(
+        &["worktree", "add", worktree_path.to_str().unwrap(), main_branch],
+        Some(project_path),
+    )?;
+    git::execute_capture(
+        &[
+            "branch",
+            &format!("--set-upstream-to={}/{}", remote, main_branch),
+            main_branch,
+        ],
+        Some(project_path),
+    )?;
+    status!(
+        "{}",
+        format!("✓ Main worktree created at: {}", worktree_path.display()).green()
+    );
+    Ok(())
+}
+
+/// The provider given with `--provider`, or the one detected from the URL
+fn resolve_provider(repo_url: &str, provider: Option<Provider>) -> Result<Provider> {
+    if let Some(provider) = provider {
+        status!("{}", format!("✓ Provider: {:?}", provider).green());
+        return Ok(provider);
+    }
+    let provider = detect_provider_from_url(repo_url).ok_or_else(|| create_provider_error(repo_url))?;
+    status!("{}", format!("✓ Detected provider: {:?}", provider).green());
+    Ok(provider)
+}
+
 fn detect_provider_from_url(repo_url: &str) -> Option<Provider> {
     if github::GitHubClient::parse_github_url(repo_url).is_some() {
         Some(Provider::Github)
     } else if bitbucket_api::is_bitbucket_repository(repo_url) {
         Some(Provider::BitbucketCloud)
+    } else if gitea::is_known_gitea_host(repo_url) {
+        Some(Provider::Gitea)
     } else {
         None
     }
@@ -87,7 +473,9 @@ fn detect_provider_from_url(repo_url: &str) -> Option<Provider> {
 fn create_provider_error(repo_url: &str) -> Error {
     Error::provider(format!(
         "Could not detect repository provider from URL: {}\n\
-         Supported providers: GitHub, Bitbucket Cloud",
+         Supported providers: GitHub, Bitbucket Cloud, Codeberg. For a self-hosted\n\
+         Bitbucket Data Center or Gitea/Forgejo instance, pass --provider bitbucket-data-center\n\
+         or --provider gitea",
         repo_url
     ))
 }
@@ -1,40 +1,161 @@
 use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::list_helpers::{
-    extract_bitbucket_cloud_url, extract_bitbucket_data_center_url, fetch_pr_for_branch, PullRequestInfo,
+    extract_bitbucket_cloud_url, extract_bitbucket_data_center_url, fetch_pr_for_branch, gitea_pull_request_info,
+    PullRequestInfo,
 };
 use crate::{
-    bitbucket_api, bitbucket_auth, bitbucket_data_center_api, bitbucket_data_center_auth, config,
-    core::project::{clean_branch_name, find_git_directory},
-    error::Result,
-    git, github,
+    bitbucket_api, bitbucket_auth, bitbucket_data_center_api, bitbucket_data_center_auth,
+    cache::{self, PrCache},
+    cli::{ListFilter, ListGroupBy, ListSort},
+    config,
+    core::project::{clean_branch_name, Project},
+    error::{Error, Result},
+    git, gitea, gitea_auth, github,
+    output::Spinner,
 };
 
+/// Maximum number of PR lookups in flight at once
+const PR_LOOKUP_CONCURRENCY: usize = 8;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Environment variable that turns on `--offline` (any value but empty, `0`, `false` or `no`)
+pub const OFFLINE_ENV: &str = "GWT_OFFLINE";
+
 struct WorktreeDisplay {
     branch: String,
+    path: PathBuf,
+    head: String,
+    bare: bool,
+    detached: bool,
+    locked: Option<String>,
+    /// Whether the current directory is inside this worktree
+    current: bool,
+    /// Age in days of the last commit, set only when it exceeds `--stale`
+    stale_days: Option<u64>,
+    /// Short SHA and subject of the checked-out commit, read only for `--show-commit`
+    last_commit: Option<(String, String)>,
     pr_info: Option<PullRequestInfo>,
 }
 
+#[derive(Serialize, Deserialize)]
 struct RemotePullRequest {
     branch: String,
     pr_info: PullRequestInfo,
+    /// Unix time of the last update, for `--since`
+    #[serde(default)]
+    updated_at: Option<i64>,
+}
+
+/// Machine-readable list entry for `gwt list --json`
+///
+/// Remote-only pull requests (no local worktree) have a null `path` and `head`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ListEntryJson {
+    branch: String,
+    path: Option<PathBuf>,
+    head: Option<String>,
+    bare: bool,
+    current: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stale_days: Option<u64>,
+    /// Subject of the checked-out commit, with `--show-commit`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit_subject: Option<String>,
+    /// Whether the pull request is a draft (false without one)
+    draft: bool,
+    pull_request: Option<PullRequestInfo>,
+}
+
+/// Flags of `gwt list`; see the `List` command in cli.rs for what each one does
+#[derive(Debug, Default)]
+pub struct ListOptions {
+    /// `--local`: skip open PRs without a local worktree
+    pub local_only: bool,
+    pub remote_only: bool,
+    pub json: bool,
+    pub refresh: bool,
+    pub sort: Option<ListSort>,
+    pub filter: Option<ListFilter>,
+    pub group_by: Option<ListGroupBy>,
+    /// `--stale <days>`
+    pub stale: Option<u64>,
+    pub with_authors: bool,
+    pub hide_drafts: bool,
+    pub offline: bool,
+    /// `--limit <n>` for the open PR section (0 for all)
+    pub limit: usize,
+    pub urls_only: bool,
+    pub show_commit: bool,
+    pub no_pr: bool,
+    /// `--format <template>`
+    pub format: Option<String>,
+    /// `--since <when>`, parsed by [`parse_since`]
+    pub since: Option<String>,
 }
 
 #[tokio::main]
-pub async fn run(local_only: bool) -> Result<()> {
-    // Find a git directory to work with
-    let git_dir = find_git_directory()?;
+pub async fn run(options: ListOptions) -> Result<()> {
+    let ListOptions {
+        local_only,
+        remote_only,
+        json,
+        refresh,
+        sort,
+        filter,
+        group_by,
+        stale,
+        with_authors,
+        hide_drafts,
+        offline,
+        limit,
+        urls_only,
+        show_commit,
+        no_pr,
+        format,
+        since,
+    } = options;
+    let format = format.as_deref();
+    // Parse up front so a typo fails before any network work
+    let since = since
+        .as_deref()
+        .map(|since| parse_since(since, unix_now()))
+        .transpose()?;
+    let offline = offline || offline_from_env(std::env::var(OFFLINE_ENV).ok().as_deref());
+    // Unlike --local, which still looks up PRs for local worktrees, this skips all PR work
+    let show_prs =
+        !no_pr && config::GitWorktreeConfig::find_config()?.is_none_or(|(_, config)| config.show_pull_requests());
+    // --format prints local worktrees only, so the open PR list isn't needed
+    let local_only = local_only || !show_prs || format.is_some();
+    let skip_providers = offline || !show_prs;
+    // Output meant for other programs: no spinners or messages
+    let plain = json || urls_only || format.is_some();
 
-    // Get the list of worktrees
-    let worktrees = git::list_worktrees(Some(&git_dir))?;
+    // Resolve the project from wherever we are (its root, a worktree, or any subdirectory)
+    let project = Project::find()?;
+    let worktrees = project.worktrees()?;
 
     if worktrees.is_empty() {
-        println!("{}", "No worktrees found.".yellow());
+        if json {
+            println!("[]");
+        } else if !plain {
+            println!("{}", "No worktrees found.".yellow());
+        }
         return Ok(());
     }
 
-    // Try to get GitHub/Bitbucket info automatically
-    let (github_client, bitbucket_client, bitbucket_data_center_client, repo_info) = {
+    // Try to get GitHub/Bitbucket info automatically (offline and --no-pr skip even the
+    // client setup, which may shell out to gh or read the keyring)
+    let (github_client, bitbucket_client, bitbucket_data_center_client, gitea_client, repo_info) = if skip_providers {
+        (None, None, None, None, None)
+    } else {
         let github_client = github::GitHubClient::new();
         let mut bitbucket_client: Option<bitbucket_api::BitbucketClient> = None;
         let mut bitbucket_data_center_client: Option<bitbucket_data_center_api::BitbucketDataCenterClient> = None;
@@ -53,17 +174,21 @@ pub async fn run(local_only: bool) -> Result<()> {
                             config.bitbucket_email.clone(),
                         ) {
                             if auth.has_stored_token() {
-                                bitbucket_client = Some(bitbucket_api::BitbucketClient::new(auth));
+                                bitbucket_client = Some(bitbucket_api::BitbucketClient::new(
+                                    auth,
+                                    config.bitbucket_api_base.as_deref(),
+                                ));
                             }
                         }
                         (
                             Some(github_client),
                             bitbucket_client,
                             None,
+                            None,
                             Some(("bitbucket-cloud".to_string(), workspace, repo)),
                         )
                     } else {
-                        (Some(github_client), None, None, None)
+                        (Some(github_client), None, None, None, None)
                     }
                 }
                 "bitbucket-data-center" => {
@@ -84,6 +209,7 @@ pub async fn run(local_only: bool) -> Result<()> {
                             Some(github_client),
                             None,
                             bitbucket_data_center_client,
+                            None,
                             Some(("bitbucket-data-center".to_string(), project_key, repo_slug)),
                         )
                     } else {
@@ -95,13 +221,30 @@ pub async fn run(local_only: bool) -> Result<()> {
                                 Some(github_client),
                                 None,
                                 None,
+                                None,
                                 Some(("bitbucket-data-center".to_string(), owner, repo)),
                             )
                         } else {
-                            (Some(github_client), None, None, None)
+                            (Some(github_client), None, None, None, None)
                         }
                     }
                 }
+                "gitea" => match gitea_auth::get_auth_from_config() {
+                    Ok((base_url, owner, repo)) => {
+                        let gitea_client = gitea_auth::GiteaAuth::new()
+                            .and_then(|auth| auth.get_token())
+                            .ok()
+                            .map(|token| gitea::GiteaClient::new(token, base_url));
+                        (
+                            Some(github_client),
+                            None,
+                            None,
+                            gitea_client,
+                            Some(("gitea".to_string(), owner, repo)),
+                        )
+                    }
+                    Err(_) => (Some(github_client), None, None, None, None),
+                },
                 _ => {
                     // Try GitHub
                     let (owner, repo) = github::GitHubClient::parse_github_url(repo_url)
@@ -112,15 +255,16 @@ pub async fn run(local_only: bool) -> Result<()> {
                             Some(github_client),
                             None,
                             None,
+                            None,
                             Some(("github".to_string(), owner, repo)),
                         )
                     } else {
-                        (Some(github_client), None, None, None)
+                        (Some(github_client), None, None, None, None)
                     }
                 }
             }
         } else {
-            (Some(github_client), None, None, None)
+            (Some(github_client), None, None, None, None)
         }
     };
 
@@ -130,11 +274,22 @@ pub async fn run(local_only: bool) -> Result<()> {
                 "github" => github_client.as_ref().map(|c| c.has_auth()).unwrap_or(false),
                 "bitbucket-cloud" => bitbucket_client.is_some(),
                 "bitbucket-data-center" => bitbucket_data_center_client.is_some(),
+                "gitea" => gitea_client.is_some(),
                 _ => false,
             },
             None => false,
         };
 
+    // Cached PR results are reused until they expire; --refresh bypasses (and overwrites) them
+    let cache_ttl = config::GitWorktreeConfig::find_config()?
+        .and_then(|(_, config)| config.pr_cache_ttl_secs)
+        .unwrap_or(cache::DEFAULT_TTL_SECS);
+    let mut pr_cache = if has_pr_info && cache_ttl > 0 {
+        PrCache::load(cache_ttl)
+    } else {
+        None
+    };
+
     // Get local branch names for filtering
     let local_branches: Vec<String> = worktrees
         .iter()
@@ -157,136 +312,274 @@ pub async fn run(local_only: bool) -> Result<()> {
                 }
             });
 
-        // Fetch PR info if available
-        let pr_info = if has_pr_info && !wt.bare && branch != "(bare)" {
-            match &repo_info {
-                Some((platform, owner_or_workspace, repo)) => {
-                    let pr_result = fetch_pr_for_branch(
+        display_worktrees.push(WorktreeDisplay {
+            branch,
+            path: wt.path.clone(),
+            head: wt.head.clone(),
+            bare: wt.bare,
+            detached: wt.detached,
+            locked: wt.locked.clone(),
+            current: false,
+            stale_days: None,
+            last_commit: None,
+            pr_info: None,
+        });
+    }
+
+    if let Ok(current_dir) = std::env::current_dir() {
+        mark_current_worktree(&mut display_worktrees, &current_dir);
+    }
+
+    if let Some(threshold_days) = stale {
+        let now = unix_now();
+        for wt in display_worktrees.iter_mut().filter(|wt| !wt.bare) {
+            wt.stale_days = git::last_commit_time(&project.git_dir, &wt.head)
+                .and_then(|time| stale_days(time, now, threshold_days));
+        }
+    }
+
+    if show_commit {
+        // Missing directories (deleted by hand) have nothing to read from
+        for wt in display_worktrees.iter_mut().filter(|wt| !wt.bare && wt.path.is_dir()) {
+            wt.last_commit = git::head_commit_summary(&wt.path);
+        }
+    }
+
+    // --remote-only still needs the local branches (to hide their PRs) but shows no worktrees
+    if remote_only {
+        display_worktrees.clear();
+    }
+
+    // Fetch PR info for all worktrees concurrently (bounded), preserving display order.
+    // Each lookup fails independently so one error doesn't hide the rest.
+    if has_pr_info {
+        if let Some((platform, owner_or_workspace, repo)) = &repo_info {
+            let cache_keys: Vec<String> = display_worktrees
+                .iter()
+                .map(|wt| PrCache::key(platform, owner_or_workspace, repo, &format!("branch:{}", wt.branch)))
+                .collect();
+            let cached: Vec<Option<Option<PullRequestInfo>>> = cache_keys
+                .iter()
+                .map(|key| match &pr_cache {
+                    Some(pr_cache) if !refresh => pr_cache.get(key),
+                    _ => None,
+                })
+                .collect();
+
+            let spinner = (!plain).then(|| Spinner::start("Fetching pull requests..."));
+
+            // Each result is paired with whether it was freshly fetched (and should be cached)
+            let pr_infos: Vec<(Option<PullRequestInfo>, bool)> = stream::iter(display_worktrees.iter().zip(cached))
+                .map(|(wt, cached)| async {
+                    // Bare and detached entries have no branch to look up
+                    if wt.bare || wt.detached {
+                        return (None, false);
+                    }
+                    if let Some(hit) = cached {
+                        return (hit, false);
+                    }
+                    match fetch_pr_for_branch(
                         platform,
                         owner_or_workspace,
                         repo,
-                        &branch,
+                        &wt.branch,
                         &github_client,
                         &bitbucket_client,
                         &bitbucket_data_center_client,
+                        &gitea_client,
                     )
-                    .await;
+                    .await
+                    {
+                        Ok(pr_info) => (pr_info, true),
+                        Err(_) => (None, false),
+                    }
+                })
+                .buffered(PR_LOOKUP_CONCURRENCY)
+                .collect()
+                .await;
+            drop(spinner);
 
-                    pr_result.unwrap_or_default()
+            for ((worktree, (pr_info, fresh)), key) in display_worktrees.iter_mut().zip(pr_infos).zip(cache_keys) {
+                if fresh {
+                    if let Some(pr_cache) = pr_cache.as_mut() {
+                        pr_cache.put(key, &pr_info);
+                    }
                 }
-                None => None,
+                worktree.pr_info = pr_info;
             }
-        } else {
-            None
-        };
+        }
+    }
 
-        display_worktrees.push(WorktreeDisplay { branch, pr_info });
+    if let Some(filter) = filter {
+        display_worktrees.retain(|wt| filter_matches(filter, wt.pr_info.as_ref()));
+    }
+    if let Some(sort) = sort {
+        sort_worktrees(&mut display_worktrees, sort);
+    }
+    if stale.is_some() {
+        // Stable, so stale worktrees keep their relative order at the end
+        display_worktrees.sort_by_key(|wt| wt.stale_days.is_some());
     }
 
     // Display local worktrees
-    if !display_worktrees.is_empty() {
-        println!("{}", "Local Worktrees:".bold());
-        println!();
+    if !display_worktrees.is_empty() && !plain {
+        match group_by {
+            Some(ListGroupBy::Base) => {
+                println!("{}", "Local Worktrees by PR base branch:".bold());
+                for (base, group) in group_by_base(&display_worktrees) {
+                    println!();
+                    match base {
+                        Some(base) => println!("{} {}", "base:".dimmed(), base.bold()),
+                        None => println!("{}", "Ungrouped (no pull request)".bold()),
+                    }
+                    println!();
+                    for worktree in group {
+                        display_worktree(worktree, with_authors, hide_drafts, show_commit);
+                    }
+                }
+            }
+            None => {
+                println!("{}", "Local Worktrees:".bold());
+                println!();
 
-        for worktree in &display_worktrees {
-            display_worktree(worktree);
+                for worktree in &display_worktrees {
+                    display_worktree(worktree, with_authors, hide_drafts, show_commit);
+                }
+            }
         }
     }
 
-    // Fetch all open pull requests and add ones that don't have local worktrees
+    // Fetch open pull requests and add ones that don't have local worktrees
     let mut remote_prs: Vec<RemotePullRequest> = Vec::new();
+    // Whether every open PR was fetched, so the "… and N more" count is exact
+    let mut fetched_all = true;
 
     if has_pr_info && !local_only {
         if let Some((platform, owner_or_workspace, repo)) = &repo_info {
-            match platform.as_str() {
-                "github" => {
-                    if let Some(ref client) = github_client {
-                        if let Ok(all_prs) = client.get_all_pull_requests(owner_or_workspace, repo) {
-                            for (pr, branch_name) in all_prs {
-                                // Skip if we already have a local worktree for this branch
-                                if !local_branches.contains(&branch_name) {
-                                    let status = if pr.draft { "DRAFT" } else { "OPEN" };
-                                    remote_prs.push(RemotePullRequest {
-                                        branch: branch_name,
-                                        pr_info: PullRequestInfo {
-                                            url: pr.html_url,
-                                            status: status.to_string(),
-                                            title: pr.title.clone(),
-                                        },
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-                "bitbucket-cloud" => {
-                    if let Some(ref client) = bitbucket_client {
-                        if let Ok(all_prs) = client.get_pull_requests(owner_or_workspace, repo).await {
-                            for pr in all_prs {
-                                // Only include open PRs
-                                if pr.state == "OPEN" {
-                                    let branch_name = pr.source.branch.name.clone();
-                                    // Skip if we already have a local worktree for this branch
-                                    if !local_branches.contains(&branch_name) {
-                                        let url = extract_bitbucket_cloud_url(&pr);
-                                        remote_prs.push(RemotePullRequest {
-                                            branch: branch_name,
-                                            pr_info: PullRequestInfo {
-                                                url,
-                                                status: "OPEN".to_string(),
-                                                title: pr.title.clone(),
-                                            },
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                "bitbucket-data-center" => {
-                    if let Some(ref client) = bitbucket_data_center_client {
-                        if let Ok(all_prs) = client.get_pull_requests(owner_or_workspace, repo).await {
-                            for pr in all_prs {
-                                // Only include open PRs
-                                if pr.state == "OPEN" {
-                                    let branch_name = pr.from_ref.display_id.clone();
-                                    // Skip if we already have a local worktree for this branch
-                                    if !local_branches.contains(&branch_name) {
-                                        let status = if pr.draft.unwrap_or(false) { "DRAFT" } else { "OPEN" };
-                                        let url = extract_bitbucket_data_center_url(&pr);
-                                        remote_prs.push(RemotePullRequest {
-                                            branch: branch_name,
-                                            pr_info: PullRequestInfo {
-                                                url,
-                                                status: status.to_string(),
-                                                title: pr.title.clone(),
-                                            },
-                                        });
-                                    }
-                                }
-                            }
-                        }
+            // --json, --urls, --sort and filters that drop PRs after the fetch need every open PR
+            let needs_all = json || urls_only || sort.is_some() || filter.is_some() || since.is_some() || hide_drafts;
+            let fetch_limit = open_pr_fetch_limit(limit, needs_all, local_branches.len());
+            let entry = match fetch_limit {
+                Some(max) => format!("open-{}", max),
+                None => "open".to_string(),
+            };
+            let key = PrCache::key(platform, owner_or_workspace, repo, &entry);
+            // Entries cached before update times were recorded can't be judged by --since
+            let cached: Option<Vec<RemotePullRequest>> = match &pr_cache {
+                Some(pr_cache) if !refresh => pr_cache.get(&key).filter(|prs: &Vec<RemotePullRequest>| {
+                    since.is_none() || prs.iter().all(|pr| pr.updated_at.is_some())
+                }),
+                _ => None,
+            };
+
+            let open_prs = match cached {
+                Some(open_prs) => Some(open_prs),
+                None => {
+                    let spinner = (!plain).then(|| Spinner::start("Fetching open pull requests..."));
+                    let fetched = fetch_open_pull_requests(
+                        platform,
+                        owner_or_workspace,
+                        repo,
+                        &github_client,
+                        &bitbucket_client,
+                        &bitbucket_data_center_client,
+                        &gitea_client,
+                        fetch_limit,
+                    )
+                    .await;
+                    drop(spinner);
+                    if let (Some(open_prs), Some(pr_cache)) = (&fetched, pr_cache.as_mut()) {
+                        pr_cache.put(key, open_prs);
                     }
+                    fetched
                 }
-                _ => {}
+            };
+
+            let open_prs = open_prs.unwrap_or_default();
+            fetched_all = fetch_limit.is_none_or(|max| open_prs.len() < max);
+
+            // Skip PRs for branches that already have a local worktree
+            remote_prs = open_prs
+                .into_iter()
+                .filter(|pr| !local_branches.contains(&pr.branch))
+                .filter(|pr| filter.is_none_or(|filter| filter_matches(filter, Some(&pr.pr_info))))
+                .filter(|pr| since.is_none_or(|since| updated_since(pr, since)))
+                .collect();
+            if let Some(sort) = sort {
+                sort_remote_prs(&mut remote_prs, sort);
             }
         }
     }
 
-    // Display remote PRs if any exist
-    if !remote_prs.is_empty() && !local_only {
+    if let Some(pr_cache) = &pr_cache {
+        pr_cache.save();
+    }
+
+    if json {
+        return print_json(display_worktrees, remote_prs);
+    }
+    if urls_only {
+        for url in pr_urls(&display_worktrees, &remote_prs) {
+            println!("{}", url);
+        }
+        return Ok(());
+    }
+    if let Some(template) = format {
+        for worktree in &display_worktrees {
+            let head = (!worktree.head.is_empty()).then_some(worktree.head.as_str());
+            let line = render_template(
+                template,
+                &worktree.branch,
+                Some(&worktree.path),
+                head,
+                worktree.pr_info.as_ref(),
+            );
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    let draft_count = remote_prs.iter().filter(|pr| is_draft(Some(&pr.pr_info))).count();
+    let open_count = remote_prs.len() - draft_count;
+    if hide_drafts {
+        remote_prs.retain(|pr| !is_draft(Some(&pr.pr_info)));
+    }
+    let more = truncate_remote_prs(&mut remote_prs, limit);
+
+    // Display remote PRs if any exist (or were hidden by --hide-drafts)
+    if (!remote_prs.is_empty() || draft_count > 0) && !local_only {
         if !display_worktrees.is_empty() {
             println!(); // Add spacing between sections
         }
         println!("{}", "Open Pull Requests (no local worktree):".bold());
+        let hidden = if hide_drafts && draft_count > 0 {
+            " (hidden)"
+        } else {
+            ""
+        };
+        let at_least = if fetched_all { "" } else { "at least " };
+        println!(
+            "{}",
+            format!("{}{} open, {} draft{}", at_least, open_count, draft_count, hidden).dimmed()
+        );
         println!();
 
         for pr in &remote_prs {
-            display_remote_pr(pr);
+            display_remote_pr(pr, with_authors);
+        }
+        if more > 0 {
+            println!(
+                "{}",
+                format!("… and {}{} more (use --limit 0 to show all)", at_least, more).dimmed()
+            );
         }
+    } else if has_pr_info && !local_only && display_worktrees.is_empty() {
+        println!("{}", "No open pull requests without a local worktree.".yellow());
     }
 
-    if !has_pr_info && !local_only {
+    if offline {
+        println!("\n{}", "offline: skipping PR info".dimmed());
+    } else if !has_pr_info && !local_only {
         if let Some((_, config)) = config::GitWorktreeConfig::find_config()? {
             match config.source_control.as_str() {
                 "bitbucket-cloud" => {
@@ -299,10 +592,17 @@ pub async fn run(local_only: bool) -> Result<()> {
                 "bitbucket-data-center" => {
                     println!("\n{}", "Tip: Run 'gwt auth bitbucket-data-center setup' to enable Bitbucket Data Center pull request information".dimmed());
                 }
+                "gitea" => {
+                    println!(
+                        "\n{}",
+                        "Tip: Run 'gwt auth gitea setup' to enable Gitea pull request information".dimmed()
+                    );
+                }
                 _ => {
                     println!(
                         "\n{}",
-                        "Tip: Run 'gh auth login' to enable GitHub pull request information".dimmed()
+                        "Tip: Run 'gh auth login' or 'gwt auth github setup' to enable GitHub pull request information"
+                            .dimmed()
                     );
                 }
             }
@@ -312,47 +612,733 @@ pub async fn run(local_only: bool) -> Result<()> {
     Ok(())
 }
 
-fn display_worktree(worktree: &WorktreeDisplay) {
-    // Display branch name in cyan
-    println!("{}", worktree.branch.cyan());
+/// Whether a `GWT_OFFLINE` value turns offline mode on
+fn offline_from_env(value: Option<&str>) -> bool {
+    value.is_some_and(|v| !matches!(v.trim().to_lowercase().as_str(), "" | "0" | "false" | "no"))
+}
 
-    // Display PR info if available
-    if let Some(ref pr_info) = worktree.pr_info {
-        // Display URL with status
-        let status_colored = match pr_info.status.as_str() {
-            "OPEN" => "open".green(),
-            "CLOSED" => "closed".red(),
-            "MERGED" => "merged".green(),
-            "DRAFT" => "draft".yellow(),
-            _ => pr_info.status.normal(),
-        };
-        println!("  {} ({})", pr_info.url.blue().underline(), status_colored);
+/// Flag the worktree that contains `current_dir`, preferring the innermost one if worktrees nest
+///
+/// Nothing is flagged when `current_dir` is outside every worktree, e.g. the root of a bare layout.
+fn mark_current_worktree(worktrees: &mut [WorktreeDisplay], current_dir: &Path) {
+    let current = worktrees
+        .iter_mut()
+        .filter(|wt| !wt.bare && current_dir.starts_with(&wt.path))
+        .max_by_key(|wt| wt.path.components().count());
+    if let Some(current) = current {
+        current.current = true;
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Unix time for `--since`: a date (`YYYY-MM-DD`, from midnight UTC) or a duration before
+/// `now` such as `7d`, `24h` or `2w`
+fn parse_since(value: &str, now: i64) -> Result<i64> {
+    let value = value.trim();
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc().timestamp());
+    }
+
+    let invalid = || {
+        Error::msg(format!(
+            "Invalid --since '{}': use a date (YYYY-MM-DD) or a duration like 7d, 24h or 2w",
+            value
+        ))
+    };
+    let (unit_index, _) = value.char_indices().last().ok_or_else(invalid)?;
+    let (amount, unit) = value.split_at(unit_index);
+    let seconds_per_unit = match unit {
+        "h" => 60 * 60,
+        "d" => SECONDS_PER_DAY as i64,
+        "w" => 7 * SECONDS_PER_DAY as i64,
+        _ => return Err(invalid()),
+    };
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    amount
+        .checked_mul(seconds_per_unit)
+        .filter(|seconds| *seconds >= 0)
+        .map(|seconds| now - seconds)
+        .ok_or_else(invalid)
+}
+
+/// Unix time of an RFC 3339 timestamp as reported by GitHub, Bitbucket Cloud and Gitea
+fn rfc3339_timestamp(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| time.timestamp())
+}
+
+/// Whether a remote PR passes `--since` (PRs whose update time is unknown don't)
+fn updated_since(pr: &RemotePullRequest, since: i64) -> bool {
+    pr.updated_at.is_some_and(|updated_at| updated_at >= since)
+}
+
+/// Whole days since `commit_time`, if at least `threshold_days`
+fn stale_days(commit_time: i64, now: i64, threshold_days: u64) -> Option<u64> {
+    let age_days = (now - commit_time).max(0) as u64 / SECONDS_PER_DAY;
+    (age_days >= threshold_days).then_some(age_days)
+}
 
-        // Display title if not empty
-        if !pr_info.title.is_empty() {
-            println!("  {}", pr_info.title.dimmed());
+fn is_draft(pr_info: Option<&PullRequestInfo>) -> bool {
+    pr_info.is_some_and(|pr| pr.status == "DRAFT")
+}
+
+/// Whether an entry with this PR (or none) passes `--filter`
+fn filter_matches(filter: ListFilter, pr_info: Option<&PullRequestInfo>) -> bool {
+    match filter {
+        ListFilter::HasPr => pr_info.is_some(),
+        ListFilter::NoPr => pr_info.is_none(),
+        ListFilter::Draft => is_draft(pr_info),
+    }
+}
+
+/// Order for `--sort status`: active PRs first, then finished ones, then no PR
+fn status_rank(pr_info: Option<&PullRequestInfo>) -> u8 {
+    match pr_info.map(|pr| pr.status.as_str()) {
+        Some("OPEN") => 0,
+        Some("DRAFT") => 1,
+        Some("MERGED") => 2,
+        Some("CLOSED") => 3,
+        Some(_) => 4,
+        None => 5,
+    }
+}
+
+/// Worktrees grouped by their PR's base branch for `--group-by base`, keeping their order
+/// within each group
+///
+/// Bases come in alphabetical order; worktrees without a PR (or whose provider doesn't
+/// report a base) are grouped under `None`, last.
+fn group_by_base(worktrees: &[WorktreeDisplay]) -> Vec<(Option<&str>, Vec<&WorktreeDisplay>)> {
+    let mut groups: BTreeMap<Option<&str>, Vec<&WorktreeDisplay>> = BTreeMap::new();
+    for worktree in worktrees {
+        let base = worktree.pr_info.as_ref().and_then(|pr| pr.base.as_deref());
+        groups.entry(base).or_default().push(worktree);
+    }
+    // `None` sorts first in the map; the ungrouped section goes at the end instead
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    if groups.first().is_some_and(|(base, _)| base.is_none()) {
+        groups.rotate_left(1);
+    }
+    groups
+}
+
+/// How many open PRs to request from the provider for `--limit`, `None` for all of them
+///
+/// Enough to fill the limit even if every local worktree's branch has an open PR, plus one
+/// to tell whether more exist.
+fn open_pr_fetch_limit(limit: usize, needs_all: bool, local_worktrees: usize) -> Option<usize> {
+    (limit > 0 && !needs_all).then(|| limit + local_worktrees + 1)
+}
+
+/// Keep the first `limit` remote PRs (all of them for 0), returning how many were dropped
+fn truncate_remote_prs(remote_prs: &mut Vec<RemotePullRequest>, limit: usize) -> usize {
+    if limit == 0 || remote_prs.len() <= limit {
+        return 0;
+    }
+    let more = remote_prs.len() - limit;
+    remote_prs.truncate(limit);
+    more
+}
+
+fn sort_worktrees(worktrees: &mut [WorktreeDisplay], sort: ListSort) {
+    match sort {
+        ListSort::Name => worktrees.sort_by(|a, b| a.branch.cmp(&b.branch)),
+        ListSort::Status => worktrees.sort_by_key(|wt| status_rank(wt.pr_info.as_ref())),
+        ListSort::Path => worktrees.sort_by(|a, b| a.path.cmp(&b.path)),
+    }
+}
+
+/// Remote-only PRs have no path, so `--sort path` keeps them ordered by branch
+fn sort_remote_prs(remote_prs: &mut [RemotePullRequest], sort: ListSort) {
+    match sort {
+        ListSort::Name | ListSort::Path => remote_prs.sort_by(|a, b| a.branch.cmp(&b.branch)),
+        ListSort::Status => remote_prs.sort_by_key(|pr| status_rank(Some(&pr.pr_info))),
+    }
+}
+
+/// Fetch open pull requests for the repository, at most `max` of them (all for `None`)
+///
+/// Returns `None` if the provider request fails, so failures are never cached.
+#[allow(clippy::too_many_arguments)]
+async fn fetch_open_pull_requests(
+    platform: &str,
+    owner_or_workspace: &str,
+    repo: &str,
+    github_client: &Option<github::GitHubClient>,
+    bitbucket_client: &Option<bitbucket_api::BitbucketClient>,
+    bitbucket_data_center_client: &Option<bitbucket_data_center_api::BitbucketDataCenterClient>,
+    gitea_client: &Option<gitea::GiteaClient>,
+    max: Option<usize>,
+) -> Option<Vec<RemotePullRequest>> {
+    match platform {
+        "github" => {
+            let client = github_client.as_ref()?;
+            let all_prs = client.get_all_pull_requests(owner_or_workspace, repo, max).await.ok()?;
+            Some(
+                all_prs
+                    .into_iter()
+                    .map(|(pr, branch_name)| {
+                        let status = if pr.draft { "DRAFT" } else { "OPEN" };
+                        RemotePullRequest {
+                            branch: branch_name,
+                            pr_info: PullRequestInfo {
+                                url: pr.html_url,
+                                status: status.to_string(),
+                                title: pr.title.clone(),
+                                author: pr.author,
+                                base: pr.base,
+                            },
+                            updated_at: pr.updated_at.as_deref().and_then(rfc3339_timestamp),
+                        }
+                    })
+                    .collect(),
+            )
+        }
+        "bitbucket-cloud" => {
+            let client = bitbucket_client.as_ref()?;
+            let all_prs = client.get_pull_requests(owner_or_workspace, repo, max).await.ok()?;
+            Some(
+                all_prs
+                    .into_iter()
+                    // Only include open PRs
+                    .filter(|pr| pr.state == "OPEN")
+                    .map(|pr| RemotePullRequest {
+                        branch: pr.source.branch.name.clone(),
+                        pr_info: PullRequestInfo {
+                            url: extract_bitbucket_cloud_url(&pr),
+                            status: "OPEN".to_string(),
+                            title: pr.title.clone(),
+                            author: Some(pr.author.display_name.clone()),
+                            base: Some(pr.destination.branch.name.clone()),
+                        },
+                        updated_at: rfc3339_timestamp(&pr.updated_on),
+                    })
+                    .collect(),
+            )
+        }
+        "bitbucket-data-center" => {
+            let client = bitbucket_data_center_client.as_ref()?;
+            let all_prs = client.get_pull_requests(owner_or_workspace, repo, max).await.ok()?;
+            Some(
+                all_prs
+                    .into_iter()
+                    // Only include open PRs
+                    .filter(|pr| pr.state == "OPEN")
+                    .map(|pr| {
+                        let status = if pr.draft.unwrap_or(false) { "DRAFT" } else { "OPEN" };
+                        RemotePullRequest {
+                            branch: pr.from_ref.display_id.clone(),
+                            pr_info: PullRequestInfo {
+                                url: extract_bitbucket_data_center_url(&pr),
+                                status: status.to_string(),
+                                title: pr.title.clone(),
+                                author: Some(pr.author.user.display_name.clone()),
+                                base: Some(pr.to_ref.display_id.clone()),
+                            },
+                            // Data Center reports milliseconds
+                            updated_at: Some((pr.updated_date / 1000) as i64),
+                        }
+                    })
+                    .collect(),
+            )
         }
+        "gitea" => {
+            let client = gitea_client.as_ref()?;
+            let all_prs = client.get_pull_requests(owner_or_workspace, repo, max).await.ok()?;
+            Some(
+                all_prs
+                    .iter()
+                    .map(|pr| RemotePullRequest {
+                        branch: pr.head.ref_name.clone(),
+                        pr_info: gitea_pull_request_info(pr),
+                        updated_at: pr.updated_at.as_deref().and_then(rfc3339_timestamp),
+                    })
+                    .collect(),
+            )
+        }
+        _ => None,
     }
-    println!(); // Empty line between worktrees
 }
 
-fn display_remote_pr(pr: &RemotePullRequest) {
-    // Display branch name in cyan
-    println!("{}", pr.branch.cyan());
+fn print_json(worktrees: Vec<WorktreeDisplay>, remote_prs: Vec<RemotePullRequest>) -> Result<()> {
+    let entries: Vec<ListEntryJson> = worktrees
+        .into_iter()
+        .map(|wt| ListEntryJson {
+            branch: wt.branch,
+            path: Some(wt.path),
+            head: Some(wt.head),
+            bare: wt.bare,
+            current: wt.current,
+            stale_days: wt.stale_days,
+            commit_subject: wt.last_commit.map(|(_, subject)| subject),
+            draft: is_draft(wt.pr_info.as_ref()),
+            pull_request: wt.pr_info,
+        })
+        .chain(remote_prs.into_iter().map(|pr| ListEntryJson {
+            branch: pr.branch,
+            path: None,
+            head: None,
+            bare: false,
+            current: false,
+            stale_days: None,
+            commit_subject: None,
+            draft: is_draft(Some(&pr.pr_info)),
+            pull_request: Some(pr.pr_info),
+        }))
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&entries)?);
+    Ok(())
+}
+
+/// PR URLs for `--urls-only`: local worktrees' first, then remote-only ones
+fn pr_urls<'a>(worktrees: &'a [WorktreeDisplay], remote_prs: &'a [RemotePullRequest]) -> Vec<&'a str> {
+    worktrees
+        .iter()
+        .filter_map(|wt| wt.pr_info.as_ref())
+        .chain(remote_prs.iter().map(|pr| &pr.pr_info))
+        .map(|pr_info| pr_info.url.as_str())
+        .filter(|url| !url.is_empty())
+        .collect()
+}
+
+/// Fill in a `--format` template for one entry
+///
+/// Placeholders are `{branch}`, `{path}`, `{head}`, `{pr_url}`, `{pr_status}` and `{pr_title}`;
+/// missing values render as empty strings and unknown placeholders are kept as written.
+/// `\t` and `\n` stand for a tab and a newline, since shells pass them through literally.
+fn render_template(
+    template: &str,
+    branch: &str,
+    path: Option<&Path>,
+    head: Option<&str>,
+    pr_info: Option<&PullRequestInfo>,
+) -> String {
+    let path = path.map(|path| path.display().to_string());
+    let value = |name: &str| -> Option<String> {
+        Some(match name {
+            "branch" => branch.to_string(),
+            "path" => path.clone().unwrap_or_default(),
+            "head" => head.unwrap_or_default().to_string(),
+            "pr_url" => pr_info.map(|pr| pr.url.clone()).unwrap_or_default(),
+            "pr_status" => pr_info.map(|pr| pr.status.clone()).unwrap_or_default(),
+            "pr_title" => pr_info.map(|pr| pr.title.clone()).unwrap_or_default(),
+            _ => return None,
+        })
+    };
+
+    // Single pass, so a value that happens to contain `{branch}` is not expanded again
+    let mut line = String::new();
+    let mut rest = template;
+    while let Some(index) = rest.find(['{', '\\']) {
+        line.push_str(&rest[..index]);
+        rest = &rest[index..];
+        if let Some(after) = rest.strip_prefix("\\t") {
+            line.push('\t');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("\\n") {
+            line.push('\n');
+            rest = after;
+        } else if let Some((name, after)) = rest
+            .strip_prefix('{')
+            .and_then(|inner| inner.split_once('}'))
+            .filter(|(name, _)| !name.contains('{'))
+        {
+            match value(name) {
+                Some(value) => line.push_str(&value),
+                None => line.push_str(&rest[..name.len() + 2]),
+            }
+            rest = after;
+        } else {
+            line.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+    line.push_str(rest);
+    line
+}
+
+fn display_worktree(worktree: &WorktreeDisplay, with_authors: bool, dim_drafts: bool, show_commit: bool) {
+    // Display branch name in cyan (bold with a green arrow for the current worktree,
+    // dimmed for a draft PR with --hide-drafts), followed by any detached/locked markers
+    let branch = if dim_drafts && is_draft(worktree.pr_info.as_ref()) {
+        worktree.branch.dimmed()
+    } else {
+        worktree.branch.cyan()
+    };
+    let mut header = if worktree.current {
+        format!("{} {}", "➤".green(), branch.bold())
+    } else {
+        branch.to_string()
+    };
+    if worktree.detached {
+        header.push_str(&format!(" {}", "(detached)".dimmed()));
+    }
+    if let Some(ref reason) = worktree.locked {
+        header.push_str(&lock_marker(reason));
+    }
+    if let Some(days) = worktree.stale_days {
+        let unit = if days == 1 { "day" } else { "days" };
+        header.push_str(&format!(
+            " {}",
+            format!("⏳ stale ({} {})", days, unit).truecolor(255, 165, 0)
+        ));
+    }
+    println!("{}", header);
 
-    // Display URL with status
-    let status_colored = match pr.pr_info.status.as_str() {
+    // The checked-out commit, prefixed with its short SHA so it reads apart from a PR title
+    if show_commit && !worktree.bare {
+        match &worktree.last_commit {
+            Some((sha, subject)) => println!("  {} {}", sha.yellow().dimmed(), subject.dimmed()),
+            None => println!("  {}", "(no commits)".dimmed()),
+        }
+    }
+
+    // Display PR info if available
+    if let Some(ref pr_info) = worktree.pr_info {
+        display_pr_info(pr_info, with_authors);
+    }
+    println!(); // Empty line between worktrees
+}
+
+/// Print a PR's URL with its status (and author with `--with-authors`), then its title
+fn display_pr_info(pr_info: &PullRequestInfo, with_authors: bool) {
+    let status_colored = match pr_info.status.as_str() {
         "OPEN" => "open".green(),
         "CLOSED" => "closed".red(),
         "MERGED" => "merged".green(),
         "DRAFT" => "draft".yellow(),
-        _ => pr.pr_info.status.normal(),
+        _ => pr_info.status.normal(),
+    };
+    let author = match &pr_info.author {
+        Some(author) if with_authors => format!(" {}", format!("by {}", author).dimmed()),
+        _ => String::new(),
     };
-    println!("  {} ({})", pr.pr_info.url.blue().underline(), status_colored);
+    println!("  {} ({}){}", pr_info.url.blue().underline(), status_colored, author);
+
+    // Display title if not empty
+    if !pr_info.title.is_empty() {
+        println!("  {}", pr_info.title.dimmed());
+    }
+}
 
-    // Display title
-    if !pr.pr_info.title.is_empty() {
-        println!("  {}", pr.pr_info.title.dimmed());
+fn lock_marker(reason: &str) -> String {
+    if reason.is_empty() {
+        " 🔒".to_string()
+    } else {
+        format!(" 🔒 {}", reason.yellow())
     }
+}
+
+fn display_remote_pr(pr: &RemotePullRequest, with_authors: bool) {
+    // Display branch name in cyan
+    println!("{}", pr.branch.cyan());
+    display_pr_info(&pr.pr_info, with_authors);
     println!(); // Empty line between PRs
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn worktree(branch: &str, status: Option<&str>) -> WorktreeDisplay {
+        WorktreeDisplay {
+            branch: branch.to_string(),
+            path: PathBuf::from(format!("/worktrees/{}", branch)),
+            head: String::new(),
+            bare: false,
+            detached: false,
+            locked: None,
+            current: false,
+            stale_days: None,
+            last_commit: None,
+            pr_info: status.map(|status| PullRequestInfo {
+                url: String::new(),
+                status: status.to_string(),
+                title: String::new(),
+                author: None,
+                base: None,
+            }),
+        }
+    }
+
+    fn branches(worktrees: &[WorktreeDisplay]) -> Vec<&str> {
+        worktrees.iter().map(|wt| wt.branch.as_str()).collect()
+    }
+
+    #[test]
+    fn test_offline_from_env() {
+        assert!(offline_from_env(Some("1")));
+        assert!(offline_from_env(Some("true")));
+        assert!(!offline_from_env(None));
+        assert!(!offline_from_env(Some("")));
+        assert!(!offline_from_env(Some("0")));
+        assert!(!offline_from_env(Some("False")));
+    }
+
+    #[test]
+    fn test_sort_by_status() {
+        let mut worktrees = vec![
+            worktree("none", None),
+            worktree("closed", Some("CLOSED")),
+            worktree("draft", Some("DRAFT")),
+            worktree("merged", Some("MERGED")),
+            worktree("open", Some("OPEN")),
+        ];
+        sort_worktrees(&mut worktrees, ListSort::Status);
+        assert_eq!(branches(&worktrees), ["open", "draft", "merged", "closed", "none"]);
+
+        sort_worktrees(&mut worktrees, ListSort::Name);
+        assert_eq!(branches(&worktrees), ["closed", "draft", "merged", "none", "open"]);
+    }
+
+    #[test]
+    fn test_mark_current_worktree() {
+        let mut worktrees = vec![
+            worktree("main", None),
+            worktree("feature", None),
+            worktree("main/nested", None),
+        ];
+        mark_current_worktree(&mut worktrees, Path::new("/worktrees/feature/src"));
+        assert_eq!(
+            worktrees.iter().map(|wt| wt.current).collect::<Vec<_>>(),
+            [false, true, false]
+        );
+
+        // The innermost worktree wins when one lives inside another
+        let mut worktrees = vec![worktree("main", None), worktree("main/nested", None)];
+        mark_current_worktree(&mut worktrees, Path::new("/worktrees/main/nested"));
+        assert_eq!(worktrees.iter().map(|wt| wt.current).collect::<Vec<_>>(), [false, true]);
+
+        // A sibling whose name shares a prefix isn't a match
+        let mut worktrees = vec![worktree("main", None)];
+        mark_current_worktree(&mut worktrees, Path::new("/worktrees/main-old"));
+        assert!(!worktrees[0].current);
+
+        // Outside every worktree (e.g. a bare project root) nothing is marked
+        let mut worktrees = vec![worktree("main", None)];
+        worktrees.push(WorktreeDisplay {
+            bare: true,
+            ..worktree("(bare)", None)
+        });
+        worktrees[1].path = PathBuf::from("/worktrees");
+        mark_current_worktree(&mut worktrees, Path::new("/worktrees"));
+        assert!(worktrees.iter().all(|wt| !wt.current));
+    }
+
+    #[test]
+    fn test_stale_days() {
+        let now = 100 * SECONDS_PER_DAY as i64;
+        assert_eq!(stale_days(now - 45 * SECONDS_PER_DAY as i64, now, 30), Some(45));
+        assert_eq!(stale_days(now - 30 * SECONDS_PER_DAY as i64, now, 30), Some(30));
+        assert_eq!(stale_days(now - 29 * SECONDS_PER_DAY as i64, now, 30), None);
+        // Commits dated in the future (clock skew) count as fresh
+        assert_eq!(stale_days(now + 60, now, 1), None);
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let draft = worktree("draft", Some("DRAFT"));
+        let open = worktree("open", Some("OPEN"));
+        let none = worktree("none", None);
+
+        assert!(filter_matches(ListFilter::HasPr, open.pr_info.as_ref()));
+        assert!(!filter_matches(ListFilter::HasPr, none.pr_info.as_ref()));
+        assert!(filter_matches(ListFilter::NoPr, none.pr_info.as_ref()));
+        assert!(filter_matches(ListFilter::Draft, draft.pr_info.as_ref()));
+        assert!(!filter_matches(ListFilter::Draft, open.pr_info.as_ref()));
+    }
+
+    #[test]
+    fn test_truncate_remote_prs() {
+        let remote_prs = |count: usize| -> Vec<RemotePullRequest> {
+            (0..count)
+                .map(|i| RemotePullRequest {
+                    branch: format!("feature/{}", i),
+                    pr_info: worktree("", Some("OPEN")).pr_info.unwrap(),
+                    updated_at: None,
+                })
+                .collect()
+        };
+
+        let mut prs = remote_prs(67);
+        assert_eq!(truncate_remote_prs(&mut prs, 30), 37);
+        assert_eq!(prs.len(), 30);
+        assert_eq!(prs[29].branch, "feature/29");
+
+        let mut prs = remote_prs(30);
+        assert_eq!(truncate_remote_prs(&mut prs, 30), 0);
+        assert_eq!(prs.len(), 30);
+
+        // 0 means unlimited
+        let mut prs = remote_prs(67);
+        assert_eq!(truncate_remote_prs(&mut prs, 0), 0);
+        assert_eq!(prs.len(), 67);
+    }
+
+    #[test]
+    fn test_open_pr_fetch_limit() {
+        assert_eq!(open_pr_fetch_limit(30, false, 4), Some(35));
+        assert_eq!(open_pr_fetch_limit(0, false, 4), None);
+        assert_eq!(open_pr_fetch_limit(30, true, 4), None);
+    }
+
+    #[test]
+    fn test_pr_urls() {
+        let with_url = |branch: &str, url: &str| {
+            let mut wt = worktree(branch, Some("OPEN"));
+            wt.pr_info.as_mut().unwrap().url = url.to_string();
+            wt
+        };
+        let worktrees = vec![
+            with_url("feature/a", "https://example.com/pr/1"),
+            worktree("no-pr", None),
+            with_url("feature/b", ""),
+        ];
+        let remote_prs = vec![RemotePullRequest {
+            branch: "feature/c".to_string(),
+            pr_info: with_url("feature/c", "https://example.com/pr/3").pr_info.unwrap(),
+            updated_at: None,
+        }];
+
+        assert_eq!(
+            pr_urls(&worktrees, &remote_prs),
+            ["https://example.com/pr/1", "https://example.com/pr/3"]
+        );
+        assert!(pr_urls(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = 1_700_000_000;
+        let day = SECONDS_PER_DAY as i64;
+        assert_eq!(parse_since("7d", now).unwrap(), now - 7 * day);
+        assert_eq!(parse_since("24h", now).unwrap(), now - day);
+        assert_eq!(parse_since("2w", now).unwrap(), now - 14 * day);
+        assert_eq!(parse_since("0d", now).unwrap(), now);
+        // 2024-03-01T00:00:00Z
+        assert_eq!(parse_since("2024-03-01", now).unwrap(), 1_709_251_200);
+
+        for invalid in [
+            "",
+            "d",
+            "7",
+            "7x",
+            "-3d",
+            "7日",
+            "seven days",
+            "2024-13-01",
+            "99999999999999999999d",
+        ] {
+            let err = parse_since(invalid, now).unwrap_err();
+            assert!(err.to_string().contains("Invalid --since"), "{}: {}", invalid, err);
+        }
+    }
+
+    #[test]
+    fn test_updated_since() {
+        assert_eq!(rfc3339_timestamp("2024-03-01T00:00:00Z"), Some(1_709_251_200));
+        assert_eq!(
+            rfc3339_timestamp("2024-03-01T01:00:00.123456+01:00"),
+            Some(1_709_251_200)
+        );
+        assert_eq!(rfc3339_timestamp("yesterday"), None);
+
+        let pr = |updated_at: Option<i64>| RemotePullRequest {
+            branch: "feature/x".to_string(),
+            pr_info: worktree("feature/x", Some("OPEN")).pr_info.unwrap(),
+            updated_at,
+        };
+        assert!(updated_since(&pr(Some(200)), 100));
+        assert!(updated_since(&pr(Some(100)), 100));
+        assert!(!updated_since(&pr(Some(99)), 100));
+        assert!(!updated_since(&pr(None), 100));
+    }
+
+    #[test]
+    fn test_render_template() {
+        let pr_info = PullRequestInfo {
+            url: "https://example.com/pr/7".to_string(),
+            status: "OPEN".to_string(),
+            title: "Add {branch} docs".to_string(),
+            author: None,
+            base: None,
+        };
+        let path = PathBuf::from("/worktrees/feature-a");
+
+        assert_eq!(
+            render_template(
+                "{branch}\\t{pr_status}\\t{pr_url}",
+                "feature/a",
+                Some(&path),
+                Some("abc123"),
+                Some(&pr_info)
+            ),
+            "feature/a\tOPEN\thttps://example.com/pr/7"
+        );
+        assert_eq!(
+            render_template(
+                "{path} {head}: {pr_title}",
+                "feature/a",
+                Some(&path),
+                Some("abc123"),
+                Some(&pr_info)
+            ),
+            "/worktrees/feature-a abc123: Add {branch} docs"
+        );
+        // No PR and no worktree: missing values are empty
+        assert_eq!(
+            render_template(
+                "[{branch}|{path}|{head}|{pr_url}|{pr_status}|{pr_title}]",
+                "main",
+                None,
+                None,
+                None
+            ),
+            "[main|||||]"
+        );
+        // Unknown placeholders, stray braces and other escapes are printed as written
+        assert_eq!(
+            render_template("{nope} { {branch} \\x {", "main", None, None, None),
+            "{nope} { main \\x {"
+        );
+    }
+
+    #[test]
+    fn test_group_by_base() {
+        let with_base = |branch: &str, base: &str| {
+            let mut wt = worktree(branch, Some("OPEN"));
+            wt.pr_info.as_mut().unwrap().base = Some(base.to_string());
+            wt
+        };
+        let worktrees = vec![
+            worktree("main", None),
+            with_base("feature/b", "main"),
+            with_base("feature/stacked", "feature/a"),
+            with_base("feature/a", "main"),
+            // A PR whose provider didn't report a base
+            worktree("feature/unknown", Some("OPEN")),
+        ];
+
+        let groups: Vec<(Option<&str>, Vec<&str>)> = group_by_base(&worktrees)
+            .into_iter()
+            .map(|(base, group)| (base, group.iter().map(|wt| wt.branch.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                (Some("feature/a"), vec!["feature/stacked"]),
+                (Some("main"), vec!["feature/b", "feature/a"]),
+                (None, vec!["main", "feature/unknown"]),
+            ]
+        );
+        assert!(group_by_base(&[]).is_empty());
+    }
+}
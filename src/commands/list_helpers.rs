@@ -1,15 +1,25 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
     bitbucket_api, bitbucket_data_center_api,
     error::{Error, Result},
-    github,
+    gitea, github,
 };
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PullRequestInfo {
     pub url: String,
     pub status: String,
     pub title: String,
+    /// Display name (or login on GitHub) of whoever opened the PR
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Branch the PR merges into (`--group-by base`)
+    #[serde(default)]
+    pub base: Option<String>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn fetch_pr_for_branch(
     platform: &str,
     owner_or_workspace: &str,
@@ -18,25 +28,27 @@ pub async fn fetch_pr_for_branch(
     github_client: &Option<github::GitHubClient>,
     bitbucket_client: &Option<bitbucket_api::BitbucketClient>,
     bitbucket_data_center_client: &Option<bitbucket_data_center_api::BitbucketDataCenterClient>,
+    gitea_client: &Option<gitea::GiteaClient>,
 ) -> Result<Option<PullRequestInfo>> {
     match platform {
-        "github" => fetch_github_pr(github_client, owner_or_workspace, repo, branch),
+        "github" => fetch_github_pr(github_client, owner_or_workspace, repo, branch).await,
         "bitbucket-cloud" => fetch_bitbucket_cloud_pr(bitbucket_client, owner_or_workspace, repo, branch).await,
         "bitbucket-data-center" => {
             fetch_bitbucket_data_center_pr(bitbucket_data_center_client, owner_or_workspace, repo, branch).await
         }
+        "gitea" => fetch_gitea_pr(gitea_client, owner_or_workspace, repo, branch).await,
         _ => Ok(None),
     }
 }
 
-fn fetch_github_pr(
+async fn fetch_github_pr(
     client: &Option<github::GitHubClient>,
     owner: &str,
     repo: &str,
     branch: &str,
 ) -> Result<Option<PullRequestInfo>> {
     if let Some(ref client) = client {
-        match client.get_pull_requests(owner, repo, branch) {
+        match client.get_pull_requests(owner, repo, branch).await {
             Ok(prs) => {
                 if let Some(pr) = prs.first() {
                     let status = if pr.draft {
@@ -54,6 +66,8 @@ fn fetch_github_pr(
                         url: pr.html_url.clone(),
                         status,
                         title: pr.title.clone(),
+                        author: pr.author.clone(),
+                        base: pr.base.clone(),
                     }))
                 } else {
                     Ok(None)
@@ -73,7 +87,7 @@ async fn fetch_bitbucket_cloud_pr(
     branch: &str,
 ) -> Result<Option<PullRequestInfo>> {
     if let Some(ref client) = client {
-        match client.get_pull_requests(workspace, repo).await {
+        match client.get_pull_requests(workspace, repo, None).await {
             Ok(prs) => {
                 if let Some(pr) = prs.iter().find(|pr| pr.source.branch.name == branch) {
                     let url = extract_bitbucket_cloud_url(pr);
@@ -81,6 +95,8 @@ async fn fetch_bitbucket_cloud_pr(
                         url,
                         status: pr.state.to_uppercase(),
                         title: pr.title.clone(),
+                        author: Some(pr.author.display_name.clone()),
+                        base: Some(pr.destination.branch.name.clone()),
                     }))
                 } else {
                     Ok(None)
@@ -100,7 +116,7 @@ async fn fetch_bitbucket_data_center_pr(
     branch: &str,
 ) -> Result<Option<PullRequestInfo>> {
     if let Some(ref client) = client {
-        match client.get_pull_requests(project, repo).await {
+        match client.get_pull_requests(project, repo, None).await {
             Ok(prs) => {
                 if let Some(pr) = prs.iter().find(|pr| pr.from_ref.display_id == branch) {
                     let url = extract_bitbucket_data_center_url(pr);
@@ -108,6 +124,8 @@ async fn fetch_bitbucket_data_center_pr(
                         url,
                         status: pr.state.to_uppercase(),
                         title: pr.title.clone(),
+                        author: Some(pr.author.user.display_name.clone()),
+                        base: Some(pr.to_ref.display_id.clone()),
                     }))
                 } else {
                     Ok(None)
@@ -120,6 +138,37 @@ async fn fetch_bitbucket_data_center_pr(
     }
 }
 
+async fn fetch_gitea_pr(
+    client: &Option<gitea::GiteaClient>,
+    owner: &str,
+    repo: &str,
+    branch: &str,
+) -> Result<Option<PullRequestInfo>> {
+    if let Some(ref client) = client {
+        match client.get_pull_requests(owner, repo, None).await {
+            Ok(prs) => Ok(prs
+                .iter()
+                .find(|pr| pr.head.ref_name == branch)
+                .map(gitea_pull_request_info)),
+            Err(_) => Err(Error::provider("Failed to fetch Gitea PRs")),
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+/// Display info for an open Gitea pull request
+pub fn gitea_pull_request_info(pr: &gitea::GiteaPullRequest) -> PullRequestInfo {
+    let status = if pr.is_draft() { "DRAFT" } else { "OPEN" };
+    PullRequestInfo {
+        url: pr.html_url.clone(),
+        status: status.to_string(),
+        title: pr.title.clone(),
+        author: pr.author(),
+        base: pr.base.as_ref().map(|base| base.ref_name.clone()),
+    }
+}
+
 pub fn extract_bitbucket_cloud_url(pr: &bitbucket_api::BitbucketPullRequest) -> String {
     if let Some(html_link) = pr.links.get("html") {
         if let Some(href) = html_link.get("href") {
@@ -0,0 +1,52 @@
+use colored::Colorize;
+
+use super::remove::find_target_worktree;
+use crate::{
+    core::project::{clean_branch_name, find_git_directory},
+    error::{Error, Result},
+    git, status,
+};
+
+/// Lock a worktree (the current one by default) against pruning and removal
+pub fn run_lock(branch_name: Option<&str>, reason: Option<&str>) -> Result<()> {
+    let git_dir = find_git_directory()?;
+    let worktrees = git::list_worktrees(Some(&git_dir))?;
+    let target = find_target_worktree(&worktrees, branch_name)?;
+
+    if target.bare {
+        return Err(Error::msg("Cannot lock the main (bare) repository."));
+    }
+    if target.locked.is_some() {
+        return Err(Error::msg(format!(
+            "Worktree '{}' is already locked",
+            display_name(target)
+        )));
+    }
+
+    git::lock_worktree(&git_dir, &target.path, reason)?;
+    status!("{}", format!("🔒 Locked worktree: {}", display_name(target)).green());
+    Ok(())
+}
+
+/// Unlock a worktree (the current one by default)
+pub fn run_unlock(branch_name: Option<&str>) -> Result<()> {
+    let git_dir = find_git_directory()?;
+    let worktrees = git::list_worktrees(Some(&git_dir))?;
+    let target = find_target_worktree(&worktrees, branch_name)?;
+
+    if target.locked.is_none() {
+        return Err(Error::msg(format!("Worktree '{}' is not locked", display_name(target))));
+    }
+
+    git::unlock_worktree(&git_dir, &target.path)?;
+    status!("{}", format!("✓ Unlocked worktree: {}", display_name(target)).green());
+    Ok(())
+}
+
+fn display_name(worktree: &git::Worktree) -> String {
+    worktree
+        .branch
+        .as_deref()
+        .map(|b| clean_branch_name(b).to_string())
+        .unwrap_or_else(|| worktree.path.display().to_string())
+}
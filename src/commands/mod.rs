@@ -1,7 +1,17 @@
 pub mod add;
 pub mod auth;
 pub mod config;
+pub mod doctor;
+pub mod fetch;
 pub mod init;
 pub mod list;
 pub mod list_helpers;
+pub mod lock;
+pub mod move_worktree;
+pub mod pr;
+pub mod projects;
+pub mod prune;
 pub mod remove;
+pub mod rename;
+pub mod status;
+pub mod sync;
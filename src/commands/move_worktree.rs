@@ -0,0 +1,93 @@
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+
+use super::remove::find_worktree_by_branch;
+use crate::{
+    config::GitWorktreeConfig,
+    core::project::find_git_directory,
+    error::{Error, Result},
+    git,
+};
+
+/// Move a worktree to another directory with `git worktree move`
+///
+/// Like `mv`, an existing directory at `new_path` receives the worktree inside it.
+/// The main repository and locked worktrees are refused up front.
+pub fn run(branch_name: &str, new_path: &Path) -> Result<()> {
+    let git_dir = find_git_directory()?;
+    let worktrees = git::list_worktrees(Some(&git_dir))?;
+    let target = find_worktree_by_branch(&worktrees, branch_name)?;
+
+    if target.bare || target.path.join(".git").is_dir() {
+        return Err(Error::msg("Cannot move the main repository worktree"));
+    }
+    if let Some(reason) = &target.locked {
+        let reason = if reason.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", reason)
+        };
+        return Err(Error::git(format!(
+            "Worktree '{}' is locked{}. Run 'gwt unlock {}' first",
+            branch_name, reason, branch_name
+        )));
+    }
+
+    let current_dir = std::env::current_dir()?;
+    let mut destination = current_dir.join(new_path);
+    if destination.is_dir() {
+        if let Some(dir_name) = target.path.file_name() {
+            destination = destination.join(dir_name);
+        }
+    }
+    if destination.exists() {
+        return Err(Error::msg(format!(
+            "Target path '{}' already exists",
+            destination.display()
+        )));
+    }
+    if destination.starts_with(&target.path) {
+        return Err(Error::msg("Cannot move a worktree into itself"));
+    }
+
+    // Read the config before the move; it may be found through the worktree being moved
+    let config = GitWorktreeConfig::find_config()?;
+
+    println!("{}", "Moving worktree:".cyan().bold());
+    println!(
+        "  {}: {} -> {}",
+        "Path".dimmed(),
+        target.path.display(),
+        destination.display().to_string().green()
+    );
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    git::move_worktree(&git_dir, &target.path, &destination)?;
+    println!("{}", format!("✓ Worktree moved to: {}", destination.display()).green());
+
+    // Keep projectPath pointing at the project if it recorded this worktree
+    if let Some((config_path, config)) = config {
+        if config.project_path.as_deref() == Some(target.path.as_path()) {
+            match GitWorktreeConfig::load_for_update(&config_path) {
+                Ok(mut config) => {
+                    config.project_path = Some(destination.clone());
+                    config.save(&config_path)?;
+                    println!("{}", "✓ Updated projectPath in the config".green());
+                }
+                Err(e) => println!("{}", format!("⚠️  projectPath not updated: {}", e).yellow()),
+            }
+        }
+    }
+
+    if current_dir.starts_with(&target.path) {
+        println!(
+            "{}",
+            format!("✓ Please navigate to the new location: {}", destination.display()).green()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use colored::Colorize;
+use std::io::{self, Write};
+
+use crate::{
+    cli::PrAction,
+    config::GitWorktreeConfig,
+    error::{Error, Result},
+    git, github,
+};
+
+#[tokio::main]
+pub async fn run(action: PrAction) -> Result<()> {
+    match action {
+        PrAction::Create { title, base, draft } => create(title, base, draft).await,
+    }
+}
+
+/// Open a pull request for the current worktree's branch
+///
+/// Each provider gets its own backend below; only GitHub is implemented so far.
+async fn create(title: Option<String>, base: Option<String>, draft: bool) -> Result<()> {
+    let (_config_path, config) = GitWorktreeConfig::find_config()?
+        .ok_or_else(|| Error::config("Config not found. Run 'gwt init' from your project directory to create one."))?;
+
+    let current_dir = std::env::current_dir()?;
+    let branch = git::get_current_branch(&current_dir)
+        .map_err(|_| Error::git("Not on a branch. Run 'gwt pr create' from inside a worktree."))?;
+    let base = base.unwrap_or_else(|| config.main_branch.clone());
+    if branch == base {
+        return Err(Error::msg(format!(
+            "Branch '{}' is the base branch; run this from a feature worktree",
+            branch
+        )));
+    }
+
+    let title = match title {
+        Some(title) => title,
+        None => prompt_title(&branch)?,
+    };
+
+    let url = match config.source_control.as_str() {
+        "github" => create_github(&config, &branch, &base, &title, draft).await?,
+        other => {
+            return Err(Error::provider(format!(
+                "Creating pull requests is not supported for '{}' yet",
+                other
+            )))
+        }
+    };
+
+    println!("{}", "✓ Pull request created".green());
+    println!("  {}", url.blue().underline());
+    Ok(())
+}
+
+async fn create_github(
+    config: &GitWorktreeConfig,
+    branch: &str,
+    base: &str,
+    title: &str,
+    draft: bool,
+) -> Result<String> {
+    let (owner, repo) = github::GitHubClient::parse_github_url(&config.repository_url)
+        .ok_or_else(|| Error::provider(format!("Not a GitHub repository URL: {}", config.repository_url)))?;
+    github::GitHubClient::new()
+        .create_pull_request(&owner, &repo, branch, base, title, draft)
+        .await
+}
+
+fn prompt_title(branch: &str) -> Result<String> {
+    print!("{}", format!("Pull request title for '{}': ", branch).cyan());
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let title = input.trim();
+    if title.is_empty() {
+        return Err(Error::msg("A pull request title is required"));
+    }
+    Ok(title.to_string())
+}
@@ -0,0 +1,152 @@
+use colored::Colorize;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::GitWorktreeConfig;
+use crate::error::Result;
+
+/// A config file from the global projects directory
+struct ProjectEntry {
+    path: PathBuf,
+    config: Result<GitWorktreeConfig>,
+}
+
+impl ProjectEntry {
+    /// Whether the config records a `projectPath` that is gone from disk
+    fn is_missing(&self) -> bool {
+        match &self.config {
+            Ok(config) => config.project_path.as_ref().is_some_and(|path| !path.exists()),
+            Err(_) => false,
+        }
+    }
+}
+
+/// List the projects initialized with a global config, optionally pruning the configs of
+/// projects whose directory no longer exists
+///
+/// Pruning asks for confirmation unless `yes` is set, and renames each config to
+/// `<name>.bak` rather than deleting it: a path on an unmounted drive also looks missing.
+/// Local configs (`gwt init --local`) live next to their repositories and aren't listed.
+pub fn run(prune: bool, yes: bool) -> Result<()> {
+    let projects_dir = GitWorktreeConfig::projects_config_dir()?;
+    let entries = project_entries()?;
+
+    if entries.is_empty() {
+        println!(
+            "{}",
+            format!("No projects found in {}", projects_dir.display()).yellow()
+        );
+        return Ok(());
+    }
+
+    println!("{}", format!("Projects ({}):", projects_dir.display()).bold());
+    println!();
+
+    for entry in &entries {
+        let name = entry
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let config = match &entry.config {
+            Ok(config) => config,
+            Err(e) => {
+                println!("{} {}", "✗".red(), name.cyan());
+                println!("  {}", format!("invalid config: {}", e).red());
+                continue;
+            }
+        };
+
+        println!("{}", config.repository_url.cyan());
+        println!("  {} {}", "main branch:".dimmed(), config.main_branch);
+        if let Some(project_path) = &config.project_path {
+            let state = if project_path.exists() {
+                "✓ present".green()
+            } else {
+                "✗ missing".red()
+            };
+            println!("  {} {} {}", "path:".dimmed(), project_path.display(), state);
+        }
+        println!("  {} {}", "config:".dimmed(), name.dimmed());
+    }
+
+    let missing: Vec<&ProjectEntry> = entries.iter().filter(|entry| entry.is_missing()).collect();
+    if prune {
+        println!();
+        if missing.is_empty() {
+            println!("{}", "✓ Nothing to prune".green());
+        } else if yes || confirm_prune(missing.len())? {
+            for entry in &missing {
+                let backup = backup_path(&entry.path);
+                fs::rename(&entry.path, &backup)?;
+                println!("{}", format!("✓ Moved config to {}", backup.display()).green());
+            }
+            println!("{}", format!("✓ Pruned {} project config(s)", missing.len()).green());
+        } else {
+            println!("{}", "Prune cancelled".yellow());
+        }
+    } else if !missing.is_empty() {
+        println!();
+        println!(
+            "{}",
+            format!(
+                "{} project(s) no longer exist on disk; run 'gwt projects --prune' to remove their configs",
+                missing.len()
+            )
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Ask before pruning `count` configs
+fn confirm_prune(count: usize) -> Result<bool> {
+    print!(
+        "{}",
+        format!(
+            "Prune {} config(s) of projects missing on disk (check that their drive is mounted)? (y/N): ",
+            count
+        )
+        .cyan()
+    );
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let confirmation = input.trim().to_lowercase();
+    Ok(confirmation == "y" || confirmation == "yes")
+}
+
+/// `<config>.bak` next to the config, which `gwt projects` no longer reads
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// Every `*.jsonc` in the projects config directory, sorted by file name
+///
+/// Files that fail to parse are kept (with their error) so they can be reported.
+fn project_entries() -> Result<Vec<ProjectEntry>> {
+    let projects_dir = GitWorktreeConfig::projects_config_dir()?;
+    if !projects_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&projects_dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "jsonc"))
+        .collect();
+    paths.sort();
+
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let config = GitWorktreeConfig::load(&path);
+            ProjectEntry { path, config }
+        })
+        .collect())
+}
@@ -0,0 +1,99 @@
+use colored::Colorize;
+use std::io::{self, Write};
+
+use super::remove::{delete_branch, UnmergedBranch};
+use crate::{
+    core::project::{clean_branch_name, find_project_root, find_valid_git_directory},
+    core::utils::{matches_branch_patterns, protected_branches},
+    error::Result,
+    git,
+};
+
+/// Prune stale worktrees whose directories were deleted outside of gwt
+///
+/// With `yes`, branches left without a worktree are deleted without prompting. Unmerged
+/// ones are only force-deleted with `force` (asked first unless `yes`), otherwise kept.
+pub fn run(dry_run: bool, yes: bool, force: bool) -> Result<()> {
+    let project_root = find_project_root()?;
+    let git_dir = find_valid_git_directory(&project_root)?;
+
+    let worktrees = git::list_worktrees(Some(&git_dir))?;
+    let stale: Vec<&git::Worktree> = worktrees.iter().filter(|wt| wt.prunable.is_some()).collect();
+
+    if stale.is_empty() {
+        println!("{}", "✓ No stale worktrees found".green());
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("{}", "Stale worktrees that would be pruned:".cyan().bold());
+    } else {
+        println!("{}", "Pruning stale worktrees:".cyan().bold());
+    }
+    for wt in &stale {
+        let branch = wt.branch.as_deref().map(clean_branch_name).unwrap_or("(detached)");
+        println!(
+            "  {} {}",
+            wt.path.display().to_string().yellow(),
+            format!("({})", branch).dimmed()
+        );
+    }
+
+    let pruned = git::prune_worktrees_verbose(&git_dir, dry_run)?;
+
+    if dry_run {
+        println!(
+            "\n{}",
+            format!("Dry run: {} worktree reference(s) would be pruned", pruned.len()).dimmed()
+        );
+        return Ok(());
+    }
+
+    println!("{}", format!("✓ Pruned {} worktree reference(s)", pruned.len()).green());
+
+    // Offer to delete branches that no longer have any worktree
+    let remaining = git::list_worktrees(Some(&git_dir))?;
+    let protected = protected_branches()?;
+    for wt in stale {
+        let Some(branch) = wt.branch.as_deref().map(clean_branch_name) else {
+            continue;
+        };
+        if matches_branch_patterns(branch, &protected) {
+            continue;
+        }
+        let still_checked_out = remaining
+            .iter()
+            .any(|other| other.branch.as_deref().map(clean_branch_name) == Some(branch));
+        if still_checked_out {
+            continue;
+        }
+
+        let confirmed = if yes {
+            true
+        } else {
+            print!(
+                "\n{}",
+                format!("Delete branch '{}' (no remaining worktree)? (y/N): ", branch).cyan()
+            );
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let confirmation = input.trim().to_lowercase();
+            confirmation == "y" || confirmation == "yes"
+        };
+
+        if confirmed {
+            let unmerged = match (force, yes) {
+                (true, true) => UnmergedBranch::ForceDelete,
+                (true, false) => UnmergedBranch::Ask,
+                (false, _) => UnmergedBranch::Keep,
+            };
+            delete_branch(branch, &git_dir, unmerged)?;
+        } else {
+            println!("{}", format!("✓ Branch: {} (preserved)", branch).green());
+        }
+    }
+
+    Ok(())
+}
@@ -1,33 +1,79 @@
 use colored::Colorize;
+use std::fs;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
+use super::add::main_branch_and_remote;
 use crate::{
-    constants,
+    config::GitWorktreeConfig,
     core::project::{
-        clean_branch_name, find_git_directory, find_project_root, find_project_root_from, find_valid_git_directory,
-        is_orphaned_worktree,
+        clean_branch_name, find_project_root_from, find_valid_git_directory, find_worktree, is_orphaned_worktree,
+        Project,
     },
+    core::utils::{matches_branch_patterns, protected_branches},
     error::{Error, Result},
-    git, hooks,
+    git, hooks, message, output, status,
 };
 
-pub fn run(branch_name: Option<&str>, force: bool) -> Result<()> {
+/// What `gwt remove` may do without asking, from `--force` and `-y/--yes`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Confirmation {
+    /// Skip confirmation prompts (`-y/--yes`, implied by `--force`)
+    pub yes: bool,
+    /// Also remove locked worktrees and force-delete unmerged branches (`--force`)
+    pub force: bool,
+}
+
+impl Confirmation {
+    /// What to do with a branch `git branch -d` refuses as unmerged
+    fn unmerged_branch(self) -> UnmergedBranch {
+        if self.force {
+            UnmergedBranch::ForceDelete
+        } else if self.yes {
+            UnmergedBranch::Keep
+        } else {
+            UnmergedBranch::Ask
+        }
+    }
+}
+
+/// What [`delete_branch`] does with a branch `git branch -d` refuses as unmerged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnmergedBranch {
+    /// Ask before force-deleting it
+    Ask,
+    /// Keep it without asking
+    Keep,
+    /// Force-delete it with `git branch -D`
+    ForceDelete,
+}
+
+/// Remove a worktree (the current one by default) and its branch
+///
+/// With `dry_run`, prints what would be removed and which hooks would run, then
+/// stops before any git command or hook runs. `delete_branch` overrides the
+/// `deleteBranchOnRemove` config.
+pub fn run(
+    branch_name: Option<&str>,
+    confirm: Confirmation,
+    dry_run: bool,
+    delete_branch: Option<bool>,
+    prune_dir: bool,
+) -> Result<()> {
+    let delete_branch = resolve_delete_branch(delete_branch)?;
+
+    let project = Project::find()?;
+
     // Check if we're trying to remove an orphaned worktree by directory name
     if let Some(branch) = branch_name {
-        if let Ok(project_root) = find_project_root() {
-            let potential_worktree_path = project_root.join(branch);
-            if is_orphaned_worktree(&potential_worktree_path) {
-                println!("{}", "⚠️  Detected orphaned worktree (stale git reference)".yellow());
-                return remove_orphaned_worktree(&potential_worktree_path, branch, force);
-            }
+        let potential_worktree_path = project.root.join(branch);
+        if is_orphaned_worktree(&potential_worktree_path) {
+            println!("{}", "⚠️  Detected orphaned worktree (stale git reference)".yellow());
+            return remove_orphaned_worktree(&potential_worktree_path, branch, confirm.yes, dry_run);
         }
     }
 
-    // Find a git directory to work with
-    let git_dir = find_git_directory()?;
-
-    // Get the list of worktrees
-    let worktrees = git::list_worktrees(Some(&git_dir))?;
+    let worktrees = project.worktrees()?;
 
     if worktrees.is_empty() {
         println!("{}", "No worktrees found.".yellow());
@@ -46,15 +92,33 @@ pub fn run(branch_name: Option<&str>, force: bool) -> Result<()> {
     if is_orphaned_worktree(&target_worktree.path) {
         let branch_display = get_branch_display(target_worktree);
         println!("{}", "⚠️  Detected orphaned worktree (stale git reference)".yellow());
-        return remove_orphaned_worktree(&target_worktree.path, branch_display, force);
+        return remove_orphaned_worktree(&target_worktree.path, branch_display, confirm.yes, dry_run);
     }
 
     let branch_display = get_branch_display(target_worktree);
 
-    // Show what will be removed
-    println!("{}", "About to remove worktree:".cyan().bold());
-    println!("  {}: {}", "Path".dimmed(), target_worktree.path.display());
-    println!("  {}: {}", "Branch".dimmed(), branch_display.green());
+    // A lock protects the worktree from accidental removal; --force overrides it
+    if let Some(reason) = &target_worktree.locked {
+        if !confirm.force {
+            return Err(Error::msg(format!(
+                "Worktree '{}' is locked{}. Run 'gwt unlock {}' first, or pass --force to remove it anyway.",
+                branch_display,
+                lock_reason_suffix(reason),
+                branch_display
+            )));
+        }
+    }
+
+    if dry_run {
+        return print_dry_run(&worktrees, target_worktree, delete_branch);
+    }
+
+    // Show what will be removed (skipped for --yes --quiet, where nothing is asked)
+    if !(confirm.yes && output::is_quiet()) {
+        println!("{}", "About to remove worktree:".cyan().bold());
+        println!("  {}: {}", "Path".dimmed(), target_worktree.path.display());
+        println!("  {}: {}", "Branch".dimmed(), branch_display.green());
+    }
 
     // Check if we're currently in the worktree being removed
     let current_dir = std::env::current_dir()?;
@@ -67,8 +131,8 @@ pub fn run(branch_name: Option<&str>, force: bool) -> Result<()> {
         );
     }
 
-    // Ask for confirmation unless --force is used
-    if !force {
+    // Ask for confirmation unless --yes or --force is used
+    if !confirm.yes {
         print!("\n{}", "Are you sure you want to remove this worktree? (y/N): ".cyan());
         io::stdout().flush()?;
 
@@ -80,8 +144,374 @@ pub fn run(branch_name: Option<&str>, force: bool) -> Result<()> {
             println!("{}", "Removal cancelled.".yellow());
             return Ok(());
         }
+    } else {
+        status!("\n{}", "Proceeding without confirmation (--yes)".dimmed());
+    }
+
+    let project_root = remove_worktree(
+        &worktrees,
+        target_worktree,
+        confirm,
+        will_remove_current,
+        delete_branch,
+        prune_dir,
+    )?;
+
+    // If we removed the current worktree, show message about moving to project root
+    if will_remove_current {
+        println!(
+            "{}",
+            format!("✓ Please navigate to project root: {}", project_root.display()).green()
+        );
+    }
+
+    Ok(())
+}
+
+/// Remove every worktree whose branch is fully merged into the main branch
+///
+/// Protected branches, the main branch itself, the worktree the command runs in,
+/// and stale or locked worktrees are skipped (see [`merged_removable`]). Everything
+/// is listed up front and confirmed once (unless `confirm.yes`); `dry_run` stops after
+/// the list, before any prompt or git change.
+pub fn run_all_merged(
+    confirm: Confirmation,
+    dry_run: bool,
+    delete_branch: Option<bool>,
+    prune_dir: bool,
+) -> Result<()> {
+    let delete_branch = resolve_delete_branch(delete_branch)?;
+    let project = Project::find()?;
+    let worktrees = project.worktrees()?;
+    let Project {
+        root: project_root,
+        git_dir,
+    } = project;
+
+    let (main_branch, remote) = main_branch_and_remote(&project_root)?;
+    let base = if git::ref_exists(&git_dir, &main_branch) {
+        main_branch.clone()
+    } else {
+        format!("{}/{}", remote, main_branch)
+    };
+    let merged = git::merged_branches(&git_dir, &base)?;
+
+    let current_dir = std::env::current_dir()?;
+    let current = innermost_worktree(&worktrees, &current_dir).map(|wt| wt.path.as_path());
+    let protected = protected_branches()?;
+    let candidates = merged_removable(&worktrees, &main_branch, &merged, &protected, current);
+
+    // Removing the worktree we're standing in is left to an explicit `gwt remove`
+    if let Some(current_wt) = worktrees.iter().find(|wt| Some(wt.path.as_path()) == current) {
+        let merged_too = !merged_removable(
+            std::slice::from_ref(current_wt),
+            &main_branch,
+            &merged,
+            &protected,
+            None,
+        )
+        .is_empty();
+        if merged_too {
+            message!(
+                "{}",
+                format!(
+                    "Skipping '{}' (the current worktree); remove it from elsewhere or by name",
+                    get_branch_display(current_wt)
+                )
+                .dimmed()
+            );
+        }
+    }
+
+    if candidates.is_empty() {
+        println!(
+            "{}",
+            format!("No worktrees with branches merged into '{}'.", base).yellow()
+        );
+        return Ok(());
+    }
+
+    if dry_run || !(confirm.yes && output::is_quiet()) {
+        print_batch(
+            &format!(
+                "About to remove {} worktree(s) merged into '{}':",
+                candidates.len(),
+                base
+            ),
+            &candidates,
+        );
+    }
+
+    if dry_run {
+        let branches: Vec<&str> = candidates.iter().map(|wt| get_branch_display(wt)).collect();
+        if delete_branch {
+            println!(
+                "{}",
+                format!("Branches that would be deleted: {}", branches.join(", ")).dimmed()
+            );
+        } else {
+            println!(
+                "{}",
+                "Branches would be preserved (--keep-branch / deleteBranchOnRemove)".dimmed()
+            );
+        }
+        println!("\n{}", "Dry run, nothing was removed.".yellow());
+        return Ok(());
+    }
+
+    if !confirm_batch(None, confirm.yes, delete_branch)? {
+        return Ok(());
+    }
+
+    // Branches are merged and the whole batch was confirmed, so don't ask per branch
+    let merged_confirm = Confirmation { yes: true, force: true };
+    let mut failed = 0;
+    for wt in &candidates {
+        if let Err(e) = remove_worktree(&worktrees, wt, merged_confirm, false, delete_branch, prune_dir) {
+            failed += 1;
+            println!(
+                "{}",
+                format!("❌ Failed to remove '{}': {}", get_branch_display(wt), e).red()
+            );
+        }
+    }
+
+    if failed > 0 {
+        return Err(Error::msg(format!("Failed to remove {} worktree(s)", failed)));
+    }
+    Ok(())
+}
+
+/// Worktrees `--all-merged` removes: those whose branch is in `merged` (from
+/// `git branch --merged <main>`), except `main_branch`, `protected` branches, the
+/// `current` worktree, and bare, detached, stale or locked entries
+fn merged_removable<'a>(
+    worktrees: &'a [git::Worktree],
+    main_branch: &str,
+    merged: &[String],
+    protected: &[String],
+    current: Option<&Path>,
+) -> Vec<&'a git::Worktree> {
+    worktrees
+        .iter()
+        .filter(|wt| !wt.bare && wt.prunable.is_none() && wt.locked.is_none())
+        .filter(|wt| current != Some(wt.path.as_path()))
+        .filter(|wt| {
+            wt.branch.as_deref().map(clean_branch_name).is_some_and(|branch| {
+                branch != main_branch
+                    && !matches_branch_patterns(branch, protected)
+                    && merged.iter().any(|m| m == branch)
+            })
+        })
+        .collect()
+}
+
+/// The worktree containing `dir`, preferring the deepest when worktrees are nested
+fn innermost_worktree<'a>(worktrees: &'a [git::Worktree], dir: &Path) -> Option<&'a git::Worktree> {
+    worktrees
+        .iter()
+        .filter(|wt| dir.starts_with(&wt.path))
+        .max_by_key(|wt| wt.path.components().count())
+}
+
+/// Remove several worktrees named on the command line, confirming once for all of them
+///
+/// Names that don't resolve, orphaned worktrees, and locked worktrees without `--force`
+/// are reported and skipped; the rest are still removed, and the command fails at the end
+/// if anything was skipped or failed. `dry_run` prints each plan instead.
+pub fn run_many(
+    branch_names: &[String],
+    confirm: Confirmation,
+    dry_run: bool,
+    delete_branch: Option<bool>,
+    prune_dir: bool,
+) -> Result<()> {
+    let delete_branch = resolve_delete_branch(delete_branch)?;
+    let project = Project::find()?;
+    let worktrees = project.worktrees()?;
+
+    let mut failed = 0;
+    let mut targets: Vec<&git::Worktree> = Vec::new();
+    for name in branch_names {
+        match resolve_batch_target(&project.root, &worktrees, name, confirm.force) {
+            Ok(wt) if targets.iter().any(|t| t.path == wt.path) => {}
+            Ok(wt) => targets.push(wt),
+            Err(e) => {
+                failed += 1;
+                println!("{}", format!("❌ Skipping '{}': {}", name, e).red());
+            }
+        }
+    }
+    let total = targets.len() + failed;
+
+    if dry_run {
+        for wt in &targets {
+            print_dry_run(&worktrees, wt, delete_branch)?;
+        }
+    } else if !targets.is_empty() {
+        if !(confirm.yes && output::is_quiet()) {
+            print_batch(&format!("About to remove {} worktree(s):", targets.len()), &targets);
+        }
+
+        let current_dir = std::env::current_dir()?;
+        let current = targets.iter().copied().find(|wt| current_dir.starts_with(&wt.path));
+        if !confirm_batch(current, confirm.yes, delete_branch)? {
+            // Cancelling doesn't make the names that failed to resolve a success
+            if failed > 0 {
+                return Err(Error::msg(format!(
+                    "Failed to resolve {} of {} worktree(s); nothing was removed",
+                    failed, total
+                )));
+            }
+            return Ok(());
+        }
+
+        for wt in &targets {
+            let will_remove_current = current.is_some_and(|c| c.path == wt.path);
+            if let Err(e) = remove_worktree(&worktrees, wt, confirm, will_remove_current, delete_branch, prune_dir) {
+                failed += 1;
+                println!(
+                    "{}",
+                    format!("❌ Failed to remove '{}': {}", get_branch_display(wt), e).red()
+                );
+            }
+        }
+
+        if current.is_some() {
+            println!(
+                "{}",
+                format!("✓ Please navigate to project root: {}", project.root.display()).green()
+            );
+        }
+    }
+
+    if failed > 0 {
+        return Err(Error::msg(format!(
+            "Failed to remove {} of {} worktree(s)",
+            failed, total
+        )));
+    }
+    if !dry_run {
+        status!("{}", format!("✓ Removed {} worktree(s)", total).green());
+    }
+    Ok(())
+}
+
+/// Find the worktree for one name given to [`run_many`], rejecting ones that need
+/// individual handling (orphaned, bare) or are locked without `force`
+fn resolve_batch_target<'a>(
+    project_root: &Path,
+    worktrees: &'a [git::Worktree],
+    name: &str,
+    force: bool,
+) -> Result<&'a git::Worktree> {
+    let orphaned = |path: &Path| {
+        Error::msg(format!(
+            "{} is an orphaned worktree; remove it on its own with 'gwt remove {}'",
+            path.display(),
+            name
+        ))
+    };
+    let by_directory = project_root.join(name);
+    if is_orphaned_worktree(&by_directory) {
+        return Err(orphaned(&by_directory));
+    }
+
+    let target = find_worktree_by_branch(worktrees, name)?;
+    if target.bare {
+        return Err(Error::msg("Cannot remove the main (bare) repository."));
+    }
+    if is_orphaned_worktree(&target.path) {
+        return Err(orphaned(&target.path));
+    }
+    if let Some(reason) = &target.locked {
+        if !force {
+            return Err(Error::msg(format!(
+                "Worktree is locked{}. Run 'gwt unlock {}' first, or pass --force to remove it anyway.",
+                lock_reason_suffix(reason),
+                name
+            )));
+        }
+    }
+    Ok(target)
+}
+
+/// List the worktrees a batch removal is about to remove under `header`
+fn print_batch(header: &str, worktrees: &[&git::Worktree]) {
+    println!("{}", header.cyan().bold());
+    for wt in worktrees {
+        println!(
+            "  {} {}",
+            get_branch_display(wt).green(),
+            wt.path.display().to_string().dimmed()
+        );
+    }
+}
+
+/// Warn if `current` (the worktree we're in) is part of the batch, then ask once for the
+/// whole batch unless `yes`. Returns whether to go ahead.
+fn confirm_batch(current: Option<&git::Worktree>, yes: bool, delete_branch: bool) -> Result<bool> {
+    if let Some(current) = current {
+        println!(
+            "\n{}",
+            format!(
+                "⚠️  You are currently in '{}'. You will be moved to the project root after removal.",
+                get_branch_display(current)
+            )
+            .yellow()
+        );
+    }
+
+    if yes {
+        status!("\n{}", "Proceeding without confirmation (--yes)".dimmed());
+        return Ok(true);
     }
 
+    let prompt = if delete_branch {
+        "Remove these worktrees and their branches? (y/N): "
+    } else {
+        "Remove these worktrees (branches are kept)? (y/N): "
+    };
+    print!("\n{}", prompt.cyan());
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let confirmation = input.trim().to_lowercase();
+
+    if confirmation != "y" && confirmation != "yes" {
+        println!("{}", "Removal cancelled.".yellow());
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// The `--keep-branch` / `--delete-branch` flag if given, else `deleteBranchOnRemove`
+fn resolve_delete_branch(flag: Option<bool>) -> Result<bool> {
+    if let Some(delete_branch) = flag {
+        return Ok(delete_branch);
+    }
+    Ok(GitWorktreeConfig::find_config()?
+        .map(|(_, config)| config.delete_branch_on_remove())
+        .unwrap_or(true))
+}
+
+/// Run the preRemove hooks, remove the worktree and its branch (protected branches,
+/// and every branch when `delete_branch` is false, are kept), then run the postRemove
+/// hooks. Returns the project root.
+///
+/// If git can't remove the directory, see [`remove_leftover_dir`]; `prune_dir` deletes it
+/// without asking.
+fn remove_worktree(
+    worktrees: &[git::Worktree],
+    target_worktree: &git::Worktree,
+    confirm: Confirmation,
+    will_remove_current: bool,
+    delete_branch: bool,
+    prune_dir: bool,
+) -> Result<PathBuf> {
+    let branch_display = get_branch_display(target_worktree);
+
     // Find project root from the worktree being removed (go up one level)
     let project_root = if let Some(parent) = target_worktree.path.parent() {
         find_project_root_from(parent)?
@@ -89,110 +519,84 @@ pub fn run(branch_name: Option<&str>, force: bool) -> Result<()> {
         find_project_root_from(&target_worktree.path)?
     };
 
-    // Execute pre-remove hooks before any removal operations (run from worktree directory)
+    // Execute pre-remove hooks before any removal operations (run from worktree directory).
+    // A failing preRemove hook aborts the removal.
     hooks::execute_hooks(
         "preRemove",
         &target_worktree.path,
         &[
             ("branchName", branch_display),
             ("worktreePath", target_worktree.path.to_str().unwrap()),
+            ("projectRoot", project_root.to_str().unwrap()),
         ],
     )?;
 
     // Find another worktree to run git commands from
-    let main_branches = constants::PROTECTED_BRANCHES;
+    let protected = protected_branches()?;
     let git_working_dir = worktrees
         .iter()
         .find(|wt| {
-            // Try to find a main branch first
+            // Try to find a protected (main) branch first
             wt.path != target_worktree.path
                 && wt
                     .branch
-                    .as_ref()
-                    .map(|b| {
-                        let clean_branch = b.strip_prefix("refs/heads/").unwrap_or(b);
-                        main_branches.contains(&clean_branch)
-                    })
-                    .unwrap_or(false)
+                    .as_deref()
+                    .is_some_and(|b| matches_branch_patterns(clean_branch_name(b), &protected))
         })
         .or_else(|| {
-            // If no main branch, use any other worktree
+            // If there is none, use any other worktree
             worktrees.iter().find(|wt| wt.path != target_worktree.path)
         })
         .ok_or_else(|| Error::msg("No other worktrees found to execute git command from."))?;
 
-    // Remove the worktree
-    println!("\n{}", "Removing worktree...".cyan());
-    git::execute_streaming(
-        &["worktree", "remove", target_worktree.path.to_str().unwrap(), "--force"],
-        Some(&git_working_dir.path),
-    )?;
+    // Remove the worktree (git needs --force twice to remove a locked one)
+    status!("\n{}", "Removing worktree...".cyan());
+    let mut args = vec!["worktree", "remove", target_worktree.path.to_str().unwrap(), "--force"];
+    if target_worktree.locked.is_some() {
+        args.push("--force");
+    }
+    let removed = git::execute_streaming(&args, Some(&git_working_dir.path));
+    if removed.is_err() || target_worktree.path.exists() {
+        remove_leftover_dir(
+            worktrees,
+            target_worktree,
+            &git_working_dir.path,
+            confirm.yes,
+            prune_dir,
+            removed.err(),
+        )?;
+    }
 
-    println!(
+    status!(
         "{}",
         format!("✓ Worktree removed: {}", target_worktree.path.display()).green()
     );
 
-    // Delete the branch if it's not a main branch
-    if !main_branches.contains(&branch_display) {
-        // First try to delete the branch normally
-        match git::execute_capture(&["branch", "-d", branch_display], Some(&git_working_dir.path)) {
-            Ok(_) => {
-                println!("{}", format!("✓ Branch deleted: {}", branch_display).green());
-            }
-            Err(e) => {
-                // If normal deletion fails, check if it's because of unmerged changes
-                if e.to_string().contains("not fully merged") {
-                    println!(
-                        "{}",
-                        format!("⚠️  Branch '{}' has unmerged changes", branch_display).yellow()
-                    );
-
-                    // Ask for confirmation to force delete unless --force is used
-                    let should_force_delete = if force {
-                        true
-                    } else {
-                        print!("{}", "Force delete the branch? (y/N): ".cyan());
-                        io::stdout().flush()?;
-
-                        let mut input = String::new();
-                        io::stdin().read_line(&mut input)?;
-                        let force_delete = input.trim().to_lowercase();
-                        force_delete == "y" || force_delete == "yes"
-                    };
-
-                    if should_force_delete {
-                        match git::execute_streaming(&["branch", "-D", branch_display], Some(&git_working_dir.path)) {
-                            Ok(_) => {
-                                println!("{}", format!("✓ Branch force deleted: {}", branch_display).green());
-                            }
-                            Err(e) => {
-                                println!(
-                                    "{}",
-                                    format!("❌ Failed to delete branch '{}': {}", branch_display, e).red()
-                                );
-                            }
-                        }
-                    } else {
-                        println!(
-                            "{}",
-                            format!("⚠️  Branch '{}' was not deleted", branch_display).yellow()
-                        );
-                    }
-                } else {
-                    // Some other error occurred
-                    println!(
-                        "{}",
-                        format!("❌ Failed to delete branch '{}': {}", branch_display, e).red()
-                    );
-                }
-            }
-        }
-    } else {
-        println!(
+    // Delete the branch the removed worktree had checked out, unless it's protected,
+    // deletion is turned off, or another worktree still has it checked out
+    if target_worktree.branch.is_none() {
+        status!("{}", "✓ Detached HEAD, no branch to delete".green());
+    } else if matches_branch_patterns(branch_display, &protected) {
+        status!(
             "{}",
-            format!("✓ Branch: {} (preserved - main branch)", branch_display).green()
+            format!("✓ Branch: {} (preserved - protected branch)", branch_display).green()
         );
+    } else if !delete_branch {
+        status!("{}", format!("✓ Branch: {} (preserved)", branch_display).green());
+    } else if let Some(other) =
+        shared_branch_worktree(&git::list_worktrees(Some(&git_working_dir.path))?, target_worktree)
+    {
+        status!(
+            "{}",
+            format!(
+                "✓ Branch: {} (preserved - still checked out at {})",
+                branch_display,
+                other.path.display()
+            )
+            .green()
+        );
+    } else {
+        self::delete_branch(branch_display, &git_working_dir.path, confirm.unmerged_branch())?;
     }
 
     // If we removed the current worktree, change to project root before executing hooks
@@ -207,21 +611,270 @@ pub fn run(branch_name: Option<&str>, force: bool) -> Result<()> {
         &[
             ("branchName", branch_display),
             ("worktreePath", target_worktree.path.to_str().unwrap()),
+            ("projectRoot", project_root.to_str().unwrap()),
         ],
     )?;
 
-    // If we removed the current worktree, show message about moving to project root
-    if will_remove_current {
+    Ok(project_root)
+}
+
+/// Handle a worktree directory that `git worktree remove` refused to delete or left
+/// behind (submodules, files a hook created, ...)
+///
+/// Shows what is still in it, then deletes it when `prune_dir` is set or the user
+/// confirms (`yes` alone never deletes files git wouldn't), and prunes git's
+/// metadata for it. Only the worktree's own directory is ever deleted.
+fn remove_leftover_dir(
+    worktrees: &[git::Worktree],
+    target_worktree: &git::Worktree,
+    git_working_dir: &Path,
+    yes: bool,
+    prune_dir: bool,
+    git_error: Option<Error>,
+) -> Result<()> {
+    let path = &target_worktree.path;
+    if !path.exists() {
+        // git failed for some other reason; there is nothing to clean up
+        return git_error.map_or(Ok(()), Err);
+    }
+
+    // git has already printed why it failed
+    match &git_error {
+        Some(_) => println!("{}", format!("⚠️  git could not remove {}", path.display()).yellow()),
+        None => println!("{}", format!("⚠️  git left {} behind", path.display()).yellow()),
+    }
+    print_leftover_contents(path);
+
+    let confirmed = if prune_dir {
+        true
+    } else if yes {
+        false
+    } else {
+        print!("{}", "Delete the directory and everything in it? (y/N): ".cyan());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let confirmation = input.trim().to_lowercase();
+        confirmation == "y" || confirmation == "yes"
+    };
+    if !confirmed {
+        return Err(Error::git(format!(
+            "Worktree directory '{}' was left in place. Re-run with --prune-dir to delete it.",
+            path.display()
+        )));
+    }
+
+    let resolved = deletable_worktree_dir(path, worktrees)?;
+    fs::remove_dir_all(&resolved)
+        .map_err(|e| Error::msg(format!("Failed to delete '{}': {}", resolved.display(), e)))?;
+    git::execute_capture(&["worktree", "prune"], Some(git_working_dir))?;
+    status!(
+        "{}",
+        format!("✓ Deleted leftover directory: {}", resolved.display()).green()
+    );
+    Ok(())
+}
+
+/// Print `git status --porcelain` for a leftover worktree (first few entries)
+fn print_leftover_contents(path: &Path) {
+    const MAX_ENTRIES: usize = 10;
+
+    let Ok(status) = git::execute_capture(&["status", "--porcelain", "--ignored"], Some(path)) else {
+        return;
+    };
+    let entries: Vec<&str> = status.lines().collect();
+    for entry in entries.iter().take(MAX_ENTRIES) {
+        println!("    {}", entry.dimmed());
+    }
+    if entries.len() > MAX_ENTRIES {
         println!(
-            "{}",
-            format!("✓ Please navigate to project root: {}", project_root.display()).green()
+            "    {}",
+            format!("... and {} more", entries.len() - MAX_ENTRIES).dimmed()
         );
     }
+}
 
+/// Resolve `path` for deletion, refusing anything but a linked worktree's own directory
+///
+/// Rejects symlinks, a main repository (`.git` directory), and any directory that
+/// contains another worktree (such as the project root).
+fn deletable_worktree_dir(path: &Path, worktrees: &[git::Worktree]) -> Result<PathBuf> {
+    let refuse = |why: &str| Err(Error::msg(format!("Refusing to delete '{}': {}", path.display(), why)));
+
+    if fs::symlink_metadata(path)?.file_type().is_symlink() {
+        return refuse("it is a symlink");
+    }
+    let resolved = path.canonicalize()?;
+    if resolved.parent().is_none() {
+        return refuse("it is a filesystem root");
+    }
+    if resolved.join(".git").is_dir() {
+        return refuse("it is a main repository, not a linked worktree");
+    }
+    let contains_other_worktree = worktrees.iter().filter(|wt| wt.path != path).any(|wt| {
+        wt.path
+            .canonicalize()
+            .unwrap_or_else(|_| wt.path.clone())
+            .starts_with(&resolved)
+    });
+    if contains_other_worktree {
+        return refuse("it contains another worktree");
+    }
+    Ok(resolved)
+}
+
+/// Print what removing `target_worktree` would do, without running anything
+fn print_dry_run(worktrees: &[git::Worktree], target_worktree: &git::Worktree, delete_branch: bool) -> Result<()> {
+    let branch_display = get_branch_display(target_worktree);
+    let worktree_path = target_worktree.path.to_str().unwrap();
+    let project_root = find_project_root_from(target_worktree.path.parent().unwrap_or(&target_worktree.path))?;
+    let hook_variables = [
+        ("branchName", branch_display),
+        ("worktreePath", worktree_path),
+        ("projectRoot", project_root.to_str().unwrap()),
+    ];
+
+    println!("{}", "Dry run, nothing will be changed:".cyan().bold());
+    println!("  {}: {}", "Path".dimmed(), target_worktree.path.display());
+    println!("  {}: {}", "Branch".dimmed(), branch_display.green());
+    if let Some(reason) = &target_worktree.locked {
+        println!(
+            "  {}: locked{} (removed anyway with --force)",
+            "Lock".dimmed(),
+            lock_reason_suffix(reason)
+        );
+    }
+    println!(
+        "  {}: git worktree remove {} --force{}",
+        "Would run".dimmed(),
+        worktree_path,
+        if target_worktree.locked.is_some() {
+            " --force"
+        } else {
+            ""
+        }
+    );
+    if target_worktree.branch.is_none() {
+        println!("  Detached HEAD, no branch to delete");
+    } else if matches_branch_patterns(branch_display, &protected_branches()?) {
+        println!("  Branch '{}' would be preserved (protected branch)", branch_display);
+    } else if !delete_branch {
+        println!(
+            "  Branch '{}' would be preserved (--keep-branch / deleteBranchOnRemove)",
+            branch_display
+        );
+    } else if let Some(other) = shared_branch_worktree(worktrees, target_worktree) {
+        println!(
+            "  Branch '{}' would be preserved (still checked out at {})",
+            branch_display,
+            other.path.display()
+        );
+    } else {
+        println!(
+            "  {}: git branch -d {} (unmerged work is confirmed first, or force-deleted with --force)",
+            "Would run".dimmed(),
+            branch_display
+        );
+    }
+    hooks::print_planned_hooks("preRemove", &hook_variables)?;
+    hooks::print_planned_hooks("postRemove", &hook_variables)?;
     Ok(())
 }
 
-fn find_target_worktree<'a>(worktrees: &'a [git::Worktree], branch_name: Option<&str>) -> Result<&'a git::Worktree> {
+/// Another worktree that has `target`'s branch checked out too (possible when one was
+/// added with `git worktree add --force`), so the branch must outlive `target`
+fn shared_branch_worktree<'a>(worktrees: &'a [git::Worktree], target: &git::Worktree) -> Option<&'a git::Worktree> {
+    let branch = target.branch.as_deref()?;
+    worktrees
+        .iter()
+        .find(|wt| wt.path != target.path && wt.branch.as_deref() == Some(branch))
+}
+
+/// Delete a local branch, asking before force-deleting unmerged work
+///
+/// Tries `git branch -d` first. If the branch has unmerged changes, `unmerged` decides
+/// whether it is force-deleted with `-D`, kept, or the user is asked.
+/// Deletion failures are reported but not treated as errors.
+pub fn delete_branch(branch_name: &str, git_working_dir: &Path, unmerged: UnmergedBranch) -> Result<()> {
+    // First try to delete the branch normally
+    match git::execute_capture(&["branch", "-d", branch_name], Some(git_working_dir)) {
+        Ok(_) => {
+            status!("{}", format!("✓ Branch deleted: {}", branch_name).green());
+        }
+        Err(e) => {
+            // If normal deletion fails, check if it's because of unmerged changes
+            if e.to_string().contains("not fully merged") {
+                println!(
+                    "{}",
+                    format!("⚠️  Branch '{}' has unmerged changes", branch_name).yellow()
+                );
+
+                let should_force_delete = match unmerged {
+                    UnmergedBranch::ForceDelete => true,
+                    UnmergedBranch::Keep => false,
+                    UnmergedBranch::Ask => {
+                        print!("{}", "Force delete the branch? (y/N): ".cyan());
+                        io::stdout().flush()?;
+
+                        let mut input = String::new();
+                        io::stdin().read_line(&mut input)?;
+                        let force_delete = input.trim().to_lowercase();
+                        force_delete == "y" || force_delete == "yes"
+                    }
+                };
+
+                if should_force_delete {
+                    match git::execute_streaming(&["branch", "-D", branch_name], Some(git_working_dir)) {
+                        Ok(_) => {
+                            status!("{}", format!("✓ Branch force deleted: {}", branch_name).green());
+                        }
+                        Err(e) => {
+                            println!(
+                                "{}",
+                                format!("❌ Failed to delete branch '{}': {}", branch_name, e).red()
+                            );
+                        }
+                    }
+                } else if unmerged == UnmergedBranch::Keep {
+                    println!(
+                        "{}",
+                        format!(
+                            "⚠️  Branch '{}' was not deleted (pass --force to delete unmerged branches)",
+                            branch_name
+                        )
+                        .yellow()
+                    );
+                } else {
+                    println!("{}", format!("⚠️  Branch '{}' was not deleted", branch_name).yellow());
+                }
+            } else {
+                // Some other error occurred
+                println!(
+                    "{}",
+                    format!("❌ Failed to delete branch '{}': {}", branch_name, e).red()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// " (reason)" for a lock with a reason, nothing otherwise
+fn lock_reason_suffix(reason: &str) -> String {
+    if reason.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", reason)
+    }
+}
+
+/// The worktree for `branch_name`, or the one containing the current directory
+pub fn find_target_worktree<'a>(
+    worktrees: &'a [git::Worktree],
+    branch_name: Option<&str>,
+) -> Result<&'a git::Worktree> {
     match branch_name {
         None => find_current_worktree(worktrees),
         Some(target_branch) => find_worktree_by_branch(worktrees, target_branch),
@@ -236,14 +889,8 @@ fn find_current_worktree(worktrees: &[git::Worktree]) -> Result<&git::Worktree>
         .ok_or_else(|| Error::msg("Not in a git worktree. Please specify a branch to remove."))
 }
 
-fn find_worktree_by_branch<'a>(worktrees: &'a [git::Worktree], target_branch: &str) -> Result<&'a git::Worktree> {
-    // First try to find by branch name
-    if let Some(worktree) = find_by_branch_name(worktrees, target_branch) {
-        return Ok(worktree);
-    }
-
-    // Then try to find by path
-    if let Some(worktree) = find_by_path_name(worktrees, target_branch) {
+pub fn find_worktree_by_branch<'a>(worktrees: &'a [git::Worktree], target_branch: &str) -> Result<&'a git::Worktree> {
+    if let Some(worktree) = find_worktree(worktrees, target_branch) {
         return Ok(worktree);
     }
 
@@ -252,25 +899,6 @@ fn find_worktree_by_branch<'a>(worktrees: &'a [git::Worktree], target_branch: &s
     Err(Error::msg(format!("Worktree for '{}' not found", target_branch)))
 }
 
-fn find_by_branch_name<'a>(worktrees: &'a [git::Worktree], target_branch: &str) -> Option<&'a git::Worktree> {
-    worktrees.iter().find(|wt| {
-        wt.branch
-            .as_ref()
-            .map(|b| clean_branch_name(b) == target_branch)
-            .unwrap_or(false)
-    })
-}
-
-fn find_by_path_name<'a>(worktrees: &'a [git::Worktree], target_branch: &str) -> Option<&'a git::Worktree> {
-    worktrees.iter().find(|wt| {
-        wt.path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .map(|name| name == target_branch)
-            .unwrap_or(false)
-    })
-}
-
 fn show_available_worktrees(worktrees: &[git::Worktree]) {
     println!("{}", "Error: Worktree not found.".red());
     println!("\n{}", "Available worktrees:".yellow());
@@ -300,14 +928,24 @@ fn get_branch_display(worktree: &git::Worktree) -> &str {
 }
 
 /// Remove an orphaned worktree (one with a stale git reference)
-fn remove_orphaned_worktree(worktree_path: &std::path::Path, branch_name: &str, force: bool) -> Result<()> {
+fn remove_orphaned_worktree(worktree_path: &Path, branch_name: &str, yes: bool, dry_run: bool) -> Result<()> {
     use std::fs;
 
-    // Show what will be removed
-    println!("{}", "About to remove orphaned worktree:".cyan().bold());
-    println!("  {}: {}", "Path".dimmed(), worktree_path.display());
-    println!("  {}: {}", "Name".dimmed(), branch_name.green());
-    println!("  {}: {}", "Status".dimmed(), "Orphaned (stale reference)".yellow());
+    if dry_run {
+        println!("{}", "Dry run, nothing will be changed:".cyan().bold());
+        println!("  {}: {}", "Path".dimmed(), worktree_path.display());
+        println!("  {}: {}", "Name".dimmed(), branch_name.green());
+        println!("  Would delete the orphaned directory and run 'git worktree prune'");
+        return Ok(());
+    }
+
+    // Show what will be removed (skipped for --yes --quiet, where nothing is asked)
+    if !(yes && output::is_quiet()) {
+        println!("{}", "About to remove orphaned worktree:".cyan().bold());
+        println!("  {}: {}", "Path".dimmed(), worktree_path.display());
+        println!("  {}: {}", "Name".dimmed(), branch_name.green());
+        println!("  {}: {}", "Status".dimmed(), "Orphaned (stale reference)".yellow());
+    }
 
     // Check if we're currently in the worktree being removed
     let current_dir = std::env::current_dir()?;
@@ -320,8 +958,8 @@ fn remove_orphaned_worktree(worktree_path: &std::path::Path, branch_name: &str,
         );
     }
 
-    // Ask for confirmation unless --force is used
-    if !force {
+    // Ask for confirmation unless --yes or --force is used
+    if !yes {
         print!(
             "\n{}",
             "Are you sure you want to remove this orphaned worktree? (y/N): ".cyan()
@@ -336,9 +974,11 @@ fn remove_orphaned_worktree(worktree_path: &std::path::Path, branch_name: &str,
             println!("{}", "Removal cancelled.".yellow());
             return Ok(());
         }
+    } else {
+        status!("\n{}", "Proceeding without confirmation (--yes)".dimmed());
     }
 
-    let project_root = find_project_root()?;
+    let project_root = Project::find()?.root;
 
     // If we're currently in the worktree being removed, change directory first
     if will_remove_current {
@@ -346,21 +986,21 @@ fn remove_orphaned_worktree(worktree_path: &std::path::Path, branch_name: &str,
     }
 
     // Remove the directory
-    println!("\n{}", "Removing orphaned worktree directory...".cyan());
+    status!("\n{}", "Removing orphaned worktree directory...".cyan());
     fs::remove_dir_all(worktree_path)
         .map_err(|e| Error::msg(format!("Failed to remove directory {}: {}", worktree_path.display(), e)))?;
 
-    println!(
+    status!(
         "{}",
         format!("✓ Directory removed: {}", worktree_path.display()).green()
     );
 
     // Try to prune worktree references from a valid git directory
     if let Ok(valid_git_dir) = find_valid_git_directory(&project_root) {
-        println!("{}", "Pruning stale worktree references...".cyan());
+        status!("{}", "Pruning stale worktree references...".cyan());
         match git::prune_worktrees(&valid_git_dir) {
             Ok(_) => {
-                println!("{}", "✓ Worktree references pruned".green());
+                status!("{}", "✓ Worktree references pruned".green());
             }
             Err(e) => {
                 println!("{}", format!("⚠️  Failed to prune worktree references: {}", e).yellow());
@@ -375,10 +1015,128 @@ fn remove_orphaned_worktree(worktree_path: &std::path::Path, branch_name: &str,
         );
     }
 
-    println!(
+    status!(
         "\n{}",
         "Note: Orphaned worktree removed. Hooks were skipped due to invalid git state.".dimmed()
     );
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::utils::default_protected_branches;
+
+    fn worktree(path: &str, branch: Option<&str>) -> git::Worktree {
+        git::Worktree {
+            path: PathBuf::from(path),
+            head: "1f0c3a9d".to_string(),
+            branch: branch.map(|b| format!("refs/heads/{}", b)),
+            bare: false,
+            detached: branch.is_none(),
+            prunable: None,
+            locked: None,
+        }
+    }
+
+    fn paths(worktrees: &[&git::Worktree]) -> Vec<String> {
+        worktrees.iter().map(|wt| wt.path.display().to_string()).collect()
+    }
+
+    fn merged(branches: &[&str]) -> Vec<String> {
+        branches.iter().map(|b| b.to_string()).collect()
+    }
+
+    #[test]
+    fn test_merged_removable() {
+        let worktrees = vec![
+            worktree("/repo", Some("main")),
+            worktree("/wt/feature/done", Some("feature/done")),
+            worktree("/wt/feature/wip", Some("feature/wip")),
+            worktree("/wt/detached", None),
+            git::Worktree {
+                locked: Some(String::new()),
+                ..worktree("/wt/feature/locked", Some("feature/locked"))
+            },
+            git::Worktree {
+                prunable: Some("gitdir file points to non-existent location".to_string()),
+                ..worktree("/wt/feature/gone", Some("feature/gone"))
+            },
+        ];
+        let merged = merged(&["main", "feature/done", "feature/locked", "feature/gone"]);
+
+        let removable = merged_removable(&worktrees, "main", &merged, &default_protected_branches(), None);
+        assert_eq!(paths(&removable), ["/wt/feature/done"]);
+    }
+
+    #[test]
+    fn test_merged_removable_skips_current_worktree() {
+        let worktrees = vec![
+            worktree("/repo", Some("main")),
+            worktree("/wt/feature/a", Some("feature/a")),
+            worktree("/wt/feature/b", Some("feature/b")),
+        ];
+        let merged = merged(&["main", "feature/a", "feature/b"]);
+
+        let removable = merged_removable(
+            &worktrees,
+            "main",
+            &merged,
+            &default_protected_branches(),
+            Some(Path::new("/wt/feature/a")),
+        );
+        assert_eq!(paths(&removable), ["/wt/feature/b"]);
+    }
+
+    #[test]
+    fn test_merged_removable_skips_protected_branches() {
+        // The main branch is trunk, so develop is only excluded for being protected
+        let worktrees = vec![
+            worktree("/repo", Some("trunk")),
+            worktree("/wt/develop", Some("develop")),
+            worktree("/wt/release", Some("release")),
+        ];
+        let merged = merged(&["trunk", "develop", "release"]);
+
+        let removable = merged_removable(&worktrees, "trunk", &merged, &default_protected_branches(), None);
+        assert_eq!(paths(&removable), ["/wt/release"]);
+
+        // A configured list replaces the defaults, and may use globs
+        let removable = merged_removable(&worktrees, "trunk", &merged, &["release".to_string()], None);
+        assert_eq!(paths(&removable), ["/wt/develop"]);
+        let removable = merged_removable(&worktrees, "trunk", &merged, &["rel*".to_string()], None);
+        assert_eq!(paths(&removable), ["/wt/develop"]);
+    }
+
+    #[test]
+    fn test_innermost_worktree() {
+        let worktrees = vec![
+            worktree("/repo", Some("main")),
+            worktree("/repo/nested", Some("feature/nested")),
+        ];
+        let find = |dir: &str| innermost_worktree(&worktrees, Path::new(dir)).map(|wt| wt.path.clone());
+
+        assert_eq!(find("/repo/nested/src"), Some(PathBuf::from("/repo/nested")));
+        assert_eq!(find("/repo/src"), Some(PathBuf::from("/repo")));
+        assert_eq!(find("/elsewhere"), None);
+    }
+
+    #[test]
+    fn test_shared_branch_worktree() {
+        let worktrees = vec![
+            worktree("/repo", Some("main")),
+            worktree("/wt/feature/a", Some("feature/a")),
+            worktree("/wt/feature/a-copy", Some("feature/a")),
+            worktree("/wt/feature/b", Some("feature/b")),
+            worktree("/wt/detached", None),
+        ];
+        let shared = |target: &git::Worktree| shared_branch_worktree(&worktrees, target).map(|wt| wt.path.clone());
+
+        assert_eq!(shared(&worktrees[1]), Some(PathBuf::from("/wt/feature/a-copy")));
+        assert_eq!(shared(&worktrees[2]), Some(PathBuf::from("/wt/feature/a")));
+        assert_eq!(shared(&worktrees[3]), None);
+        // Detached worktrees have no branch to share
+        assert_eq!(shared(&worktrees[4]), None);
+    }
+}
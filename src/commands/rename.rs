@@ -0,0 +1,84 @@
+use colored::Colorize;
+use std::fs;
+
+use super::add::{main_branch_and_remote, worktrees_path_and_layout};
+use super::remove::find_worktree_by_branch;
+use crate::{
+    core::project::{find_git_directory, find_project_root},
+    core::utils::is_protected_branch,
+    error::{Error, Result},
+    git,
+};
+
+/// Rename a branch and move its worktree directory to match
+pub fn run(old_branch: &str, new_branch: &str) -> Result<()> {
+    if old_branch.is_empty() || new_branch.is_empty() {
+        return Err(Error::msg(
+            "Both branch names are required\nUsage: gwt rename <old-branch> <new-branch>",
+        ));
+    }
+    if old_branch == new_branch {
+        return Err(Error::msg("Old and new branch names are the same"));
+    }
+    for branch in [old_branch, new_branch] {
+        if is_protected_branch(branch)? {
+            return Err(Error::msg(format!("Cannot rename protected branch '{}'", branch)));
+        }
+    }
+
+    let project_root = find_project_root()?;
+    let git_dir = find_git_directory()?;
+    let worktrees = git::list_worktrees(Some(&git_dir))?;
+    let target = find_worktree_by_branch(&worktrees, old_branch)?;
+
+    if target.bare || target.path.join(".git").is_dir() {
+        return Err(Error::msg("Cannot move the main repository worktree"));
+    }
+
+    let (_, remote) = main_branch_and_remote(&project_root)?;
+    let (local_exists, _) = git::branch_exists(&git_dir, new_branch, &remote)?;
+    if local_exists {
+        return Err(Error::git(format!("Branch '{}' already exists", new_branch)));
+    }
+
+    let (worktrees_path, layout) = worktrees_path_and_layout(&project_root)?;
+    let new_path = layout.worktree_path(&worktrees_path, new_branch);
+    if new_path.exists() {
+        return Err(Error::msg(format!(
+            "Target directory already exists: {}",
+            new_path.display()
+        )));
+    }
+
+    println!("{}", "Renaming worktree:".cyan().bold());
+    println!("  {}: {} -> {}", "Branch".dimmed(), old_branch, new_branch.green());
+    println!(
+        "  {}: {} -> {}",
+        "Path".dimmed(),
+        target.path.display(),
+        new_path.display().to_string().green()
+    );
+
+    git::execute_capture(&["branch", "-m", old_branch, new_branch], Some(&git_dir))?;
+    println!("{}", format!("✓ Branch renamed: {}", new_branch).green());
+
+    if let Some(parent) = new_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if let Err(e) = git::move_worktree(&git_dir, &target.path, &new_path) {
+        // Put the branch name back so branch and directory stay in sync
+        git::execute_capture(&["branch", "-m", new_branch, old_branch], Some(&git_dir)).ok();
+        return Err(e);
+    }
+    println!("{}", format!("✓ Worktree moved to: {}", new_path.display()).green());
+
+    let current_dir = std::env::current_dir()?;
+    if current_dir.starts_with(&target.path) {
+        println!(
+            "{}",
+            format!("✓ Please navigate to the new location: {}", new_path.display()).green()
+        );
+    }
+
+    Ok(())
+}
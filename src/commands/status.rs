@@ -0,0 +1,71 @@
+use colored::Colorize;
+
+use crate::{
+    config::GitWorktreeConfig,
+    constants::DEFAULT_REMOTE,
+    core::project::{clean_branch_name, find_git_directory},
+    error::Result,
+    git, status,
+};
+
+struct WorktreeStatus {
+    branch: String,
+    dirty: Option<usize>,
+    ahead_behind: Option<(usize, usize)>,
+}
+
+/// Show uncommitted changes and ahead/behind counts for every worktree
+pub fn run(fetch: bool) -> Result<()> {
+    let git_dir = find_git_directory()?;
+
+    if fetch {
+        let remote = GitWorktreeConfig::find_config()?
+            .map(|(_, config)| config.remote().to_string())
+            .unwrap_or_else(|| DEFAULT_REMOTE.to_string());
+        status!("{}", format!("Fetching latest changes from {}...", remote).cyan());
+        git::execute_streaming(&["fetch", &remote], Some(&git_dir))?;
+    }
+
+    let worktrees = git::list_worktrees(Some(&git_dir))?;
+    let statuses: Vec<WorktreeStatus> = worktrees
+        .iter()
+        .filter(|wt| !wt.bare && wt.prunable.is_none())
+        .map(|wt| WorktreeStatus {
+            branch: wt
+                .branch
+                .as_deref()
+                .map(|b| clean_branch_name(b).to_string())
+                .unwrap_or_else(|| format!("(detached {})", &wt.head[..wt.head.len().min(7)])),
+            dirty: git::count_dirty_files(&wt.path).ok(),
+            ahead_behind: git::ahead_behind_upstream(&wt.path),
+        })
+        .collect();
+
+    if statuses.is_empty() {
+        println!("{}", "No worktrees found.".yellow());
+        return Ok(());
+    }
+
+    let branch_width = statuses.iter().map(|s| s.branch.len()).max().unwrap_or(0);
+
+    for status in &statuses {
+        let dirty = match status.dirty {
+            Some(0) => format!("{:<10}", "clean").green(),
+            Some(n) => format!("{:<10}", format!("{} changed", n)).yellow(),
+            None => format!("{:<10}", "-").dimmed(),
+        };
+        let sync = match status.ahead_behind {
+            Some((0, 0)) => "up to date".green(),
+            Some((ahead, behind)) => format!("↑{} ↓{}", ahead, behind).cyan(),
+            None => "-".dimmed(),
+        };
+        println!(
+            "{}  {}  {}",
+            format!("{:<width$}", status.branch, width = branch_width).bold(),
+            dirty,
+            sync
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use colored::Colorize;
+use std::path::Path;
+
+use super::add::main_branch_and_remote;
+use crate::{
+    cli::SyncStrategy,
+    core::project::find_project_root,
+    error::{Error, Result},
+    git, status,
+};
+
+/// Bring the current worktree's branch up to date with `<remote>/<main-branch>`
+///
+/// Fetches the configured remote, then rebases onto (or merges) the remote main
+/// branch. A dirty working tree is refused unless `autostash` is set.
+pub fn run(strategy: SyncStrategy, autostash: bool) -> Result<()> {
+    let worktree = git::get_git_root()?
+        .ok_or_else(|| Error::git("Not in a git worktree. Run gwt sync from inside the worktree to update."))?;
+    let branch = git::get_current_branch(&worktree)
+        .map_err(|_| Error::branch("HEAD is detached. Check out a branch before syncing."))?;
+
+    let project_root = find_project_root()?;
+    let (main_branch, remote) = main_branch_and_remote(&project_root)?;
+
+    if !autostash {
+        let dirty = git::count_dirty_files(&worktree)?;
+        if dirty > 0 {
+            return Err(Error::git(format!(
+                "Working tree has {} uncommitted change(s). Commit or stash them, or re-run with --autostash.",
+                dirty
+            )));
+        }
+    }
+
+    status!("{}", format!("Fetching latest changes from {}...", remote).cyan());
+    git::execute_streaming(&["fetch", &remote], Some(&worktree))?;
+
+    let upstream = format!("{}/{}", remote, main_branch);
+    let (command, label) = match strategy {
+        SyncStrategy::Rebase => ("rebase", "Rebase"),
+        SyncStrategy::Merge => ("merge", "Merge"),
+    };
+    let mut args = vec![command];
+    if autostash {
+        args.push("--autostash");
+    }
+    args.push(&upstream);
+
+    status!(
+        "{}",
+        format!("Syncing '{}' with {} ({})...", branch, upstream, command).cyan()
+    );
+    if let Err(e) = git::execute_streaming(&args, Some(&worktree)) {
+        if !operation_in_progress(&worktree, strategy) {
+            return Err(e);
+        }
+        return Err(Error::git(format!(
+            "{} of '{}' with {} stopped with conflicts.\n\
+             Resolve them in {}, then run 'git {} --continue' (or 'git {} --abort' to undo) and re-run gwt sync.",
+            label,
+            branch,
+            upstream,
+            worktree.display(),
+            command,
+            command
+        )));
+    }
+
+    match strategy {
+        SyncStrategy::Rebase => status!("{}", format!("✓ Rebased '{}' onto {}", branch, upstream).green()),
+        SyncStrategy::Merge => status!("{}", format!("✓ Merged {} into '{}'", upstream, branch).green()),
+    }
+    Ok(())
+}
+
+/// Whether a failed rebase/merge left an operation to resolve (i.e. it hit conflicts)
+fn operation_in_progress(worktree: &Path, strategy: SyncStrategy) -> bool {
+    match strategy {
+        SyncStrategy::Merge => git::ref_exists(worktree, "MERGE_HEAD"),
+        SyncStrategy::Rebase => ["rebase-merge", "rebase-apply"].iter().any(|dir| {
+            git::execute_capture(&["rev-parse", "--git-path", dir], Some(worktree))
+                .map(|path| worktree.join(path).exists())
+                .unwrap_or(false)
+        }),
+    }
+}
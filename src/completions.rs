@@ -3,6 +3,7 @@ use colored::Colorize;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 use crate::error::{Error, Result};
 
@@ -33,18 +34,139 @@ fn is_writable(path: &Path) -> bool {
     }
 }
 
-pub fn get_completion_content(shell: Shell) -> &'static str {
+/// Hidden command that prints worktree branch names for dynamic completion
+pub const COMPLETE_BRANCHES_COMMAND: &str = "__complete_branches";
+
+// Dynamic branch completion: these snippets call the hidden `gwt __complete_branches`
+// command for the branch argument of `remove`, `rename`, `lock`, `unlock` and `move` (bash, zsh, fish only).
+
+const BASH_BRANCH_COMPLETION: &str = r#"
+_gwt_with_branches() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    # remove takes any number of branches; the others take one
+    if [[ "$cur" != -* && ( ${COMP_CWORD} -eq 2 || "${COMP_WORDS[1]}" == remove ) ]]; then
+        case "${COMP_WORDS[1]}" in
+            remove|rename|lock|unlock|move)
+                COMPREPLY=( $(compgen -W "$(gwt __complete_branches 2>/dev/null)" -- "$cur") )
+                return 0
+                ;;
+        esac
+    fi
+    _gwt "$@"
+}
+complete -F _gwt_with_branches -o bashdefault -o default gwt
+"#;
+
+const ZSH_BRANCH_FUNCTION: &str = r#"
+_gwt_worktree_branches() {
+    local -a branches
+    branches=(${(f)"$(gwt __complete_branches 2>/dev/null)"})
+    compadd -a branches
+}
+"#;
+
+/// Positional argument specs in the generated zsh script that complete worktree branches
+const ZSH_BRANCH_ARGS: &[&str] = &[
+    "'*::branch_names -- Branch names to remove (current worktree if none given):_default'",
+    "':old_branch -- Current branch name:_default'",
+    "'::branch_name -- Branch name of the worktree to lock (current worktree if not specified):_default'",
+    "'::branch_name -- Branch name of the worktree to unlock (current worktree if not specified):_default'",
+    "':branch_name -- Branch name of the worktree to move:_default'",
+];
+
+const FISH_BRANCH_COMPLETION: &str = r#"
+complete -c gwt -n "__fish_seen_subcommand_from remove rename lock unlock move" -f -a "(gwt __complete_branches 2>/dev/null)"
+"#;
+
+/// Type names the generated PowerShell script imports with `using namespace`, fully qualified
+const POWERSHELL_QUALIFIED_TYPES: &[(&str, &str)] = &[
+    ("[CompletionResult]", "[System.Management.Automation.CompletionResult]"),
+    (
+        "[CompletionResultType]",
+        "[System.Management.Automation.CompletionResultType]",
+    ),
+    (
+        "[StringConstantExpressionAst]",
+        "[System.Management.Automation.Language.StringConstantExpressionAst]",
+    ),
+    (
+        "[StringConstantType]",
+        "[System.Management.Automation.Language.StringConstantType]",
+    ),
+];
+
+/// Line of the PowerShell script that registers the completer, used to spot an existing install
+const POWERSHELL_COMPLETER_SIGNATURE: &str = "Register-ArgumentCompleter -Native -CommandName 'gwt'";
+
+// Everything gwt writes into a shell rc file or profile goes between these markers, so a
+// reinstall can replace its own block instead of appending another one.
+const BLOCK_START_MARKER: &str = "# >>> gwt completions >>>";
+const BLOCK_END_MARKER: &str = "# <<< gwt completions <<<";
+
+pub fn get_completion_content(shell: Shell) -> String {
     match shell {
-        Shell::Bash => BASH_COMPLETION,
-        Shell::Zsh => ZSH_COMPLETION,
-        Shell::Fish => FISH_COMPLETION,
-        Shell::PowerShell => POWERSHELL_COMPLETION,
-        Shell::Elvish => ELVISH_COMPLETION,
+        Shell::Bash => format!("{}{}", BASH_COMPLETION, BASH_BRANCH_COMPLETION),
+        Shell::Zsh => zsh_with_branch_completion(ZSH_COMPLETION),
+        Shell::Fish => format!("{}{}", FISH_COMPLETION, FISH_BRANCH_COMPLETION),
+        Shell::PowerShell => powershell_without_using(POWERSHELL_COMPLETION),
+        Shell::Elvish => ELVISH_COMPLETION.to_string(),
         _ => panic!("Unsupported shell: {:?}", shell),
     }
 }
 
+/// Point the branch arguments at `_gwt_worktree_branches` and define it after `#compdef`
+fn zsh_with_branch_completion(script: &str) -> String {
+    let mut script = script.to_string();
+    for spec in ZSH_BRANCH_ARGS {
+        let dynamic = spec.replace(":_default'", ":_gwt_worktree_branches'");
+        script = script.replace(spec, &dynamic);
+    }
+    match script.split_once('\n') {
+        Some((compdef, rest)) => format!("{}\n{}{}", compdef, ZSH_BRANCH_FUNCTION, rest),
+        None => script,
+    }
+}
+
+/// Drop the `using namespace` lines so the script still parses when appended to `$PROFILE`
+///
+/// PowerShell only allows `using` at the very top of a script, which an appended block isn't.
+fn powershell_without_using(script: &str) -> String {
+    let mut script: String = script
+        .lines()
+        .filter(|line| !line.starts_with("using namespace "))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    for (short, qualified) in POWERSHELL_QUALIFIED_TYPES {
+        script = script.replace(short, qualified);
+    }
+    script.trim_start().to_string()
+}
+
+/// Print branch names of the current project's worktrees, one per line
+///
+/// Backs shell tab completion, so it stays quiet and never fails.
+pub fn print_worktree_branches() {
+    let Ok(git_dir) = crate::core::project::find_git_directory() else {
+        return;
+    };
+    let Ok(worktrees) = crate::git::list_worktrees(Some(&git_dir)) else {
+        return;
+    };
+    for branch in worktrees.iter().filter_map(|wt| wt.branch.as_deref()) {
+        println!("{}", crate::core::project::clean_branch_name(branch));
+    }
+}
+
 pub fn detect_shell() -> Result<Shell> {
+    // PowerShell doesn't set $SHELL, so look at the process that launched gwt first
+    if parent_process_name()
+        .as_deref()
+        .and_then(powershell_executable)
+        .is_some()
+    {
+        return Ok(Shell::PowerShell);
+    }
+
     if let Ok(shell_path) = env::var("SHELL") {
         if shell_path.contains("zsh") {
             return Ok(Shell::Zsh);
@@ -70,6 +192,78 @@ pub fn detect_shell() -> Result<Shell> {
     }
 }
 
+/// Name of the process that launched gwt, where that's cheap to find out
+fn parent_process_name() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let ppid = std::os::unix::process::parent_id();
+        fs::read_to_string(format!("/proc/{}/comm", ppid)).ok()
+    }
+
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        let ppid = std::os::unix::process::parent_id();
+        let output = Command::new("ps")
+            .args(["-o", "comm=", "-p", &ppid.to_string()])
+            .output()
+            .ok()?;
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+/// Map a process name or path to the PowerShell executable it runs: `pwsh` or Windows `powershell`
+fn powershell_executable(process: &str) -> Option<&'static str> {
+    let name = process
+        .trim()
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match name.strip_suffix(".exe").unwrap_or(&name) {
+        "pwsh" => Some("pwsh"),
+        "powershell" => Some("powershell"),
+        _ => None,
+    }
+}
+
+/// Locate the current user's PowerShell profile (`$PROFILE`)
+///
+/// Asks PowerShell itself, trying the flavour that launched gwt first, and falls back to the
+/// documented default when neither `pwsh` nor Windows `powershell` can be run.
+fn powershell_profile_path(home: &str) -> PathBuf {
+    let mut executables = ["pwsh", "powershell"];
+    if parent_process_name().as_deref().and_then(powershell_executable) == Some("powershell") {
+        executables.reverse();
+    }
+
+    for executable in executables {
+        let Ok(output) = Command::new(executable)
+            .args(["-NoLogo", "-NoProfile", "-Command", "$PROFILE"])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+        else {
+            continue;
+        };
+        let profile = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !profile.is_empty() {
+            return PathBuf::from(profile);
+        }
+    }
+
+    if cfg!(windows) {
+        PathBuf::from(env::var("USERPROFILE").unwrap_or_else(|_| home.to_string()))
+            .join("Documents/PowerShell/Microsoft.PowerShell_profile.ps1")
+    } else {
+        PathBuf::from(home).join(".config/powershell/Microsoft.PowerShell_profile.ps1")
+    }
+}
+
 pub fn get_completion_install_path(shell: Shell) -> Result<PathBuf> {
     let home = env::var("HOME").map_err(|_| Error::msg("Could not determine home directory"))?;
 
@@ -101,16 +295,8 @@ pub fn get_completion_install_path(shell: Shell) -> Result<PathBuf> {
             Ok(PathBuf::from(&home).join(".local/share/zsh/site-functions/_gwt"))
         }
         Shell::Fish => Ok(PathBuf::from(&home).join(".config/fish/completions/gwt.fish")),
-        Shell::PowerShell => {
-            // PowerShell profile location
-            let profile_path = if cfg!(windows) {
-                PathBuf::from(&env::var("USERPROFILE").unwrap_or(home))
-                    .join("Documents/PowerShell/Modules/gwt-completions/gwt-completions.psm1")
-            } else {
-                PathBuf::from(&home).join(".config/powershell/Modules/gwt-completions/gwt-completions.psm1")
-            };
-            Ok(profile_path)
-        }
+        // PowerShell has no completions directory; the script goes into the profile itself
+        Shell::PowerShell => Ok(powershell_profile_path(&home)),
         Shell::Elvish => Ok(PathBuf::from(&home).join(".elvish/lib/gwt-completions.elv")),
         _ => Err(Error::msg(format!("Unsupported shell: {:?}", shell))),
     }
@@ -120,6 +306,10 @@ pub fn install_completions_for_shell(shell: Shell) -> Result<()> {
     let content = get_completion_content(shell);
     let install_path = get_completion_install_path(shell)?;
 
+    if matches!(shell, Shell::PowerShell) {
+        return install_powershell_completions(&content, &install_path);
+    }
+
     // Create parent directory if it doesn't exist
     if let Some(parent) = install_path.parent() {
         fs::create_dir_all(parent)?;
@@ -147,12 +337,6 @@ pub fn install_completions_for_shell(shell: Shell) -> Result<()> {
         Shell::Fish => {
             println!("\nCompletions will be available immediately in new fish sessions.");
         }
-        Shell::PowerShell => {
-            println!("\nTo activate completions, add the following to your PowerShell profile:");
-            println!("  {}", "Import-Module gwt-completions".cyan());
-            println!("\nYour profile is located at:");
-            println!("  {}", "$PROFILE".cyan());
-        }
         Shell::Elvish => {
             println!("\nTo activate completions, add the following to your ~/.elvish/rc.elv:");
             println!("  {}", "use gwt-completions".cyan());
@@ -163,18 +347,177 @@ pub fn install_completions_for_shell(shell: Shell) -> Result<()> {
     Ok(())
 }
 
+/// Remove what `install` added for `shell`: the completion file and any marked rc/profile block
+pub fn uninstall_completions_for_shell(shell: Shell) -> Result<()> {
+    let install_path = get_completion_install_path(shell)?;
+    let mut removed = Vec::new();
+
+    if matches!(shell, Shell::PowerShell) {
+        if remove_marked_block_from(&install_path)? {
+            removed.push(format!(
+                "completion block from your profile: {}",
+                install_path.display()
+            ));
+        }
+    } else if install_path.is_file() {
+        fs::remove_file(&install_path)?;
+        removed.push(install_path.display().to_string());
+    }
+
+    if matches!(shell, Shell::Zsh) {
+        let zshrc_path = PathBuf::from(env::var("HOME")?).join(".zshrc");
+        if remove_marked_block_from(&zshrc_path)? {
+            removed.push("completion block from ~/.zshrc".to_string());
+        }
+    }
+
+    if removed.is_empty() {
+        println!(
+            "No {} completions installed by gwt were found",
+            shell.to_string().green()
+        );
+        return Ok(());
+    }
+    for item in removed {
+        println!("✓ Removed {}", item.cyan());
+    }
+    Ok(())
+}
+
+fn install_powershell_completions(content: &str, profile_path: &Path) -> Result<()> {
+    let profile = read_if_exists(profile_path)?;
+
+    // Installs from before the markers were added can't be replaced safely, so leave them be
+    let unmarked_install = !has_marked_block(&profile) && profile.contains(POWERSHELL_COMPLETER_SIGNATURE);
+    if unmarked_install || !write_marked_block(profile_path, content)? {
+        println!(
+            "✓ {} completions already present in: {}",
+            Shell::PowerShell.to_string().green(),
+            profile_path.display().to_string().cyan()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "✓ Installed {} completions to your profile: {}",
+        Shell::PowerShell.to_string().green(),
+        profile_path.display().to_string().cyan()
+    );
+    println!("\nTo activate completions in your current shell, run:");
+    println!("  {}", ". $PROFILE".cyan());
+    println!("\nOr start a new PowerShell session.");
+    Ok(())
+}
+
+fn read_if_exists(path: &Path) -> Result<String> {
+    if path.exists() {
+        Ok(fs::read_to_string(path)?)
+    } else {
+        Ok(String::new())
+    }
+}
+
+/// Byte range of the marked gwt block in `content`, including the end marker's newline
+fn marked_block_range(content: &str) -> Option<std::ops::Range<usize>> {
+    let start = content.find(BLOCK_START_MARKER)?;
+    let mut end = start + content[start..].find(BLOCK_END_MARKER)? + BLOCK_END_MARKER.len();
+    if content[end..].starts_with('\n') {
+        end += 1;
+    }
+    Some(start..end)
+}
+
+fn has_marked_block(content: &str) -> bool {
+    marked_block_range(content).is_some()
+}
+
+/// Put `body` between the gwt markers, replacing an existing marked block or appending a new one
+///
+/// Returns whether `content` changed.
+fn upsert_marked_block(content: &mut String, body: &str) -> bool {
+    let mut block = format!("{}\n{}", BLOCK_START_MARKER, body);
+    if !block.ends_with('\n') {
+        block.push('\n');
+    }
+    block.push_str(BLOCK_END_MARKER);
+    block.push('\n');
+
+    if let Some(range) = marked_block_range(content) {
+        if content[range.clone()] == block {
+            return false;
+        }
+        content.replace_range(range, &block);
+        return true;
+    }
+
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    content.push_str(&block);
+    true
+}
+
+/// Write `body` as the marked gwt block of the file at `path`, creating the file if needed
+///
+/// Returns whether the file changed.
+fn write_marked_block(path: &Path, body: &str) -> Result<bool> {
+    let mut content = read_if_exists(path)?;
+    if !upsert_marked_block(&mut content, body) {
+        return Ok(false);
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(true)
+}
+
+/// Remove the marked gwt block from `content`, along with the blank line `upsert_marked_block`
+/// put before it
+///
+/// Returns whether `content` changed.
+fn remove_marked_block(content: &mut String) -> bool {
+    let Some(mut range) = marked_block_range(content) else {
+        return false;
+    };
+    if content[..range.start].ends_with("\n\n") {
+        range.start -= 1;
+    }
+    content.replace_range(range, "");
+    true
+}
+
+/// Remove the marked gwt block from the file at `path`, if the file exists and has one
+///
+/// Returns whether the file changed.
+fn remove_marked_block_from(path: &Path) -> Result<bool> {
+    let mut content = read_if_exists(path)?;
+    if !remove_marked_block(&mut content) {
+        return Ok(false);
+    }
+    fs::write(path, content)?;
+    Ok(true)
+}
+
 fn setup_zsh_completions() -> Result<()> {
     let home = env::var("HOME")?;
     let zshrc_path = PathBuf::from(&home).join(".zshrc");
 
     ensure_zshrc_exists(&zshrc_path)?;
 
-    let mut content = fs::read_to_string(&zshrc_path)?;
-    let modified = add_zsh_completion_config(&mut content, &home)?;
+    let content = fs::read_to_string(&zshrc_path)?;
+    let fpath_dir = zsh_fpath_dir(&home);
 
-    if modified {
-        fs::write(&zshrc_path, content)?;
-        println!("✓ Updated ~/.zshrc");
+    // Installs from before the markers were added can't be replaced safely, so leave them be
+    let unmarked_install = !has_marked_block(&content) && content.contains(&fpath_dir);
+    if unmarked_install || !write_marked_block(&zshrc_path, &zsh_completion_config(&content, &fpath_dir))? {
+        println!("\n✓ Completion path already configured in ~/.zshrc");
+    } else {
+        println!("\n✓ Updated ~/.zshrc");
     }
 
     show_zsh_activation_instructions();
@@ -189,31 +532,24 @@ fn ensure_zshrc_exists(zshrc_path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn add_zsh_completion_config(content: &mut String, home: &str) -> Result<bool> {
-    let fpath_dir = format!("{}/.local/share/zsh/site-functions", home);
-
-    if content.contains(&fpath_dir) {
-        println!("\n✓ Completion path already configured in ~/.zshrc");
-        return Ok(false);
-    }
+fn zsh_fpath_dir(home: &str) -> String {
+    format!("{}/.local/share/zsh/site-functions", home)
+}
 
-    println!("\n✓ Adding completion path to ~/.zshrc");
+/// The ~/.zshrc lines that put gwt's completion directory on the fpath
+fn zsh_completion_config(zshrc: &str, fpath_dir: &str) -> String {
+    let mut config = format!("fpath=({} $fpath)\n", fpath_dir);
 
-    // Ensure proper newline before adding content
-    if !content.is_empty() && !content.ends_with('\n') {
-        content.push('\n');
+    // Add compinit unless the rest of ~/.zshrc already runs it
+    let mut rest = zshrc.to_string();
+    if let Some(range) = marked_block_range(zshrc) {
+        rest.replace_range(range, "");
     }
-
-    // Add fpath configuration
-    content.push_str("\n# Git worktree CLI completions\n");
-    content.push_str(&format!("fpath=({} $fpath)\n", fpath_dir));
-
-    // Add compinit if not present
-    if !content.contains("compinit") {
-        content.push_str("autoload -Uz compinit && compinit\n");
+    if !rest.contains("compinit") {
+        config.push_str("autoload -Uz compinit && compinit\n");
     }
 
-    Ok(true)
+    config
 }
 
 fn show_zsh_activation_instructions() {
@@ -230,6 +566,12 @@ pub fn check_completions_installed(shell: Shell) -> Result<bool> {
         return Ok(false);
     }
 
+    // The PowerShell profile usually exists anyway; look for the completer itself
+    if matches!(shell, Shell::PowerShell) {
+        let profile = fs::read_to_string(&install_path)?;
+        return Ok(has_marked_block(&profile) || profile.contains(POWERSHELL_COMPLETER_SIGNATURE));
+    }
+
     // For Zsh, also check if fpath is configured
     if matches!(shell, Shell::Zsh) {
         let home = env::var("HOME")?;
@@ -237,10 +579,110 @@ pub fn check_completions_installed(shell: Shell) -> Result<bool> {
 
         if zshrc_path.exists() {
             let content = fs::read_to_string(&zshrc_path)?;
-            let fpath_configured = content.contains(&format!("{}/.local/share/zsh/site-functions", home));
-            return Ok(fpath_configured);
+            return Ok(has_marked_block(&content) || content.contains(&zsh_fpath_dir(&home)));
         }
     }
 
     Ok(true)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zsh_branch_args_are_dynamic() {
+        // Fails if the cli.rs doc text for these arguments changes without updating ZSH_BRANCH_ARGS
+        let script = get_completion_content(Shell::Zsh);
+        assert!(script.starts_with("#compdef gwt\n"));
+        assert!(script.contains("_gwt_worktree_branches() {"));
+        assert_eq!(
+            script.matches(":_gwt_worktree_branches'").count(),
+            ZSH_BRANCH_ARGS.len()
+        );
+    }
+
+    #[test]
+    fn test_bash_and_fish_call_branch_helper() {
+        assert!(get_completion_content(Shell::Bash).contains("gwt __complete_branches"));
+        assert!(get_completion_content(Shell::Fish).contains("gwt __complete_branches"));
+    }
+
+    #[test]
+    fn test_powershell_script_can_be_appended_to_profile() {
+        let script = get_completion_content(Shell::PowerShell);
+        assert!(!script.contains("using namespace"));
+        assert!(script.starts_with(POWERSHELL_COMPLETER_SIGNATURE));
+        assert!(!script.contains("[CompletionResult]::"));
+        assert!(script.contains("[System.Management.Automation.CompletionResult]::new("));
+
+        let mut profile = String::from("Set-PSReadLineOption -EditMode Emacs");
+        assert!(upsert_marked_block(&mut profile, &script));
+        assert!(profile.starts_with("Set-PSReadLineOption -EditMode Emacs\n\n# >>> gwt completions >>>\n"));
+        assert!(!upsert_marked_block(&mut profile, &script));
+        assert_eq!(profile.matches(POWERSHELL_COMPLETER_SIGNATURE).count(), 1);
+    }
+
+    #[test]
+    fn test_install_twice_writes_one_block() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rc_path = temp_dir.path().join(".zshrc");
+        fs::write(&rc_path, "export EDITOR=vim\n").unwrap();
+
+        let config = zsh_completion_config(&fs::read_to_string(&rc_path).unwrap(), "/home/me/zsh");
+        assert!(write_marked_block(&rc_path, &config).unwrap());
+        assert!(has_marked_block(&fs::read_to_string(&rc_path).unwrap()));
+
+        // Reinstalling with a different body replaces the block rather than appending one
+        fs::write(&rc_path, fs::read_to_string(&rc_path).unwrap() + "alias g=git\n").unwrap();
+        let config = zsh_completion_config(&fs::read_to_string(&rc_path).unwrap(), "/home/me/other");
+        assert!(write_marked_block(&rc_path, &config).unwrap());
+        assert!(!write_marked_block(&rc_path, &config).unwrap());
+
+        let rc = fs::read_to_string(&rc_path).unwrap();
+        assert_eq!(rc.matches(BLOCK_START_MARKER).count(), 1);
+        assert_eq!(rc.matches(BLOCK_END_MARKER).count(), 1);
+        assert_eq!(rc.matches("autoload -Uz compinit").count(), 1);
+        assert!(rc.contains("fpath=(/home/me/other $fpath)"));
+        assert!(!rc.contains("/home/me/zsh"));
+        assert!(rc.starts_with("export EDITOR=vim\n\n"));
+        assert!(rc.ends_with("# <<< gwt completions <<<\nalias g=git\n"));
+    }
+
+    #[test]
+    fn test_uninstall_restores_rc_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rc_path = temp_dir.path().join(".zshrc");
+        let original = "export EDITOR=vim\n\nalias g=git\n";
+        fs::write(&rc_path, original).unwrap();
+
+        let config = zsh_completion_config(original, "/home/me/zsh");
+        assert!(write_marked_block(&rc_path, &config).unwrap());
+        assert_ne!(fs::read_to_string(&rc_path).unwrap(), original);
+
+        assert!(remove_marked_block_from(&rc_path).unwrap());
+        assert_eq!(fs::read_to_string(&rc_path).unwrap(), original);
+        assert!(!remove_marked_block_from(&rc_path).unwrap());
+
+        // A block the user moved into the middle of the file comes out cleanly too
+        let mut rc = String::from("a=1\n");
+        upsert_marked_block(&mut rc, "fpath=(x $fpath)\n");
+        rc.push_str("b=2\n");
+        assert!(remove_marked_block(&mut rc));
+        assert_eq!(rc, "a=1\nb=2\n");
+
+        assert!(!remove_marked_block_from(&temp_dir.path().join("missing")).unwrap());
+    }
+
+    #[test]
+    fn test_powershell_executable_names() {
+        assert_eq!(powershell_executable("pwsh\n"), Some("pwsh"));
+        assert_eq!(powershell_executable("/usr/local/bin/pwsh"), Some("pwsh"));
+        assert_eq!(
+            powershell_executable("C:\\Windows\\System32\\powershell.exe"),
+            Some("powershell")
+        );
+        assert_eq!(powershell_executable("PowerShell.EXE"), Some("powershell"));
+        assert_eq!(powershell_executable("bash"), None);
+    }
+}
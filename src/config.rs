@@ -1,17 +1,30 @@
 use chrono::{DateTime, Utc};
+use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Once;
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::cli::Provider;
+use crate::constants::DEFAULT_REMOTE;
 use crate::error::{Error, Result};
 use crate::git;
 
+/// Schema version written by this build; older files are upgraded by [`GitWorktreeConfig::migrate`]
+pub const CONFIG_VERSION: u32 = 1;
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GitWorktreeConfig {
+    /// Schema version (0 for files written before versioning was introduced)
+    #[serde(default)]
+    pub config_version: u32,
     pub repository_url: String,
     pub main_branch: String,
+    #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
     pub source_control: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -19,20 +32,148 @@ pub struct GitWorktreeConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub worktrees_path: Option<PathBuf>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree_layout: Option<WorktreeLayout>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub bitbucket_email: Option<String>,
+    /// Bitbucket Cloud API root for proxies/mirrors (defaults to https://api.bitbucket.org/2.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitbucket_api_base: Option<String>,
+    /// Bitbucket Data Center server URL, for repository URLs gwt can't parse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitbucket_base_url: Option<String>,
+    /// Bitbucket Data Center project key (overrides the one parsed from the URL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitbucket_project_key: Option<String>,
+    /// Bitbucket Data Center repository slug (overrides the one parsed from the URL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitbucket_repo_slug: Option<String>,
+    /// Gitea/Forgejo instance URL, when the API isn't served from the repository's host
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gitea_base_url: Option<String>,
+    /// Remote to fetch, track, and detect the provider from (defaults to `origin`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_remote: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hooks: Option<Hooks>,
+    /// Glob patterns of untracked files copied from the main worktree into new worktrees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copy_on_add: Option<Vec<String>>,
+    /// Kill a hook command that runs longer than this many seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hooks_timeout_secs: Option<u64>,
+    /// Abort the command when any hook fails (pre hooks always abort)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hooks_fail_fast: Option<bool>,
+    /// File of `KEY=VALUE` lines (relative to the project root) exported to hook commands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hook_env_file: Option<String>,
+    /// How long `gwt list` reuses cached PR results (0 disables the cache)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_cache_ttl_secs: Option<u64>,
+    /// Whether `gwt remove` deletes the worktree's branch (defaults to true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_branch_on_remove: Option<bool>,
+    /// Whether `gwt add` pushes new branches with `git push -u` (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push_on_add: Option<bool>,
+    /// Whether `gwt add` initializes submodules in new worktrees (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_submodules: Option<bool>,
+    /// Whether `gwt list` looks up pull requests at all (defaults to true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_pull_requests: Option<bool>,
+    /// Branch names or globs (`release/*`) never deleted or renamed; replaces the defaults
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected_branches: Option<Vec<String>>,
+    /// Keys this version doesn't recognize (typos or newer settings); kept on save
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// How branch names map to directories under the worktrees path
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorktreeLayout {
+    /// Single directory level, slashes replaced with dashes (feature/login -> feature-login)
+    Flat,
+    /// Directory structure mirrors the branch name (feature/login -> feature/login)
+    #[default]
+    Nested,
+}
+
+impl WorktreeLayout {
+    /// Directory name (relative to the worktrees path) for a branch
+    ///
+    /// In flat mode a branch containing `/` has it replaced with `-` and gets a hash of
+    /// the full branch name appended (`feature/a` -> `feature-a-8beb37a6`), so it can never
+    /// share a directory with `feature-a`, whichever of the two is added first.
+    fn dir_name(&self, branch_name: &str) -> String {
+        match self {
+            WorktreeLayout::Flat if branch_name.contains('/') => {
+                format!("{}-{:08x}", branch_name.replace('/', "-"), fnv1a(branch_name) >> 32)
+            }
+            WorktreeLayout::Flat | WorktreeLayout::Nested => branch_name.to_string(),
+        }
+    }
+
+    /// Resolve the worktree path for a branch, used by both `gwt add` and `gwt rename`
+    pub fn worktree_path(&self, worktrees_path: &Path, branch_name: &str) -> PathBuf {
+        worktrees_path.join(self.dir_name(branch_name))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Hooks {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_add: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub post_add: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pre_remove: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub post_remove: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// Hook types in the order they run, as named in the config
+pub const HOOK_TYPES: &[&str] = &["preAdd", "postAdd", "preRemove", "postRemove"];
+
+impl Hooks {
+    /// Empty lists for every hook type, as written by `gwt init`
+    pub fn empty() -> Self {
+        Self {
+            pre_add: Some(vec![]),
+            post_add: Some(vec![]),
+            pre_remove: Some(vec![]),
+            post_remove: Some(vec![]),
+            extra: BTreeMap::new(),
+        }
+    }
+
+    /// Commands configured for `hook_type` (one of [`HOOK_TYPES`])
+    pub fn get(&self, hook_type: &str) -> Option<&Vec<String>> {
+        match hook_type {
+            "preAdd" => self.pre_add.as_ref(),
+            "postAdd" => self.post_add.as_ref(),
+            "preRemove" => self.pre_remove.as_ref(),
+            "postRemove" => self.post_remove.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Mutable commands for `hook_type`, creating the list if it's missing
+    pub fn get_mut(&mut self, hook_type: &str) -> Option<&mut Vec<String>> {
+        let hooks = match hook_type {
+            "preAdd" => &mut self.pre_add,
+            "postAdd" => &mut self.post_add,
+            "preRemove" => &mut self.pre_remove,
+            "postRemove" => &mut self.post_remove,
+            _ => return None,
+        };
+        Some(hooks.get_or_insert_with(Vec::new))
+    }
 }
 
 impl GitWorktreeConfig {
@@ -48,24 +189,49 @@ impl GitWorktreeConfig {
             Provider::Github => "github".to_string(),
             Provider::BitbucketCloud => "bitbucket-cloud".to_string(),
             Provider::BitbucketDataCenter => "bitbucket-data-center".to_string(),
+            Provider::Gitea => "gitea".to_string(),
         };
 
         Self {
+            config_version: CONFIG_VERSION,
             repository_url,
             main_branch,
             created_at: Utc::now(),
             source_control,
             project_path,
             worktrees_path,
+            worktree_layout: None,
             bitbucket_email: None,
-            hooks: Some(Hooks {
-                post_add: Some(vec![]),
-                pre_remove: Some(vec![]),
-                post_remove: Some(vec![]),
-            }),
+            bitbucket_api_base: None,
+            bitbucket_base_url: None,
+            bitbucket_project_key: None,
+            bitbucket_repo_slug: None,
+            gitea_base_url: None,
+            default_remote: None,
+            hooks: Some(Hooks::empty()),
+            copy_on_add: None,
+            hooks_timeout_secs: None,
+            hooks_fail_fast: None,
+            hook_env_file: None,
+            pr_cache_ttl_secs: None,
+            delete_branch_on_remove: None,
+            push_on_add: None,
+            init_submodules: None,
+            show_pull_requests: None,
+            protected_branches: None,
+            extra: BTreeMap::new(),
         }
     }
 
+    /// Keys in the file that this version ignores, e.g. `mainBranche` or `hooks.postadd`
+    pub fn unknown_keys(&self) -> Vec<String> {
+        let hook_keys = self
+            .hooks
+            .iter()
+            .flat_map(|hooks| hooks.extra.keys().map(|key| format!("hooks.{}", key)));
+        self.extra.keys().cloned().chain(hook_keys).collect()
+    }
+
     /// Derive worktrees path from project path (repo-name -> repo-name-worktrees)
     pub fn derive_worktrees_path(project_path: &Path) -> PathBuf {
         let repo_name = project_path.file_name().and_then(|n| n.to_str()).unwrap_or("repo");
@@ -75,6 +241,49 @@ impl GitWorktreeConfig {
             .unwrap_or_else(|| project_path.join("worktrees"))
     }
 
+    /// Get the configured worktree layout (nested by default)
+    pub fn layout(&self) -> WorktreeLayout {
+        self.worktree_layout.unwrap_or_default()
+    }
+
+    /// Get the remote name used for fetching and tracking (`origin` by default)
+    pub fn remote(&self) -> &str {
+        self.default_remote.as_deref().unwrap_or(DEFAULT_REMOTE)
+    }
+
+    /// Whether `gwt remove` deletes the branch along with the worktree (true by default)
+    pub fn delete_branch_on_remove(&self) -> bool {
+        self.delete_branch_on_remove.unwrap_or(true)
+    }
+
+    /// Whether `gwt add` pushes a new branch and sets its upstream (false by default)
+    pub fn push_on_add(&self) -> bool {
+        self.push_on_add.unwrap_or(false)
+    }
+
+    /// Whether `gwt add` runs `git submodule update --init --recursive` in new worktrees (false by default)
+    pub fn init_submodules(&self) -> bool {
+        self.init_submodules.unwrap_or(false)
+    }
+
+    /// Whether `gwt list` fetches pull request info (true by default)
+    pub fn show_pull_requests(&self) -> bool {
+        self.show_pull_requests.unwrap_or(true)
+    }
+
+    /// Protected branch patterns (main, master, dev and develop by default), always
+    /// including `mainBranch`
+    pub fn protected_branches(&self) -> Vec<String> {
+        let mut branches = self
+            .protected_branches
+            .clone()
+            .unwrap_or_else(crate::core::utils::default_protected_branches);
+        if !branches.contains(&self.main_branch) {
+            branches.push(self.main_branch.clone());
+        }
+        branches
+    }
+
     /// Get worktrees path, deriving from project_path if not stored
     pub fn get_worktrees_path(&self) -> Option<PathBuf> {
         self.worktrees_path
@@ -82,10 +291,12 @@ impl GitWorktreeConfig {
             .or_else(|| self.project_path.as_ref().map(|p| Self::derive_worktrees_path(p)))
     }
 
+    /// Write the config atomically: a crash mid-write leaves the old file in place
     pub fn save(&self, path: &Path) -> Result<()> {
         let json_string = serde_json::to_string_pretty(self)?;
 
-        fs::write(path, json_string).map_err(|e| Error::config(format!("Failed to write config file: {}", e)))?;
+        write_atomic(path, |file| file.write_all(json_string.as_bytes()))
+            .map_err(|e| Error::config(format!("Failed to write config file: {}", e)))?;
 
         Ok(())
     }
@@ -93,44 +304,85 @@ impl GitWorktreeConfig {
     pub fn load(path: &Path) -> Result<Self> {
         let content =
             fs::read_to_string(path).map_err(|e| Error::config(format!("Failed to read config file: {}", e)))?;
+        Self::parse(&content)
+    }
 
-        let config: Self = json5::from_str(&content)?;
+    /// Load a config that is about to be rewritten with [`save`](Self::save)
+    ///
+    /// `save` writes plain JSON, so a file with comments is refused rather than
+    /// silently losing them.
+    pub fn load_for_update(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).map_err(|e| Error::config(format!("Failed to read config file: {}", e)))?;
+        if has_comments(&content) {
+            return Err(Error::config(format!(
+                "{} contains comments, which rewriting it would remove. Make the change with 'gwt config edit' instead.",
+                path.display()
+            )));
+        }
+        Self::parse(&content)
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let mut config: Self = json5::from_str(content)?;
+        config.migrate();
 
         Ok(config)
     }
 
+    /// Upgrade a config loaded from an older schema version in memory
+    ///
+    /// New fields must deserialize with a default (an `Option` or `#[serde(default)]`)
+    /// so old files keep loading; steps here fill in anything else a version
+    /// introduced. The file itself is rewritten on the next save (`gwt config set`).
+    /// Returns whether anything changed.
+    pub fn migrate(&mut self) -> bool {
+        if self.config_version >= CONFIG_VERSION {
+            return false;
+        }
+
+        // 0 -> 1: unversioned files may predate hooks; add the empty lists `gwt init` writes
+        if self.config_version < 1 && self.hooks.is_none() {
+            self.hooks = Some(Hooks::empty());
+        }
+
+        self.config_version = CONFIG_VERSION;
+        true
+    }
+
     /// Find configuration for the current project
     /// Priority: Local config first, then global config
     pub fn find_config() -> Result<Option<(PathBuf, Self)>> {
         let current_dir = std::env::current_dir()?;
 
-        // Step 1: Check for local config (walk up directory tree)
-        if let Some(result) = Self::find_local_config(&current_dir)? {
-            return Ok(Some(result));
-        }
-
+        // Step 1: Check for local config (walk up directory tree), then
         // Step 2: Try to find global config
-        if let Some(result) = Self::find_global_config(&current_dir)? {
-            return Ok(Some(result));
-        }
+        let found = match Self::find_local_config(&current_dir)? {
+            Some(result) => Some(result),
+            None => Self::find_global_config(&current_dir)?,
+        };
 
-        Ok(None)
+        if let Some((path, config)) = &found {
+            warn_unknown_keys(path, config);
+        }
+        Ok(found)
     }
 
     /// Find local config by walking up directory tree
     fn find_local_config(start_dir: &Path) -> Result<Option<(PathBuf, Self)>> {
         let mut current_dir = start_dir.to_path_buf();
+        let filename = config_filename();
 
         loop {
             // Check in current directory
-            let config_path = current_dir.join(CONFIG_FILENAME);
+            let config_path = current_dir.join(&filename);
             if config_path.exists() {
                 let config = Self::load(&config_path)?;
                 return Ok(Some((config_path, config)));
             }
 
             // Check in ./main/ subdirectory
-            let main_config_path = current_dir.join("main").join(CONFIG_FILENAME);
+            let main_config_path = current_dir.join("main").join(&filename);
             if main_config_path.exists() {
                 let config = Self::load(&main_config_path)?;
                 return Ok(Some((main_config_path, config)));
@@ -155,13 +407,18 @@ impl GitWorktreeConfig {
             return Ok(None);
         }
 
-        // Strategy 1: Try to match by repository URL
-        if let Some(repo_url) = git::get_remote_origin_url(start_dir) {
-            let filename = generate_config_filename(&repo_url);
-            let config_path = projects_dir.join(&filename);
+        // Strategy 1: Try to match by repository URL. The config is named after the URL of
+        // its defaultRemote, so check each remote and keep the config that names it.
+        for remote in git::list_remotes(start_dir) {
+            let Some(repo_url) = git::get_remote_url(start_dir, &remote) else {
+                continue;
+            };
+            let config_path = projects_dir.join(generate_config_filename(&repo_url));
             if config_path.exists() {
                 let config = Self::load(&config_path)?;
-                return Ok(Some((config_path, config)));
+                if config.remote() == remote {
+                    return Ok(Some((config_path, config)));
+                }
             }
         }
 
@@ -191,8 +448,17 @@ impl GitWorktreeConfig {
         Ok(None)
     }
 
-    /// Get the global config directory (~/.config/git-worktree-cli)
+    /// Get the global config directory.
+    ///
+    /// Resolved from `GWT_CONFIG_DIR`, then `$XDG_CONFIG_HOME/git-worktree-cli`,
+    /// falling back to `~/.config/git-worktree-cli`. Empty values are ignored.
     pub fn global_config_dir() -> Result<PathBuf> {
+        if let Some(dir) = non_empty_env(GWT_CONFIG_DIR_ENV) {
+            return Ok(PathBuf::from(dir));
+        }
+        if let Some(xdg) = non_empty_env("XDG_CONFIG_HOME") {
+            return Ok(PathBuf::from(xdg).join("git-worktree-cli"));
+        }
         dirs::home_dir()
             .ok_or_else(|| Error::config("Could not determine home directory"))
             .map(|home| home.join(".config").join("git-worktree-cli"))
@@ -204,6 +470,134 @@ impl GitWorktreeConfig {
     }
 }
 
+/// Print a dimmed warning (once per process) listing config keys that are ignored
+fn warn_unknown_keys(path: &Path, config: &GitWorktreeConfig) {
+    static WARNED: Once = Once::new();
+
+    let unknown = config.unknown_keys();
+    if unknown.is_empty() {
+        return;
+    }
+    WARNED.call_once(|| {
+        eprintln!(
+            "{}",
+            format!(
+                "Warning: ignoring unknown config key(s) in {}: {}",
+                path.display(),
+                unknown.join(", ")
+            )
+            .dimmed()
+        );
+    });
+}
+
+/// Replace `path` with whatever `write` produces, without ever exposing a partial file
+///
+/// The content goes to a temp file in the same directory, which is flushed to disk
+/// and renamed over the target. An existing file keeps its permissions, and a
+/// symlinked config is replaced at the link's target rather than the link itself.
+fn write_atomic<F>(path: &Path, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut fs::File) -> io::Result<()>,
+{
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let dir = match target.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let file_name = target
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "config path has no file name"))?;
+    let temp_path = dir.join(format!(".{}.{}.tmp", file_name.to_string_lossy(), std::process::id()));
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        write(&mut file)?;
+        file.sync_all()?;
+        if let Ok(metadata) = fs::metadata(&target) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        fs::rename(&temp_path, &target)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// How long `ConfigLock::acquire` waits for another `gwt` to finish
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Lock files older than this are left over from a crashed run and get taken over
+const STALE_LOCK_AGE: Duration = Duration::from_secs(60);
+
+/// Advisory lock held around a read-modify-write of a config file
+///
+/// Backed by a `<config>.lock` file created exclusively; dropping the guard removes it.
+/// Only `gwt` itself honors the lock, so it guards against concurrent `gwt` runs,
+/// not editors.
+pub struct ConfigLock {
+    path: PathBuf,
+}
+
+impl ConfigLock {
+    pub fn acquire(config_path: &Path) -> Result<Self> {
+        let mut lock_name = config_path.as_os_str().to_owned();
+        lock_name.push(".lock");
+        let path = PathBuf::from(lock_name);
+        let started = Instant::now();
+
+        loop {
+            match fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let _ = write!(file, "{}", std::process::id());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    if lock_is_stale(&path) {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if started.elapsed() >= LOCK_TIMEOUT {
+                        return Err(Error::config(format!(
+                            "Config is locked by another gwt process. If none is running, delete {}",
+                            path.display()
+                        )));
+                    }
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                Err(e) => {
+                    return Err(Error::config(format!(
+                        "Failed to create lock file {}: {}",
+                        path.display(),
+                        e
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ConfigLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn lock_is_stale(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > STALE_LOCK_AGE)
+}
+
+/// Read an environment variable, treating unset and empty values the same
+fn non_empty_env(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|v| !v.is_empty())
+}
+
 /// Generate a safe filename from a repository URL
 pub fn generate_config_filename(repo_url: &str) -> String {
     if let Some(id) = extract_repo_identifier(repo_url) {
@@ -214,6 +608,18 @@ pub fn generate_config_filename(repo_url: &str) -> String {
     }
 }
 
+/// Check whether two remote URLs refer to the same repository
+///
+/// SSH and HTTPS forms of the same repository match, as do URLs with and
+/// without a trailing `.git`.
+pub fn repository_urls_match(a: &str, b: &str) -> bool {
+    let normalize = |url: &str| {
+        let url = url.trim().trim_end_matches('/');
+        extract_repo_identifier(url).unwrap_or_else(|| url.trim_end_matches(".git").to_string())
+    };
+    normalize(a) == normalize(b)
+}
+
 fn extract_repo_identifier(url: &str) -> Option<String> {
     // GitHub SSH: git@github.com:owner/repo.git
     if let Some(rest) = url.strip_prefix("git@github.com:") {
@@ -279,6 +685,36 @@ fn sanitize_for_filename(s: &str) -> String {
         .to_lowercase()
 }
 
+/// Whether JSONC text has a `//` or `/* */` comment outside of strings
+fn has_comments(content: &str) -> bool {
+    let mut chars = content.chars().peekable();
+    let mut quote = None;
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) => {
+                if c == '\\' {
+                    chars.next();
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '/' if matches!(chars.peek(), Some('/' | '*')) => return true,
+                _ => {}
+            },
+        }
+    }
+    false
+}
+
+/// 64-bit FNV-1a, stable across Rust releases unlike `DefaultHasher`
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
 fn short_hash(s: &str) -> String {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
@@ -290,9 +726,21 @@ fn short_hash(s: &str) -> String {
 
 pub const CONFIG_FILENAME: &str = "git-worktree-config.jsonc";
 
+/// Environment variable that overrides the global config directory
+pub const GWT_CONFIG_DIR_ENV: &str = "GWT_CONFIG_DIR";
+
+/// Environment variable that overrides the local config filename
+pub const GWT_CONFIG_FILENAME_ENV: &str = "GWT_CONFIG_FILENAME";
+
+/// Name of the local config file: `GWT_CONFIG_FILENAME` if set, else `CONFIG_FILENAME`
+pub fn config_filename() -> String {
+    non_empty_env(GWT_CONFIG_FILENAME_ENV).unwrap_or_else(|| CONFIG_FILENAME.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
     use tempfile::tempdir;
 
     #[test]
@@ -312,6 +760,7 @@ mod tests {
         assert!(config.hooks.is_some());
 
         let hooks = config.hooks.unwrap();
+        assert!(hooks.pre_add.is_some());
         assert!(hooks.post_add.is_some());
         assert!(hooks.pre_remove.is_some());
         assert!(hooks.post_remove.is_some());
@@ -376,6 +825,64 @@ mod tests {
     }
 
     #[test]
+    fn test_config_load_migrates_legacy_file() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("legacy.jsonc");
+        fs::write(
+            &config_path,
+            r#"{
+  // written before configVersion and createdAt were required
+  "repositoryUrl": "git@github.com:test/repo.git",
+  "mainBranch": "main",
+  "sourceControl": "github",
+}"#,
+        )
+        .unwrap();
+
+        let config = GitWorktreeConfig::load(&config_path).unwrap();
+        assert_eq!(config.config_version, CONFIG_VERSION);
+        assert_eq!(config.repository_url, "git@github.com:test/repo.git");
+        assert_eq!(config.layout(), WorktreeLayout::Nested);
+        assert_eq!(config.remote(), DEFAULT_REMOTE);
+        assert!(config.delete_branch_on_remove());
+        assert!(config.hooks.as_ref().and_then(|h| h.post_add.as_ref()).is_some());
+
+        // Saving writes the current version, and a current config needs no migration
+        config.save(&config_path).unwrap();
+        let mut reloaded: GitWorktreeConfig = json5::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(reloaded.config_version, CONFIG_VERSION);
+        assert!(!reloaded.migrate());
+    }
+
+    #[test]
+    fn test_config_unknown_keys_are_kept_and_reported() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("typo.jsonc");
+        fs::write(
+            &config_path,
+            r#"{
+  "configVersion": 1,
+  "repositoryUrl": "git@github.com:test/repo.git",
+  "mainBranch": "main",
+  "mainBranche": "develop",
+  "sourceControl": "github",
+  "hooks": { "postadd": ["npm install"] },
+}"#,
+        )
+        .unwrap();
+
+        let config = GitWorktreeConfig::load(&config_path).unwrap();
+        assert_eq!(config.main_branch, "main");
+        assert_eq!(config.unknown_keys(), vec!["mainBranche", "hooks.postadd"]);
+
+        // Unknown keys survive a save so nothing the user wrote is lost
+        config.save(&config_path).unwrap();
+        let reloaded = GitWorktreeConfig::load(&config_path).unwrap();
+        assert_eq!(reloaded.unknown_keys(), vec!["mainBranche", "hooks.postadd"]);
+    }
+
+    #[test]
+    #[serial]
     fn test_config_find_local_in_current_dir() {
         let temp_dir = tempdir().unwrap();
 
@@ -387,7 +894,7 @@ mod tests {
             None,
             None,
         );
-        config.save(&temp_dir.path().join(CONFIG_FILENAME)).unwrap();
+        config.save(&temp_dir.path().join(config_filename())).unwrap();
 
         // Find local config should return the config
         let result = GitWorktreeConfig::find_local_config(temp_dir.path()).unwrap();
@@ -418,6 +925,131 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_repository_urls_match() {
+        assert!(repository_urls_match(
+            "git@github.com:owner/repo.git",
+            "https://github.com/owner/repo"
+        ));
+        assert!(repository_urls_match("/srv/git/repo.git", "/srv/git/repo"));
+        assert!(!repository_urls_match(
+            "git@github.com:owner/repo.git",
+            "git@github.com:other/repo.git"
+        ));
+    }
+
+    #[test]
+    fn test_has_comments() {
+        assert!(has_comments("{\n  // main branch\n  mainBranch: \"main\"\n}"));
+        assert!(has_comments("{ /* block */ }"));
+        assert!(!has_comments(
+            r#"{"repositoryUrl": "https://github.com/owner/repo.git"}"#
+        ));
+        assert!(!has_comments(r#"{"postAdd": ["echo '//' \"/*\""]}"#));
+    }
+
+    #[test]
+    fn test_load_for_update_refuses_comments() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("config.jsonc");
+        fs::write(
+            &path,
+            "{\n  // keep me\n  repositoryUrl: \"git@github.com:test/repo.git\",\n  mainBranch: \"main\",\n  createdAt: \"2025-01-01T00:00:00Z\",\n  sourceControl: \"github\"\n}",
+        )
+        .unwrap();
+
+        assert!(GitWorktreeConfig::load(&path).is_ok());
+        let err = GitWorktreeConfig::load_for_update(&path).unwrap_err();
+        assert!(err.to_string().contains("contains comments"));
+    }
+
+    #[test]
+    fn test_worktree_layout_dir_name() {
+        assert_eq!(WorktreeLayout::Flat.dir_name("feature/login"), "feature-login-4fff94af");
+        assert_eq!(WorktreeLayout::Nested.dir_name("feature/login"), "feature/login");
+        assert_eq!(WorktreeLayout::Flat.dir_name("main"), "main");
+        assert_eq!(WorktreeLayout::Flat.dir_name("feature-a"), "feature-a");
+    }
+
+    #[test]
+    fn test_worktree_layout_flat_collision() {
+        let worktrees_path = Path::new("/worktrees");
+        let slash = WorktreeLayout::Flat.worktree_path(worktrees_path, "feature/a");
+        let dash = WorktreeLayout::Flat.worktree_path(worktrees_path, "feature-a");
+        assert_ne!(slash, dash);
+
+        // The mapping doesn't depend on which directories exist, so creation order can't matter
+        for first in ["feature/a", "feature-a"] {
+            let temp_dir = tempdir().unwrap();
+            let first_path = WorktreeLayout::Flat.worktree_path(temp_dir.path(), first);
+            std::fs::create_dir_all(&first_path).unwrap();
+            assert_eq!(
+                WorktreeLayout::Flat.worktree_path(temp_dir.path(), "feature/a"),
+                temp_dir.path().join("feature-a-8beb37a6")
+            );
+            assert_eq!(
+                WorktreeLayout::Flat.worktree_path(temp_dir.path(), "feature-a"),
+                temp_dir.path().join("feature-a")
+            );
+        }
+
+        // Nested layout never collides since git forbids feature and feature/a together
+        let nested = WorktreeLayout::Nested.worktree_path(worktrees_path, "feature/a");
+        assert_eq!(nested, worktrees_path.join("feature/a"));
+    }
+
+    #[test]
+    fn test_worktree_layout_deserialize() {
+        let config: GitWorktreeConfig = json5::from_str(
+            r#"{
+                repositoryUrl: "git@github.com:test/repo.git",
+                mainBranch: "main",
+                createdAt: "2025-01-01T00:00:00Z",
+                sourceControl: "github",
+                worktreeLayout: "flat",
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.layout(), WorktreeLayout::Flat);
+    }
+
+    #[test]
+    fn test_protected_branches_override() {
+        let config = GitWorktreeConfig::new(
+            "git@github.com:test/repo.git".to_string(),
+            "main".to_string(),
+            Provider::Github,
+            None,
+            None,
+        );
+        assert_eq!(config.protected_branches(), ["main", "master", "dev", "develop"]);
+
+        let config: GitWorktreeConfig = json5::from_str(
+            r#"{
+                repositoryUrl: "git@github.com:test/repo.git",
+                mainBranch: "main",
+                createdAt: "2025-01-01T00:00:00Z",
+                sourceControl: "github",
+                protectedBranches: ["main", "release/*"],
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.protected_branches(), ["main", "release/*"]);
+        assert!(config.unknown_keys().is_empty());
+
+        let config: GitWorktreeConfig = json5::from_str(
+            r#"{
+                repositoryUrl: "git@github.com:test/repo.git",
+                mainBranch: "trunk",
+                createdAt: "2025-01-01T00:00:00Z",
+                sourceControl: "github",
+                protectedBranches: ["release/*"],
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.protected_branches(), ["release/*", "trunk"]);
+    }
+
     #[test]
     fn test_config_local_not_found() {
         let temp_dir = tempdir().unwrap();
@@ -426,4 +1058,183 @@ mod tests {
         let result = GitWorktreeConfig::find_local_config(temp_dir.path()).unwrap();
         assert!(result.is_none());
     }
+
+    /// Run `f` with the given env vars set (or removed), restoring them afterwards
+    fn with_env<F: FnOnce()>(vars: &[(&str, Option<&Path>)], f: F) {
+        let saved: Vec<(String, Option<std::ffi::OsString>)> =
+            vars.iter().map(|(k, _)| (k.to_string(), std::env::var_os(k))).collect();
+        for (key, value) in vars {
+            match value {
+                Some(v) => std::env::set_var(key, v),
+                None => std::env::remove_var(key),
+            }
+        }
+        f();
+        for (key, value) in saved {
+            match value {
+                Some(v) => std::env::set_var(&key, v),
+                None => std::env::remove_var(&key),
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_projects_config_dir_gwt_override() {
+        let temp_dir = tempdir().unwrap();
+        let xdg_dir = tempdir().unwrap();
+        with_env(
+            &[
+                (GWT_CONFIG_DIR_ENV, Some(temp_dir.path())),
+                ("XDG_CONFIG_HOME", Some(xdg_dir.path())),
+            ],
+            || {
+                let dir = GitWorktreeConfig::projects_config_dir().unwrap();
+                assert_eq!(dir, temp_dir.path().join("projects"));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_find_global_config_by_default_remote_url() {
+        let config_dir = tempdir().unwrap();
+        let repo_dir = tempdir().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(repo_dir.path())
+                .output()
+                .unwrap();
+        };
+        git(&["init"]);
+        git(&["remote", "add", "origin", "git@github.com:me/repo.git"]);
+        git(&["remote", "add", "upstream", "git@github.com:team/repo.git"]);
+
+        with_env(&[(GWT_CONFIG_DIR_ENV, Some(config_dir.path()))], || {
+            let projects_dir = GitWorktreeConfig::projects_config_dir().unwrap();
+            fs::create_dir_all(&projects_dir).unwrap();
+            let mut config = GitWorktreeConfig::new(
+                "git@github.com:team/repo.git".to_string(),
+                "main".to_string(),
+                Provider::Github,
+                None,
+                None,
+            );
+            config.default_remote = Some("upstream".to_string());
+            let config_path = projects_dir.join(generate_config_filename("git@github.com:team/repo.git"));
+            config.save(&config_path).unwrap();
+
+            let (found_path, found) = GitWorktreeConfig::find_global_config(repo_dir.path()).unwrap().unwrap();
+            assert_eq!(found_path, config_path);
+            assert_eq!(found.remote(), "upstream");
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_projects_config_dir_xdg_override() {
+        let xdg_dir = tempdir().unwrap();
+        with_env(
+            &[(GWT_CONFIG_DIR_ENV, None), ("XDG_CONFIG_HOME", Some(xdg_dir.path()))],
+            || {
+                let dir = GitWorktreeConfig::projects_config_dir().unwrap();
+                assert_eq!(dir, xdg_dir.path().join("git-worktree-cli").join("projects"));
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_projects_config_dir_default() {
+        with_env(&[(GWT_CONFIG_DIR_ENV, None), ("XDG_CONFIG_HOME", None)], || {
+            let dir = GitWorktreeConfig::projects_config_dir().unwrap();
+            let home = dirs::home_dir().unwrap();
+            assert_eq!(dir, home.join(".config").join("git-worktree-cli").join("projects"));
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_find_local_config_with_filename_override() {
+        let temp_dir = tempdir().unwrap();
+        let config = GitWorktreeConfig::new(
+            "git@github.com:test/repo.git".to_string(),
+            "main".to_string(),
+            Provider::Github,
+            None,
+            None,
+        );
+        config.save(&temp_dir.path().join("gwt.jsonc")).unwrap();
+        config.save(&temp_dir.path().join(CONFIG_FILENAME)).unwrap();
+
+        with_env(&[(GWT_CONFIG_FILENAME_ENV, Some(Path::new("gwt.jsonc")))], || {
+            assert_eq!(config_filename(), "gwt.jsonc");
+            let (path, _) = GitWorktreeConfig::find_local_config(temp_dir.path()).unwrap().unwrap();
+            assert_eq!(path, temp_dir.path().join("gwt.jsonc"));
+        });
+
+        with_env(&[(GWT_CONFIG_FILENAME_ENV, None)], || {
+            let (path, _) = GitWorktreeConfig::find_local_config(temp_dir.path()).unwrap().unwrap();
+            assert_eq!(path, temp_dir.path().join(CONFIG_FILENAME));
+        });
+    }
+
+    #[test]
+    fn test_failed_write_leaves_config_intact() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILENAME);
+        let original =
+            r#"{ "repositoryUrl": "git@github.com:test/repo.git", "mainBranch": "main", "sourceControl": "github" }"#;
+        fs::write(&path, original).unwrap();
+
+        // Simulate a crash halfway through writing the new content
+        let result = write_atomic(&path, |file| {
+            file.write_all(br#"{ "repositoryUrl": "git@github.com:te"#)?;
+            Err(io::Error::other("simulated failure"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+        assert!(GitWorktreeConfig::load(&path).is_ok());
+        let leftovers: Vec<_> = fs::read_dir(temp_dir.path()).unwrap().collect();
+        assert_eq!(leftovers.len(), 1, "temp file should be cleaned up");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILENAME);
+        let config = GitWorktreeConfig::new(
+            "git@github.com:test/repo.git".to_string(),
+            "main".to_string(),
+            Provider::Github,
+            None,
+            None,
+        );
+        config.save(&path).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        config.save(&path).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+    }
+
+    #[test]
+    fn test_config_lock_is_exclusive_and_released() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILENAME);
+        let lock_path = temp_dir.path().join(format!("{}.lock", CONFIG_FILENAME));
+
+        let lock = ConfigLock::acquire(&path).unwrap();
+        assert!(lock_path.exists());
+        drop(lock);
+        assert!(!lock_path.exists());
+
+        // A second acquire succeeds once the first guard is gone
+        let _lock = ConfigLock::acquire(&path).unwrap();
+    }
 }
@@ -1,6 +1,9 @@
 /// Main/master branch names that are protected from deletion
 pub const PROTECTED_BRANCHES: &[&str] = &["main", "master", "dev", "develop"];
 
+/// Remote used when the config doesn't set `defaultRemote`
+pub const DEFAULT_REMOTE: &str = "origin";
+
 /// Default main branch names to check
 #[allow(dead_code)]
 pub const DEFAULT_MAIN_BRANCHES: &[&str] = &["main", "master"];
@@ -28,7 +31,3 @@ pub const GIT_REFS_REMOTES_PREFIX: &str = "refs/remotes/";
 /// File extensions
 #[allow(dead_code)]
 pub const GIT_EXTENSION: &str = ".git";
-
-/// Configuration file
-#[allow(dead_code)]
-pub const CONFIG_FILE_NAME: &str = "git-worktree-config.jsonc";
@@ -5,13 +5,17 @@
 
 use crate::config::GitWorktreeConfig;
 use crate::error::{Error, Result};
+use crate::git::{self, Worktree};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Directory holding the shared repository in the bare layout (`gwt init --bare`)
+pub const BARE_DIR: &str = ".bare";
+
 /// Represents a git worktree project with its root and git directory
 #[derive(Debug, Clone)]
 pub struct Project {
-    /// The root directory containing git-worktree-config.jsonc
+    /// The root directory containing the local config (or recorded in the global one)
     pub root: PathBuf,
     /// The .git directory path
     pub git_dir: PathBuf,
@@ -36,9 +40,41 @@ impl Project {
     pub fn bare_repo_dir(&self) -> Result<PathBuf> {
         find_existing_worktree(&self.root)
     }
+
+    /// All worktrees of the project, as reported by `git worktree list`
+    pub fn worktrees(&self) -> Result<Vec<Worktree>> {
+        git::list_worktrees(Some(&self.git_dir))
+    }
+}
+
+/// Find the worktree for `name` among `worktrees`
+///
+/// A checked-out branch matches first; otherwise `name` may be the worktree's directory
+/// name or its path (absolute or relative to the current directory), for worktrees
+/// created outside the worktrees folder.
+pub fn find_worktree<'a>(worktrees: &'a [Worktree], name: &str) -> Option<&'a Worktree> {
+    worktrees
+        .iter()
+        .find(|wt| wt.branch.as_deref().is_some_and(|b| clean_branch_name(b) == name))
+        .or_else(|| find_worktree_by_path(worktrees, name))
 }
 
-/// Find the project root containing git-worktree-config.jsonc
+fn find_worktree_by_path<'a>(worktrees: &'a [Worktree], name: &str) -> Option<&'a Worktree> {
+    let target_path = std::env::current_dir()
+        .ok()
+        .map(|dir| dir.join(name))
+        .map(|path| path.canonicalize().unwrap_or(path));
+    let paths_match = |worktree_path: &Path, target: &Path| {
+        worktree_path == target || worktree_path.canonicalize().is_ok_and(|path| path == target)
+    };
+
+    worktrees.iter().find(|wt| {
+        let name_matches = wt.path.file_name().and_then(|n| n.to_str()) == Some(name);
+        name_matches || target_path.as_ref().is_some_and(|target| paths_match(&wt.path, target))
+    })
+}
+
+/// Find the project root for the current directory
 pub fn find_project_root() -> Result<PathBuf> {
     let current_dir = std::env::current_dir().map_err(Error::Io)?;
     find_project_root_from(&current_dir)
@@ -51,31 +87,68 @@ pub fn find_project_root() -> Result<PathBuf> {
 /// - Running from inside the main repo
 /// - Running from inside a worktree in the -worktrees folder
 pub fn find_project_root_from(start_path: &Path) -> Result<PathBuf> {
-    // Strategy 1: Check if we're in a git repository directly
-    if let Ok(Some(git_root)) = crate::git::get_git_root() {
+    let git_root = crate::git::get_git_root().ok().flatten();
+    log::debug!(
+        "finding project root from {} (git root: {:?})",
+        start_path.display(),
+        git_root
+    );
+
+    // Strategy 1: Prefer the project path stored by `gwt init` when we're inside that
+    // repository (covers worktrees created outside the -worktrees folder)
+    if let Some(ref git_root) = git_root {
+        if let Some(project_path) = stored_project_path_for(git_root) {
+            log::debug!("project root {} (configured projectPath)", project_path.display());
+            return Ok(project_path);
+        }
+    }
+
+    // Strategy 2: Check if we're in a git repository directly
+    if let Some(git_root) = git_root {
         // Check if this git root is inside a -worktrees folder (it's a worktree)
         if let Some(main_project) = find_main_project_from_worktree(&git_root) {
+            log::debug!("project root {} (from the -worktrees folder)", main_project.display());
             return Ok(main_project);
         }
         // Otherwise, this is the main project
+        log::debug!("project root {} (git root)", git_root.display());
         return Ok(git_root);
     }
 
-    // Strategy 2: Check if we're inside a -worktrees folder (but not in a git worktree)
+    // Strategy 3: Check if we're inside a -worktrees folder (but not in a git worktree)
     if let Some(main_project) = find_main_project_from_worktrees_path(start_path) {
+        log::debug!(
+            "project root {} (outside git, from the -worktrees folder)",
+            main_project.display()
+        );
         return Ok(main_project);
     }
 
-    // Strategy 3: Check global config
+    // Strategy 4: Check global config
     if let Ok(Some((_config_path, config))) = GitWorktreeConfig::find_config() {
         if let Some(project_path) = config.project_path {
+            log::debug!(
+                "project root {} (outside git, configured projectPath)",
+                project_path.display()
+            );
             return Ok(project_path);
         }
     }
 
-    Err(Error::Other(
-        "Not in a git-worktree-cli project. Run 'gwt init' inside a git repository.".to_string(),
-    ))
+    log::debug!("no project root found from {}", start_path.display());
+
+    Err(Error::ProjectRootNotFound)
+}
+
+/// The configured project path, if it exists and shares a repository with `git_root`
+fn stored_project_path_for(git_root: &Path) -> Option<PathBuf> {
+    let (_config_path, config) = GitWorktreeConfig::find_config().ok().flatten()?;
+    let project_path = config.project_path?;
+    if !project_path.exists() {
+        return None;
+    }
+    let common_dir = crate::git::get_git_common_dir(git_root)?;
+    (crate::git::get_git_common_dir(&project_path)? == common_dir).then_some(project_path)
 }
 
 /// Check if a path is inside a -worktrees folder and return the main project path
@@ -141,6 +214,12 @@ fn find_main_project_from_worktrees_path(start_path: &Path) -> Option<PathBuf> {
     None
 }
 
+/// Whether the project uses the bare layout: a `.bare` repository with a `.git` file
+/// pointing at it, and every branch (the main one included) as a linked worktree
+pub fn is_bare_layout(project_root: &Path) -> bool {
+    project_root.join(BARE_DIR).is_dir() && project_root.join(".git").is_file()
+}
+
 /// Find the .git directory within a project
 pub fn find_git_directory() -> Result<PathBuf> {
     let project_root = find_project_root()?;
@@ -148,27 +227,53 @@ pub fn find_git_directory() -> Result<PathBuf> {
 }
 
 /// Find the .git directory starting from a specific path
+///
+/// When the project root holds several checkouts, the one named after the configured
+/// `mainBranch` wins, then a main repository (`.git` directory), then the first
+/// remaining one alphabetically, so the choice doesn't depend on directory order.
 pub fn find_git_directory_from(project_root: &Path) -> Result<PathBuf> {
+    let main_branch = GitWorktreeConfig::find_config()
+        .ok()
+        .flatten()
+        .map(|(_, config)| config.main_branch);
+    find_git_directory_in(project_root, main_branch.as_deref())
+}
+
+fn find_git_directory_in(project_root: &Path, main_branch: Option<&str>) -> Result<PathBuf> {
     // First check if the project root itself has a .git directory
-    // This handles the case where config is inside main/ directory
+    // This handles the case where config is inside main/ directory, and the bare
+    // layout, where git commands run against .bare through the root's .git file
     if project_root.join(".git").exists() {
+        log::debug!("git directory {} (project root)", project_root.display());
         return Ok(project_root.to_path_buf());
     }
 
-    let entries = fs::read_dir(project_root).map_err(Error::Io)?;
+    let candidates = git_subdirectories(project_root, main_branch)?;
+    log::debug!(
+        "git directory candidates in {} (main branch {:?}): {:?}",
+        project_root.display(),
+        main_branch,
+        candidates
+    );
+    candidates.into_iter().next().ok_or(Error::GitDirectoryNotFound)
+}
 
-    for entry in entries {
+/// Subdirectories of `project_root` containing `.git`, in preference order
+fn git_subdirectories(project_root: &Path, main_branch: Option<&str>) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(project_root).map_err(Error::Io)? {
         let entry = entry.map_err(Error::Io)?;
-        if entry.file_type().map_err(Error::Io)?.is_dir() {
-            let dir_path = entry.path();
-            if dir_path.join(".git").exists() {
-                // This is a git directory (worktree or regular repository)
-                return Ok(dir_path);
-            }
+        if entry.file_type().map_err(Error::Io)?.is_dir() && entry.path().join(".git").exists() {
+            dirs.push(entry.path());
         }
     }
 
-    Err(Error::GitDirectoryNotFound)
+    dirs.sort_by_key(|dir| {
+        let is_main_branch = main_branch.is_some() && dir.file_name().and_then(|n| n.to_str()) == main_branch;
+        let is_main_repo = dir.join(".git").is_dir();
+        (!is_main_branch, !is_main_repo, dir.clone())
+    });
+    Ok(dirs)
 }
 
 /// Find an existing git directory (worktree or main repository)
@@ -176,12 +281,28 @@ pub fn find_git_directory_from(project_root: &Path) -> Result<PathBuf> {
 /// This function looks for any directory with a .git file or directory,
 /// prioritizing worktrees (where .git is a file) over main repositories.
 pub fn find_existing_worktree(project_root: &Path) -> Result<PathBuf> {
+    // In the bare layout the root's .git file points at .bare rather than a checkout;
+    // use a linked worktree, preferring the main branch's
+    if is_bare_layout(project_root) {
+        let main_branch = GitWorktreeConfig::find_config()
+            .ok()
+            .flatten()
+            .map(|(_, config)| config.main_branch);
+        let worktree = git_subdirectories(project_root, main_branch.as_deref())?
+            .into_iter()
+            .find(|dir| !is_orphaned_worktree(dir))
+            .unwrap_or_else(|| project_root.to_path_buf());
+        log::debug!("existing worktree {} (bare layout)", worktree.display());
+        return Ok(worktree);
+    }
+
     // First check if the project root itself has a .git directory
     // This handles the case where config is inside main/ directory
     let root_git_path = project_root.join(".git");
     if root_git_path.exists() {
         if root_git_path.is_file() {
             // Project root is a worktree
+            log::debug!("existing worktree {} (project root)", project_root.display());
             return Ok(project_root.to_path_buf());
         } else if root_git_path.is_dir() {
             // Project root is a main repository - save as fallback
@@ -189,8 +310,6 @@ pub fn find_existing_worktree(project_root: &Path) -> Result<PathBuf> {
         }
     }
 
-    let entries = fs::read_dir(project_root).map_err(Error::Io)?;
-
     let mut main_repo: Option<PathBuf> = None;
 
     // If project root has .git directory, use it as fallback
@@ -198,31 +317,25 @@ pub fn find_existing_worktree(project_root: &Path) -> Result<PathBuf> {
         main_repo = Some(project_root.to_path_buf());
     }
 
-    for entry in entries {
-        let entry = entry.map_err(Error::Io)?;
-        if entry.file_type().map_err(Error::Io)?.is_dir() {
-            let dir_path = entry.path();
-            let git_path = dir_path.join(".git");
-
-            if git_path.exists() {
-                if git_path.is_file() {
-                    // This is a worktree - prefer these over main repos
-                    return Ok(dir_path);
-                } else if git_path.is_dir() {
-                    // This is a main repository - save as fallback
-                    main_repo = Some(dir_path);
-                }
-            }
+    for dir_path in git_subdirectories(project_root, None)? {
+        let git_path = dir_path.join(".git");
+        if git_path.is_file() {
+            // This is a worktree - prefer these over main repos
+            log::debug!("existing worktree {}", dir_path.display());
+            return Ok(dir_path);
+        } else if git_path.is_dir() && main_repo.is_none() {
+            // This is a main repository - save as fallback
+            main_repo = Some(dir_path);
         }
     }
 
     // If no worktree found, use main repository if available
-    main_repo.ok_or_else(|| {
-        Error::Other(format!(
-            "No existing git directory found in project at {}. Have you run 'gwt init' yet?",
-            project_root.display()
-        ))
-    })
+    log::debug!(
+        "no linked worktree in {}, using main repository {:?}",
+        project_root.display(),
+        main_repo
+    );
+    main_repo.ok_or(Error::GitDirectoryNotFound)
 }
 
 /// Check if a path is an orphaned worktree
@@ -260,33 +373,147 @@ pub fn is_orphaned_worktree(path: &Path) -> bool {
 pub fn find_valid_git_directory(project_root: &Path) -> Result<PathBuf> {
     // Check if project root itself has valid .git directory
     let root_git_path = project_root.join(".git");
-    if root_git_path.is_dir() {
+    if root_git_path.is_dir() || is_bare_layout(project_root) {
         return Ok(project_root.to_path_buf());
     }
 
-    // Search subdirectories for valid git directories
-    let entries = fs::read_dir(project_root).map_err(Error::Io)?;
+    // Search subdirectories for valid git directories (main repositories sort first)
+    for dir_path in git_subdirectories(project_root, None)? {
+        let git_path = dir_path.join(".git");
 
-    for entry in entries {
-        let entry = entry.map_err(Error::Io)?;
-        if entry.file_type().map_err(Error::Io)?.is_dir() {
-            let dir_path = entry.path();
-            let git_path = dir_path.join(".git");
-
-            if git_path.is_dir() {
-                // Main repository - always valid
-                return Ok(dir_path);
-            } else if git_path.is_file() && !is_orphaned_worktree(&dir_path) {
-                // Valid worktree (not orphaned)
-                return Ok(dir_path);
-            }
+        if git_path.is_dir() {
+            // Main repository - always valid
+            return Ok(dir_path);
+        } else if git_path.is_file() && !is_orphaned_worktree(&dir_path) {
+            // Valid worktree (not orphaned)
+            return Ok(dir_path);
         }
     }
 
-    Err(Error::Other("No valid git directory found in project".to_string()))
+    Err(Error::GitDirectoryNotFound)
 }
 
 /// Clean a branch name by removing refs/heads/ prefix
 pub fn clean_branch_name(branch: &str) -> &str {
     branch.trim().strip_prefix("refs/heads/").unwrap_or(branch.trim())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_git_directory_prefers_main_branch_dir() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        for name in ["alpha", "main", "zeta"] {
+            fs::create_dir_all(root.join(name).join(".git")).unwrap();
+        }
+
+        assert_eq!(find_git_directory_in(root, Some("main")).unwrap(), root.join("main"));
+        assert_eq!(find_git_directory_in(root, Some("zeta")).unwrap(), root.join("zeta"));
+        // Without a configured main branch the choice is alphabetical
+        assert_eq!(find_git_directory_in(root, None).unwrap(), root.join("alpha"));
+    }
+
+    #[test]
+    fn test_bare_layout() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join(BARE_DIR)).unwrap();
+        fs::write(root.join(".git"), "gitdir: ./.bare").unwrap();
+        for name in ["feature", "main"] {
+            fs::create_dir_all(root.join(name)).unwrap();
+            fs::write(
+                root.join(name).join(".git"),
+                format!("gitdir: {}", root.join(BARE_DIR).display()),
+            )
+            .unwrap();
+        }
+
+        assert!(is_bare_layout(root));
+        assert!(!is_bare_layout(&root.join("main")));
+        // Git commands run from the root; file-based operations need a checkout
+        assert_eq!(find_git_directory_in(root, Some("main")).unwrap(), root);
+        assert_eq!(find_valid_git_directory(root).unwrap(), root);
+        assert!(find_existing_worktree(root).unwrap().starts_with(root));
+        assert_ne!(find_existing_worktree(root).unwrap(), root);
+    }
+
+    #[test]
+    fn test_find_git_directory_prefers_main_repository_over_worktrees() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a-worktree")).unwrap();
+        fs::write(root.join("a-worktree").join(".git"), "gitdir: /elsewhere").unwrap();
+        fs::create_dir_all(root.join("repo").join(".git")).unwrap();
+
+        assert_eq!(find_git_directory_in(root, None).unwrap(), root.join("repo"));
+    }
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    /// A project at `<tmp>/repo/main` with a `feature/login` worktree in `<tmp>/repo-worktrees/login`
+    fn fixture_project(temp_dir: &Path) -> Project {
+        let root = temp_dir.join("repo");
+        let main = root.join("main");
+        fs::create_dir_all(&main).unwrap();
+        run_git(&main, &["init", "-q", "-b", "main"]);
+        run_git(&main, &["commit", "-q", "--allow-empty", "-m", "initial"]);
+        let worktree = temp_dir.join("repo-worktrees").join("login");
+        run_git(
+            &main,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "feature/login",
+                worktree.to_str().unwrap(),
+            ],
+        );
+        Project { root, git_dir: main }
+    }
+
+    #[test]
+    fn test_project_worktrees() {
+        let temp_dir = tempdir().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+        let project = fixture_project(&temp_path);
+
+        let worktrees = project.worktrees().unwrap();
+        let branches: Vec<_> = worktrees
+            .iter()
+            .map(|wt| wt.branch.as_deref().map(clean_branch_name))
+            .collect();
+        assert_eq!(branches, vec![Some("main"), Some("feature/login")]);
+    }
+
+    #[test]
+    fn test_find_worktree_in_project() {
+        let temp_dir = tempdir().unwrap();
+        let temp_path = temp_dir.path().canonicalize().unwrap();
+        let project = fixture_project(&temp_path);
+        let worktrees = project.worktrees().unwrap();
+        let login_path = temp_path.join("repo-worktrees").join("login");
+
+        let by_branch = find_worktree(&worktrees, "feature/login").unwrap();
+        assert_eq!(by_branch.path.canonicalize().unwrap(), login_path);
+        // The directory name and the full path resolve to the same worktree
+        let by_name = find_worktree(&worktrees, "login").unwrap();
+        assert_eq!(by_name.path, by_branch.path);
+        let by_path = find_worktree(&worktrees, login_path.to_str().unwrap()).unwrap();
+        assert_eq!(by_path.path, by_branch.path);
+        assert!(find_worktree(&worktrees, "feature/missing").is_none());
+    }
+}
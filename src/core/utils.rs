@@ -4,18 +4,49 @@
 
 use std::path::Path;
 
+use crate::config::GitWorktreeConfig;
+use crate::constants::PROTECTED_BRANCHES;
+use crate::error::Result;
+
 /// Check if a path looks like a git SSH URL
+///
+/// Accepts the scp-like `user@host:path` form and explicit `ssh://` URLs. Other
+/// schemes (`https://`) and Windows drive paths (`C:\repo`) are not SSH URLs.
 pub fn is_git_ssh_url(url: &str) -> bool {
-    url.starts_with("git@") || url.contains(":")
+    if url.starts_with("ssh://") {
+        return true;
+    }
+    if url.contains("://") {
+        return false;
+    }
+    match url.split_once(':') {
+        Some((user_host, path)) => match user_host.split_once('@') {
+            Some((user, host)) => {
+                !user.is_empty() && !host.is_empty() && !path.is_empty() && !user_host.contains(['/', '\\'])
+            }
+            None => false,
+        },
+        None => false,
+    }
 }
 
 /// Convert SSH URL to HTTPS URL for cloning
 pub fn ssh_to_https_url(url: &str) -> String {
-    if url.starts_with("git@") {
-        // Convert git@github.com:user/repo.git to https://github.com/user/repo.git
-        url.replace(":", "/").replace("git@", "https://")
+    if !is_git_ssh_url(url) {
+        return url.to_string();
+    }
+
+    if let Some(rest) = url.strip_prefix("ssh://") {
+        // Convert ssh://git@host[:port]/user/repo.git to https://host/user/repo.git
+        let rest = rest.split_once('@').map(|(_, r)| r).unwrap_or(rest);
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let host = host.split(':').next().unwrap_or(host);
+        format!("https://{}/{}", host, path)
     } else {
-        url.to_string()
+        // Convert git@github.com:user/repo.git to https://github.com/user/repo.git
+        let (user_host, path) = url.split_once(':').unwrap_or((url, ""));
+        let host = user_host.split_once('@').map(|(_, h)| h).unwrap_or(user_host);
+        format!("https://{}/{}", host, path)
     }
 }
 
@@ -29,7 +60,88 @@ pub fn get_repo_name_from_url(url: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
-/// Check if a branch name is a main branch (shouldn't be deleted)
-pub fn is_main_branch(branch_name: &str) -> bool {
-    matches!(branch_name, "main" | "master" | "develop" | "dev")
+/// The built-in protected branches, used when the config doesn't set `protectedBranches`
+pub fn default_protected_branches() -> Vec<String> {
+    PROTECTED_BRANCHES.iter().map(|b| b.to_string()).collect()
+}
+
+/// Branch patterns protected from deletion and renaming: the config's `protectedBranches`
+/// plus its `mainBranch`, or the defaults when there is no config
+///
+/// A config that fails to load is an error rather than a silent fallback to the defaults,
+/// which might not cover the project's main branch.
+pub fn protected_branches() -> Result<Vec<String>> {
+    Ok(GitWorktreeConfig::find_config()?
+        .map(|(_, config)| config.protected_branches())
+        .unwrap_or_else(default_protected_branches))
+}
+
+/// Check if a branch is protected (shouldn't be deleted or renamed)
+pub fn is_protected_branch(branch_name: &str) -> Result<bool> {
+    Ok(matches_branch_patterns(branch_name, &protected_branches()?))
+}
+
+/// Whether `branch_name` matches one of `patterns`, by name or as a glob like `release/*`
+///
+/// `*` stays within one path segment (`release/*` doesn't match `release/1.x/fix`);
+/// `**` spans segments.
+pub fn matches_branch_patterns(branch_name: &str, patterns: &[String]) -> bool {
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+    patterns.iter().any(|pattern| {
+        pattern == branch_name
+            || glob::Pattern::new(pattern).is_ok_and(|pattern| pattern.matches_with(branch_name, options))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_git_ssh_url() {
+        assert!(is_git_ssh_url("git@github.com:o/r.git"));
+        assert!(is_git_ssh_url("ssh://git@host/o/r.git"));
+        assert!(!is_git_ssh_url("https://github.com/o/r.git"));
+        assert!(!is_git_ssh_url("C:\\repo"));
+        assert!(!is_git_ssh_url("C:/repo"));
+        assert!(!is_git_ssh_url("/home/user/repo"));
+    }
+
+    #[test]
+    fn test_ssh_to_https_url() {
+        assert_eq!(ssh_to_https_url("git@github.com:o/r.git"), "https://github.com/o/r.git");
+        assert_eq!(ssh_to_https_url("ssh://git@host:7999/o/r.git"), "https://host/o/r.git");
+        assert_eq!(
+            ssh_to_https_url("https://github.com/o/r.git"),
+            "https://github.com/o/r.git"
+        );
+        assert_eq!(ssh_to_https_url("C:\\repo"), "C:\\repo");
+    }
+
+    #[test]
+    fn test_matches_branch_patterns() {
+        let patterns: Vec<String> = ["main", "release/*", "hotfix/**"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert!(matches_branch_patterns("main", &patterns));
+        assert!(matches_branch_patterns("release/1.0", &patterns));
+        assert!(matches_branch_patterns("hotfix/1.x/urgent", &patterns));
+        assert!(!matches_branch_patterns("release/1.x/fix", &patterns));
+        assert!(!matches_branch_patterns("release", &patterns));
+        assert!(!matches_branch_patterns("feature/main", &patterns));
+        assert!(!matches_branch_patterns("master", &patterns));
+    }
+
+    #[test]
+    fn test_default_protected_branches() {
+        let defaults = default_protected_branches();
+        for branch in ["main", "master", "dev", "develop"] {
+            assert!(matches_branch_patterns(branch, &defaults), "{}", branch);
+        }
+        assert!(!matches_branch_patterns("release/1.0", &defaults));
+    }
 }
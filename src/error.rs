@@ -24,12 +24,12 @@ pub enum Error {
     #[error("API provider error: {0}")]
     Provider(String),
 
-    /// Project root or git directory not found
-    #[error("Project root not found")]
+    /// Project root not found
+    #[error("Not in a git-worktree-cli project. Run 'gwt init' inside a git repository.")]
     ProjectRootNotFound,
 
     /// Git directory not found
-    #[error("Git directory not found in project")]
+    #[error("No valid git directory found in project. Have you run 'gwt init' yet?")]
     GitDirectoryNotFound,
 
     /// Branch operation errors
@@ -105,6 +105,34 @@ impl Error {
     pub fn network<S: Into<String>>(msg: S) -> Self {
         Error::Network(msg.into())
     }
+
+    /// Process exit code for this error, stable so scripts can branch on it
+    ///
+    /// | Code | Error                                          |
+    /// |------|------------------------------------------------|
+    /// | 1    | Other, IO, JSON and regex errors               |
+    /// | 2    | Invalid arguments (reported by clap)           |
+    /// | 3    | Project root or git directory not found        |
+    /// | 4    | Authentication                                 |
+    /// | 5    | Network                                        |
+    /// | 6    | Git command failed                             |
+    /// | 7    | Configuration                                  |
+    /// | 8    | API provider                                   |
+    /// | 9    | Branch operation                               |
+    /// | 10   | Hook execution                                 |
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Io(_) | Error::Json(_) | Error::Regex(_) | Error::Other(_) => 1,
+            Error::ProjectRootNotFound | Error::GitDirectoryNotFound => 3,
+            Error::Auth(_) => 4,
+            Error::Network(_) => 5,
+            Error::Git(_) => 6,
+            Error::Config(_) => 7,
+            Error::Provider(_) => 8,
+            Error::Branch(_) => 9,
+            Error::Hook(_) => 10,
+        }
+    }
 }
 
 // Helper implementations for common conversions
@@ -143,3 +171,24 @@ impl From<std::string::FromUtf8Error> for Error {
         Error::Other(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(Error::msg("x").exit_code(), 1);
+        assert_eq!(Error::Io(std::io::Error::other("x")).exit_code(), 1);
+        assert_eq!(Error::Json("x".into()).exit_code(), 1);
+        assert_eq!(Error::ProjectRootNotFound.exit_code(), 3);
+        assert_eq!(Error::GitDirectoryNotFound.exit_code(), 3);
+        assert_eq!(Error::auth("x").exit_code(), 4);
+        assert_eq!(Error::network("x").exit_code(), 5);
+        assert_eq!(Error::git("x").exit_code(), 6);
+        assert_eq!(Error::config("x").exit_code(), 7);
+        assert_eq!(Error::provider("x").exit_code(), 8);
+        assert_eq!(Error::branch("x").exit_code(), 9);
+        assert_eq!(Error::hook("x").exit_code(), 10);
+    }
+}
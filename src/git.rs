@@ -1,13 +1,29 @@
 use colored::Colorize;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 use crate::error::{Error, Result};
+use crate::output;
+
+/// Echo the git invocation to stderr in `--verbose` mode (and to the debug log)
+fn log_command(args: &[&str], cwd: Option<&Path>) {
+    log::debug!(
+        "git {:?} (cwd: {})",
+        args,
+        cwd.map_or_else(|| ".".into(), |dir| dir.display().to_string())
+    );
+    if output::is_verbose() {
+        let location = cwd.map(|dir| format!(" (in {})", dir.display())).unwrap_or_default();
+        eprintln!("{}", format!("$ git {}{}", args.join(" "), location).dimmed());
+    }
+}
 
 /// Execute a git command with real-time output streaming
 pub fn execute_streaming(args: &[&str], cwd: Option<&Path>) -> Result<()> {
+    log_command(args, cwd);
     let mut cmd = Command::new("git");
-    cmd.args(args).stdout(Stdio::inherit()).stderr(Stdio::inherit());
+    cmd.args(args).stdout(output::child_stdout()).stderr(Stdio::inherit());
 
     if let Some(dir) = cwd {
         cmd.current_dir(dir);
@@ -18,6 +34,7 @@ pub fn execute_streaming(args: &[&str], cwd: Option<&Path>) -> Result<()> {
         .map_err(|e| Error::git(format!("Failed to execute git command: {}", e)))?;
 
     if !status.success() {
+        log::debug!("git {:?} exited with {:?}", args, status.code());
         return Err(Error::git(format!(
             "Git command failed with exit code: {:?}",
             status.code()
@@ -27,8 +44,53 @@ pub fn execute_streaming(args: &[&str], cwd: Option<&Path>) -> Result<()> {
     Ok(())
 }
 
+/// What `git worktree add` should create; see [`add_worktree`]
+#[derive(Debug, Clone, Default)]
+pub struct WorktreeAddOptions {
+    /// Directory of the new worktree
+    pub path: PathBuf,
+    /// Create this branch (`-b`) instead of checking out an existing one
+    pub new_branch: Option<String>,
+    /// Branch to check out, or the ref a new branch (or detached HEAD) starts at
+    pub start_point: Option<String>,
+    /// `--track` (`Some(true)`) or `--no-track` (`Some(false)`); `None` leaves git's default
+    pub track: Option<bool>,
+    /// Check out `start_point` as a detached HEAD
+    pub detach: bool,
+}
+
+/// The `git worktree add` arguments for `options`, without the leading `git`
+pub fn worktree_add_args(options: &WorktreeAddOptions) -> Vec<String> {
+    let mut args = vec!["worktree".to_string(), "add".to_string()];
+    if options.detach {
+        args.push("--detach".to_string());
+    }
+    match options.track {
+        Some(true) => args.push("--track".to_string()),
+        Some(false) => args.push("--no-track".to_string()),
+        None => {}
+    }
+    args.push(options.path.to_string_lossy().into_owned());
+    if let Some(branch) = &options.new_branch {
+        args.push("-b".to_string());
+        args.push(branch.clone());
+    }
+    if let Some(start_point) = &options.start_point {
+        args.push(start_point.clone());
+    }
+    args
+}
+
+/// Create a worktree with `git worktree add`, streaming git's output
+pub fn add_worktree(git_dir: &Path, options: &WorktreeAddOptions) -> Result<()> {
+    let args = worktree_add_args(options);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    execute_streaming(&args, Some(git_dir))
+}
+
 /// Execute a git command and capture output
 pub fn execute_capture(args: &[&str], cwd: Option<&Path>) -> Result<String> {
+    log_command(args, cwd);
     let mut cmd = Command::new("git");
     cmd.args(args);
 
@@ -42,16 +104,121 @@ pub fn execute_capture(args: &[&str], cwd: Option<&Path>) -> Result<String> {
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
+        log::debug!(
+            "git {:?} exited with {:?}: {}",
+            args,
+            output.status.code(),
+            stderr.trim()
+        );
         return Err(Error::git(format!("Git command failed: {}", stderr)));
     }
 
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
-/// Clone a repository with streaming output
-pub fn clone(repo_url: &str, target_dir: &str) -> Result<()> {
-    println!("{}", format!("Cloning {}...", repo_url).cyan());
-    execute_streaming(&["clone", repo_url, target_dir], None)
+/// Execute a git command and capture stdout and stderr combined
+///
+/// Some git commands (e.g. `git worktree prune -v`) report on stderr only.
+pub fn execute_capture_combined(args: &[&str], cwd: Option<&Path>) -> Result<String> {
+    log_command(args, cwd);
+    let mut cmd = Command::new("git");
+    cmd.args(args);
+
+    if let Some(dir) = cwd {
+        cmd.current_dir(dir);
+    }
+
+    let output = cmd
+        .output()
+        .map_err(|e| Error::git(format!("Failed to execute git command: {}", e)))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        log::debug!(
+            "git {:?} exited with {:?}: {}",
+            args,
+            output.status.code(),
+            stderr.trim()
+        );
+        return Err(Error::git(format!("Git command failed: {}", stderr)));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(format!("{}{}", stdout, stderr).trim().to_string())
+}
+
+/// Clone a repository with streaming output, naming its remote `remote`
+///
+/// `branch` is checked out instead of the remote's default branch when given.
+pub fn clone(repo_url: &str, target_dir: &Path, remote: &str, branch: Option<&str>) -> Result<()> {
+    crate::status!("{}", format!("Cloning {}...", repo_url).cyan());
+    let mut args = vec!["clone", "--origin", remote];
+    if let Some(branch) = branch {
+        args.extend(["--branch", branch]);
+    }
+    args.extend([repo_url, target_dir.to_str().unwrap()]);
+    execute_streaming(&args, None)
+}
+
+/// Whether `remote` (a remote name or URL) has the branch, asking the remote itself
+/// (`git ls-remote --heads`) rather than trusting remote-tracking refs
+pub fn remote_has_branch(cwd: Option<&Path>, remote: &str, branch_name: &str) -> Result<bool> {
+    let full_ref = format!("refs/heads/{}", branch_name);
+    let output = execute_capture(&["ls-remote", "--heads", remote, &full_ref], cwd)?;
+    Ok(ls_remote_has_ref(&output, &full_ref))
+}
+
+/// `ls-remote` patterns match any ref ending in them, so look for the exact ref
+fn ls_remote_has_ref(output: &str, full_ref: &str) -> bool {
+    output
+        .lines()
+        .any(|line| line.split_once('\t').is_some_and(|(_, name)| name == full_ref))
+}
+
+/// Check a branch name against git's ref naming rules (`git check-ref-format --branch`)
+///
+/// Catches the mistakes that otherwise surface as confusing errors from
+/// `git worktree add`: spaces, `..`, `~`, `^`, `:`, a leading `-`, and so on.
+pub fn validate_branch_name(branch_name: &str) -> Result<()> {
+    let problem = if branch_name.is_empty() {
+        Some("it is empty".to_string())
+    } else if branch_name.starts_with('-') {
+        Some("it starts with '-'".to_string())
+    } else if branch_name == "@" {
+        Some("'@' is reserved".to_string())
+    } else if let Some(c) = branch_name.chars().find(|c| c.is_whitespace()) {
+        Some(if c == ' ' {
+            "it contains a space".to_string()
+        } else {
+            "it contains whitespace".to_string()
+        })
+    } else if branch_name.chars().any(|c| c.is_ascii_control()) {
+        Some("it contains a control character".to_string())
+    } else if let Some(c) = branch_name.chars().find(|c| "~^:?*[\\".contains(*c)) {
+        Some(format!("it contains '{}'", c))
+    } else if branch_name.contains("..") {
+        Some("it contains '..'".to_string())
+    } else if branch_name.contains("@{") {
+        Some("it contains '@{'".to_string())
+    } else if branch_name.starts_with('/') || branch_name.ends_with('/') || branch_name.contains("//") {
+        Some("it has an empty path component".to_string())
+    } else if branch_name.ends_with('.') {
+        Some("it ends with '.'".to_string())
+    } else if branch_name.split('/').any(|part| part.starts_with('.')) {
+        Some("a path component starts with '.'".to_string())
+    } else if branch_name.split('/').any(|part| part.ends_with(".lock")) {
+        Some("a path component ends with '.lock'".to_string())
+    } else {
+        None
+    };
+
+    match problem {
+        Some(problem) => Err(Error::branch(format!(
+            "'{}' is not a valid branch name: {}",
+            branch_name, problem
+        ))),
+        None => Ok(()),
+    }
 }
 
 /// Get the current branch name (the branch HEAD points to)
@@ -59,20 +226,32 @@ pub fn get_current_branch(repo_path: &Path) -> Result<String> {
     execute_capture(&["symbolic-ref", "--short", "HEAD"], Some(repo_path))
 }
 
-/// Get the default branch name from the remote origin
-pub fn get_remote_default_branch(repo_path: &Path) -> Result<String> {
-    // Try git symbolic-ref refs/remotes/origin/HEAD
+/// Get the default branch name from a remote
+///
+/// This is the remote's HEAD, not whatever is checked out locally; the current branch
+/// is only the last resort when the remote's HEAD can't be determined.
+pub fn get_remote_default_branch(repo_path: &Path, remote: &str) -> Result<String> {
+    // Try git symbolic-ref refs/remotes/<remote>/HEAD
     // Returns something like "refs/remotes/origin/master"
-    if let Ok(ref_output) = execute_capture(&["symbolic-ref", "refs/remotes/origin/HEAD"], Some(repo_path)) {
-        if let Some(branch) = ref_output.strip_prefix("refs/remotes/origin/") {
+    let remote_prefix = format!("refs/remotes/{}/", remote);
+    if let Ok(ref_output) = execute_capture(&["symbolic-ref", &format!("{}HEAD", remote_prefix)], Some(repo_path)) {
+        if let Some(branch) = ref_output.strip_prefix(&remote_prefix) {
             return Ok(branch.to_string());
         }
     }
 
-    // Fallback: check which common branches exist on origin
+    // refs/remotes/<remote>/HEAD is only set by clone (or `git remote set-head`),
+    // so ask the remote which branch its HEAD points at
+    if let Ok(output) = execute_capture(&["ls-remote", "--symref", remote, "HEAD"], Some(repo_path)) {
+        if let Some(branch) = parse_symref_head(&output) {
+            return Ok(branch);
+        }
+    }
+
+    // Fallback: check which common branches exist on the remote
     for branch in &["main", "master"] {
         if let Ok(result) = execute_capture(
-            &["rev-parse", "--verify", &format!("origin/{}", branch)],
+            &["rev-parse", "--verify", &format!("{}/{}", remote, branch)],
             Some(repo_path),
         ) {
             if !result.is_empty() {
@@ -99,14 +278,36 @@ pub fn prune_worktrees(git_dir: &Path) -> Result<()> {
     execute_streaming(&["worktree", "prune"], Some(git_dir))
 }
 
+/// Prune worktree administrative files verbosely and report what was pruned
+///
+/// Returns one entry per pruned worktree as reported by `git worktree prune -v`
+/// (e.g. `worktrees/feature: gitdir file points to non-existent location`).
+/// With `dry_run`, git only reports what would be pruned.
+pub fn prune_worktrees_verbose(git_dir: &Path, dry_run: bool) -> Result<Vec<String>> {
+    let mut args = vec!["worktree", "prune", "-v"];
+    if dry_run {
+        args.push("--dry-run");
+    }
+    let output = execute_capture_combined(&args, Some(git_dir))?;
+    Ok(parse_prune_output(&output))
+}
+
+fn parse_prune_output(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Removing "))
+        .map(|entry| entry.to_string())
+        .collect()
+}
+
 /// Remove a worktree
 /// Delete a branch
-/// Check if a branch exists
-pub fn branch_exists(git_dir: &Path, branch_name: &str) -> Result<(bool, bool)> {
+/// Check if a branch exists locally and on the given remote
+pub fn branch_exists(git_dir: &Path, branch_name: &str, remote: &str) -> Result<(bool, bool)> {
     let local = execute_capture(&["branch", "--list", branch_name], Some(git_dir)).unwrap_or_default();
 
     let remote = execute_capture(
-        &["branch", "-r", "--list", &format!("origin/{}", branch_name)],
+        &["branch", "-r", "--list", &format!("{}/{}", remote, branch_name)],
         Some(git_dir),
     )
     .unwrap_or_default();
@@ -114,6 +315,106 @@ pub fn branch_exists(git_dir: &Path, branch_name: &str) -> Result<(bool, bool)>
     Ok((!local.is_empty(), !remote.is_empty()))
 }
 
+/// Find a remote (other than the default) that has the branch
+///
+/// Returns the first remote name from `git branch -r --list '*/<branch>'`
+/// whose remote-tracking branch matches exactly.
+pub fn find_remote_with_branch(git_dir: &Path, branch_name: &str) -> Option<String> {
+    let output = execute_capture(
+        &["branch", "-r", "--list", &format!("*/{}", branch_name)],
+        Some(git_dir),
+    )
+    .ok()?;
+    parse_remote_with_branch(&output, branch_name)
+}
+
+/// Parse the branch from `ls-remote --symref <remote> HEAD` output ("ref: refs/heads/<branch>\tHEAD")
+fn parse_symref_head(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        line.strip_prefix("ref: refs/heads/")?
+            .strip_suffix("\tHEAD")
+            .map(str::to_string)
+    })
+}
+
+fn parse_remote_with_branch(output: &str, branch_name: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.contains(" -> "))
+        .find_map(|line| {
+            let (remote, branch) = line.split_once('/')?;
+            (branch == branch_name).then(|| remote.to_string())
+        })
+}
+
+/// Map every remote-tracking ref (e.g. `refs/remotes/origin/main`) to its commit
+pub fn remote_refs(git_dir: &Path) -> Result<HashMap<String, String>> {
+    let output = execute_capture(
+        &["for-each-ref", "--format=%(refname) %(objectname)", "refs/remotes"],
+        Some(git_dir),
+    )?;
+    Ok(output
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(name, sha)| (name.to_string(), sha.to_string()))
+        .collect())
+}
+
+/// List local branches whose tips are reachable from `base` (`git branch --merged`)
+pub fn merged_branches(git_dir: &Path, base: &str) -> Result<Vec<String>> {
+    let output = execute_capture(
+        &["branch", "--merged", base, "--format=%(refname:short)"],
+        Some(git_dir),
+    )?;
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Check whether a ref (branch, tag, or commit) resolves to a commit
+pub fn ref_exists(git_dir: &Path, reference: &str) -> bool {
+    execute_capture(
+        &["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", reference)],
+        Some(git_dir),
+    )
+    .is_ok()
+}
+
+/// Whether `file` (relative to the checkout at `dir`) is tracked by git
+pub fn is_tracked(dir: &Path, file: &str) -> bool {
+    execute_capture(&["ls-files", "--error-unmatch", "--", file], Some(dir)).is_ok()
+}
+
+/// Full SHA of the commit `reference` points at, if it resolves to one
+pub fn commit_sha(git_dir: &Path, reference: &str) -> Option<String> {
+    execute_capture(
+        &["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", reference)],
+        Some(git_dir),
+    )
+    .ok()
+    .filter(|sha| !sha.is_empty())
+}
+
+/// Abbreviated SHA of the commit `reference` points at, if it resolves to one
+pub fn short_commit(git_dir: &Path, reference: &str) -> Option<String> {
+    execute_capture(
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "--short",
+            &format!("{}^{{commit}}", reference),
+        ],
+        Some(git_dir),
+    )
+    .ok()
+    .filter(|sha| !sha.is_empty())
+}
+
 /// Find a local branch with case-insensitive matching (for macOS compatibility)
 /// Returns the actual branch name if found, None otherwise
 pub fn find_local_branch_case_insensitive(git_dir: &Path, branch_name: &str) -> Result<Option<String>> {
@@ -137,9 +438,120 @@ pub fn get_git_root() -> Result<Option<PathBuf>> {
     }
 }
 
-/// Get the remote origin URL from a git repository
-pub fn get_remote_origin_url(path: &Path) -> Option<String> {
-    execute_capture(&["remote", "get-url", "origin"], Some(path)).ok()
+/// Get the URL of a named remote from a git repository
+pub fn get_remote_url(path: &Path, remote: &str) -> Option<String> {
+    execute_capture(&["remote", "get-url", remote], Some(path)).ok()
+}
+
+/// Get the shared git directory of the repository a worktree belongs to
+///
+/// All worktrees of a repository report the same common dir, so comparing it tells
+/// whether two checkouts are the same repository.
+pub fn get_git_common_dir(path: &Path) -> Option<PathBuf> {
+    let dir = execute_capture(&["rev-parse", "--git-common-dir"], Some(path)).ok()?;
+    let dir = path.join(dir);
+    Some(dir.canonicalize().unwrap_or(dir))
+}
+
+/// Names of the repository's remotes (`git remote`), empty if they can't be read
+pub fn list_remotes(path: &Path) -> Vec<String> {
+    execute_capture(&["remote"], Some(path))
+        .map(|output| output.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Lock a worktree so it can't be pruned, moved, or removed (`git worktree lock`)
+pub fn lock_worktree(git_dir: &Path, worktree_path: &Path, reason: Option<&str>) -> Result<()> {
+    let path = worktree_path.to_str().unwrap();
+    match reason {
+        Some(reason) => execute_capture(&["worktree", "lock", "--reason", reason, path], Some(git_dir)),
+        None => execute_capture(&["worktree", "lock", path], Some(git_dir)),
+    }
+    .map(|_| ())
+}
+
+/// Unlock a worktree locked with [`lock_worktree`] (`git worktree unlock`)
+pub fn unlock_worktree(git_dir: &Path, worktree_path: &Path) -> Result<()> {
+    execute_capture(&["worktree", "unlock", worktree_path.to_str().unwrap()], Some(git_dir)).map(|_| ())
+}
+
+/// Move a worktree to a new location (`git worktree move`)
+pub fn move_worktree(git_dir: &Path, old_path: &Path, new_path: &Path) -> Result<()> {
+    let old_path = old_path
+        .to_str()
+        .ok_or_else(|| Error::git("Worktree path is not valid UTF-8"))?;
+    let new_path = new_path
+        .to_str()
+        .ok_or_else(|| Error::git("Worktree path is not valid UTF-8"))?;
+    execute_capture(&["worktree", "move", old_path, new_path], Some(git_dir))
+        .map(|_| ())
+        .map_err(|e| match move_error_hint(&e.to_string()) {
+            Some(hint) => Error::git(format!("{}\n{}", hint, e)),
+            None => e,
+        })
+}
+
+/// Friendlier guidance for the ways `git worktree move` refuses to move a worktree
+fn move_error_hint(message: &str) -> Option<&'static str> {
+    if message.contains("cannot move a locked working tree") {
+        Some("The worktree is locked. Unlock it with 'gwt unlock <branch>' and try again.")
+    } else if message.contains("containing submodules cannot be moved") {
+        Some(
+            "git can't move worktrees that contain submodules. Remove it and add it again at the new location instead.",
+        )
+    } else if message.contains("is a main working tree") {
+        Some("The main working tree can't be moved; only linked worktrees can.")
+    } else if message.contains("validation failed, cannot move working tree") {
+        Some("git's records for this worktree are broken. Run 'gwt prune' or 'git worktree repair' first.")
+    } else {
+        None
+    }
+}
+
+/// Committer time (Unix seconds) of the commit `rev` points at
+///
+/// Returns `None` when `rev` doesn't resolve, e.g. on a branch with no commits yet.
+pub fn last_commit_time(git_dir: &Path, rev: &str) -> Option<i64> {
+    execute_capture(&["log", "-1", "--format=%ct", rev, "--"], Some(git_dir))
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Short SHA and subject of the commit checked out in a worktree (`git log -1`)
+///
+/// Run from the worktree itself so detached HEADs work too; `None` for a branch
+/// without commits yet.
+pub fn head_commit_summary(worktree_path: &Path) -> Option<(String, String)> {
+    let output = execute_capture(&["log", "-1", "--format=%h%x00%s", "HEAD", "--"], Some(worktree_path)).ok()?;
+    let (sha, subject) = output.split_once('\0')?;
+    Some((sha.to_string(), subject.to_string()))
+}
+
+/// Count changed and untracked files in a worktree (`git status --porcelain`)
+pub fn count_dirty_files(worktree_path: &Path) -> Result<usize> {
+    let output = execute_capture(&["status", "--porcelain"], Some(worktree_path))?;
+    Ok(output.lines().filter(|line| !line.trim().is_empty()).count())
+}
+
+/// Commits ahead of and behind the upstream branch, as `(ahead, behind)`
+///
+/// Returns `None` when the branch has no upstream (or HEAD is detached).
+pub fn ahead_behind_upstream(worktree_path: &Path) -> Option<(usize, usize)> {
+    let output = execute_capture(
+        &["rev-list", "--left-right", "--count", "@{upstream}...HEAD"],
+        Some(worktree_path),
+    )
+    .ok()?;
+    parse_ahead_behind(&output)
+}
+
+/// Parse `rev-list --left-right --count @{upstream}...HEAD` output ("<behind>\t<ahead>")
+fn parse_ahead_behind(output: &str) -> Option<(usize, usize)> {
+    let mut counts = output.split_whitespace().map(|n| n.parse::<usize>().ok());
+    let behind = counts.next()??;
+    let ahead = counts.next()??;
+    Some((ahead, behind))
 }
 
 #[derive(Debug, Clone)]
@@ -148,6 +560,12 @@ pub struct Worktree {
     pub head: String,
     pub branch: Option<String>,
     pub bare: bool,
+    /// True when HEAD is detached (no branch checked out)
+    pub detached: bool,
+    /// Set when git reports the worktree as prunable (e.g. its directory was deleted)
+    pub prunable: Option<String>,
+    /// Set when the worktree is locked (`git worktree lock`); holds the reason, if any
+    pub locked: Option<String>,
 }
 
 fn parse_worktree_list(output: &str) -> Result<Vec<Worktree>> {
@@ -160,6 +578,9 @@ fn parse_worktree_list(output: &str) -> Result<Vec<Worktree>> {
         head: Option<String>,
         branch: Option<String>,
         bare: bool,
+        detached: bool,
+        prunable: Option<String>,
+        locked: Option<String>,
     }
 
     impl PartialWorktree {
@@ -170,6 +591,9 @@ fn parse_worktree_list(output: &str) -> Result<Vec<Worktree>> {
                     head,
                     branch: self.branch,
                     bare: self.bare,
+                    detached: self.detached,
+                    prunable: self.prunable,
+                    locked: self.locked,
                 }),
                 _ => None,
             }
@@ -204,6 +628,21 @@ fn parse_worktree_list(output: &str) -> Result<Vec<Worktree>> {
                     wt.bare = true;
                 }
             }
+            WorktreeLine::Detached => {
+                if let Some(ref mut wt) = current_worktree {
+                    wt.detached = true;
+                }
+            }
+            WorktreeLine::Prunable(reason) => {
+                if let Some(ref mut wt) = current_worktree {
+                    wt.prunable = Some(reason);
+                }
+            }
+            WorktreeLine::Locked(reason) => {
+                if let Some(ref mut wt) = current_worktree {
+                    wt.locked = Some(reason);
+                }
+            }
             WorktreeLine::Other => {}
         }
     }
@@ -223,6 +662,9 @@ enum WorktreeLine {
     Head(String),
     Branch(String),
     Bare,
+    Detached,
+    Prunable(String),
+    Locked(String),
     Other,
 }
 
@@ -235,7 +677,240 @@ fn parse_worktree_line(line: &str) -> WorktreeLine {
         WorktreeLine::Branch(branch.to_string())
     } else if line == "bare" {
         WorktreeLine::Bare
+    } else if line == "detached" {
+        WorktreeLine::Detached
+    } else if line == "prunable" {
+        WorktreeLine::Prunable(String::new())
+    } else if let Some(reason) = line.strip_prefix("prunable ") {
+        WorktreeLine::Prunable(reason.to_string())
+    } else if line == "locked" {
+        WorktreeLine::Locked(String::new())
+    } else if let Some(reason) = line.strip_prefix("locked ") {
+        WorktreeLine::Locked(reason.to_string())
     } else {
         WorktreeLine::Other
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worktree_add_args() {
+        let path = PathBuf::from("/work/repo-worktrees/feature/x");
+        let args = |options: WorktreeAddOptions| worktree_add_args(&options).join(" ");
+
+        // Existing local branch
+        assert_eq!(
+            args(WorktreeAddOptions {
+                path: path.clone(),
+                start_point: Some("feature/x".to_string()),
+                ..Default::default()
+            }),
+            "worktree add /work/repo-worktrees/feature/x feature/x"
+        );
+
+        // Branch that only exists on the remote, tracking it
+        assert_eq!(
+            args(WorktreeAddOptions {
+                path: path.clone(),
+                new_branch: Some("feature/x".to_string()),
+                start_point: Some("origin/feature/x".to_string()),
+                track: Some(true),
+                ..Default::default()
+            }),
+            "worktree add --track /work/repo-worktrees/feature/x -b feature/x origin/feature/x"
+        );
+
+        // New branch off the main branch, without tracking it
+        assert_eq!(
+            args(WorktreeAddOptions {
+                path: path.clone(),
+                new_branch: Some("feature/x".to_string()),
+                start_point: Some("origin/main".to_string()),
+                track: Some(false),
+                ..Default::default()
+            }),
+            "worktree add --no-track /work/repo-worktrees/feature/x -b feature/x origin/main"
+        );
+
+        // Detached HEAD at a commit
+        assert_eq!(
+            args(WorktreeAddOptions {
+                path,
+                start_point: Some("v1.2.0".to_string()),
+                detach: true,
+                ..Default::default()
+            }),
+            "worktree add --detach /work/repo-worktrees/feature/x v1.2.0"
+        );
+    }
+
+    #[test]
+    fn test_parse_worktree_list_locked() {
+        let output = "worktree /repo\n\
+HEAD 0b051d20586b3a48f8db329aeefbc4e492c89a57\n\
+branch refs/heads/main\n\
+\n\
+worktree /repo-worktrees/shared\n\
+HEAD 0b051d20586b3a48f8db329aeefbc4e492c89a57\n\
+branch refs/heads/shared\n\
+locked in use by the CI box\n\
+\n\
+worktree /repo-worktrees/quiet\n\
+HEAD 0b051d20586b3a48f8db329aeefbc4e492c89a57\n\
+branch refs/heads/quiet\n\
+locked\n";
+        let worktrees = parse_worktree_list(output).unwrap();
+        assert_eq!(worktrees[0].locked, None);
+        assert_eq!(worktrees[1].locked.as_deref(), Some("in use by the CI box"));
+        assert_eq!(worktrees[2].locked.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_parse_worktree_list_detached_and_locked() {
+        let output = "worktree /repo/main\n\
+HEAD 0b051d20586b3a48f8db329aeefbc4e492c89a57\n\
+branch refs/heads/main\n\
+\n\
+worktree /repo/review\n\
+HEAD 9f3c1e2a8d7b6c5f4e3d2c1b0a9f8e7d6c5b4a39\n\
+detached\n\
+locked reviewing v1.2\n";
+
+        let worktrees = parse_worktree_list(output).unwrap();
+        assert_eq!(worktrees.len(), 2);
+        assert!(!worktrees[0].detached);
+        assert_eq!(worktrees[0].locked, None);
+        assert!(worktrees[1].detached);
+        assert_eq!(worktrees[1].branch, None);
+        assert_eq!(worktrees[1].locked.as_deref(), Some("reviewing v1.2"));
+    }
+
+    #[test]
+    fn test_validate_branch_name() {
+        for valid in [
+            "main",
+            "feature/login",
+            "bugfix/JIRA-123_fix",
+            "release/1.2.3",
+            "user@feature",
+        ] {
+            assert!(validate_branch_name(valid).is_ok(), "{} should be valid", valid);
+        }
+
+        let err = validate_branch_name("feature branch").unwrap_err();
+        assert!(matches!(err, Error::Branch(_)));
+        assert!(err.to_string().contains("contains a space"));
+        assert!(validate_branch_name("feature..login")
+            .unwrap_err()
+            .to_string()
+            .contains("'..'"));
+        for invalid in [
+            "", "-b", "@", "a~1", "a^", "a:b", "a?", "a*", "a[b", "a\\b", "a@{1}", "/a", "a/", "a//b", "a.", ".a",
+            "a/.b", "a.lock", "a\tb",
+        ] {
+            assert!(
+                validate_branch_name(invalid).is_err(),
+                "{:?} should be invalid",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_worktree_list_prunable() {
+        let output = "worktree /repo\n\
+HEAD 0b051d20586b3a48f8db329aeefbc4e492c89a57\n\
+branch refs/heads/main\n\
+\n\
+worktree /repo-worktrees/gone\n\
+HEAD 0b051d20586b3a48f8db329aeefbc4e492c89a57\n\
+branch refs/heads/gone\n\
+prunable gitdir file points to non-existent location\n";
+
+        let worktrees = parse_worktree_list(output).unwrap();
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees[0].prunable, None);
+        assert_eq!(
+            worktrees[1].prunable.as_deref(),
+            Some("gitdir file points to non-existent location")
+        );
+        assert_eq!(worktrees[1].branch.as_deref(), Some("refs/heads/gone"));
+    }
+
+    #[test]
+    fn test_parse_ahead_behind() {
+        assert_eq!(parse_ahead_behind("3\t5"), Some((5, 3)));
+        assert_eq!(parse_ahead_behind("0\t0\n"), Some((0, 0)));
+        assert_eq!(parse_ahead_behind(""), None);
+    }
+
+    #[test]
+    fn test_parse_prune_output() {
+        let output = "Removing worktrees/gone: gitdir file points to non-existent location\n\
+Removing worktrees/old: gitdir file points to non-existent location";
+        assert_eq!(
+            parse_prune_output(output),
+            vec![
+                "worktrees/gone: gitdir file points to non-existent location".to_string(),
+                "worktrees/old: gitdir file points to non-existent location".to_string(),
+            ]
+        );
+        assert!(parse_prune_output("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_remote_with_branch() {
+        let output = "  origin/HEAD -> origin/main\n  origin/main\n  upstream/feature/x\n  upstream/feature/x-2";
+        assert_eq!(
+            parse_remote_with_branch(output, "feature/x"),
+            Some("upstream".to_string())
+        );
+        assert_eq!(parse_remote_with_branch(output, "main"), Some("origin".to_string()));
+        assert_eq!(parse_remote_with_branch(output, "missing"), None);
+    }
+
+    #[test]
+    fn test_move_error_hint() {
+        let locked = "Git command failed: fatal: cannot move a locked working tree, lock reason: usb\n\
+use 'move -f -f' to override or unlock first";
+        assert!(move_error_hint(locked).unwrap().contains("gwt unlock"));
+        assert!(
+            move_error_hint("fatal: working trees containing submodules cannot be moved or removed")
+                .unwrap()
+                .contains("submodules")
+        );
+        assert!(move_error_hint("fatal: '.' is a main working tree").is_some());
+        assert_eq!(move_error_hint("fatal: '../wt' already exists"), None);
+    }
+
+    #[test]
+    fn test_parse_symref_head() {
+        let output = "ref: refs/heads/develop\tHEAD\n1f0c3a9d2b7e4c5a6f8d9e0b1c2d3e4f5a6b7c8d\tHEAD";
+        assert_eq!(parse_symref_head(output), Some("develop".to_string()));
+        assert_eq!(
+            parse_symref_head("ref: refs/heads/release/2.x\tHEAD"),
+            Some("release/2.x".to_string())
+        );
+        // Servers that don't advertise symrefs only send the commit
+        assert_eq!(
+            parse_symref_head("1f0c3a9d2b7e4c5a6f8d9e0b1c2d3e4f5a6b7c8d\tHEAD"),
+            None
+        );
+        assert_eq!(parse_symref_head(""), None);
+    }
+
+    #[test]
+    fn test_ls_remote_has_ref() {
+        let output = "1f0c3a9d\trefs/heads/release\n2e1d4b0c\trefs/heads/team/release";
+        assert!(ls_remote_has_ref(output, "refs/heads/release"));
+        assert!(ls_remote_has_ref(output, "refs/heads/team/release"));
+        assert!(!ls_remote_has_ref(
+            "2e1d4b0c\trefs/heads/team/release",
+            "refs/heads/release"
+        ));
+        assert!(!ls_remote_has_ref("", "refs/heads/release"));
+    }
+}
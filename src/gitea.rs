@@ -0,0 +1,341 @@
+use reqwest::{Client, RequestBuilder, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::future::Future;
+
+use crate::error::{Error, Result};
+use crate::http;
+
+/// Public instances recognized from the URL; self-hosted ones need `--provider gitea`
+const KNOWN_GITEA_HOSTS: &[&str] = &["codeberg.org"];
+
+/// Page size requested from the pull requests endpoint (Gitea's default maximum)
+const PR_PAGE_LIMIT: usize = 50;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GiteaUser {
+    pub login: String,
+    #[serde(default)]
+    pub full_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GiteaBranchRef {
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GiteaPullRequest {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub html_url: String,
+    /// Only reported by newer versions; older ones mark drafts with a WIP title prefix
+    #[serde(default)]
+    pub draft: bool,
+    pub head: GiteaBranchRef,
+    /// Branch the PR merges into
+    #[serde(default)]
+    pub base: Option<GiteaBranchRef>,
+    pub user: Option<GiteaUser>,
+    /// When the PR was last updated (RFC 3339)
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+impl GiteaPullRequest {
+    pub fn is_draft(&self) -> bool {
+        let title = self.title.to_uppercase();
+        self.draft || title.starts_with("WIP:") || title.starts_with("[WIP]")
+    }
+
+    /// Full name of whoever opened the PR, or their login when it's not set
+    pub fn author(&self) -> Option<String> {
+        self.user.as_ref().map(|user| {
+            if user.full_name.is_empty() {
+                user.login.clone()
+            } else {
+                user.full_name.clone()
+            }
+        })
+    }
+}
+
+/// Fetch pages starting at 1 until one comes back short or `max` values are collected
+async fn collect_pull_request_pages<F, Fut>(mut fetch_page: F, max: Option<usize>) -> Result<Vec<GiteaPullRequest>>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<Vec<GiteaPullRequest>>>,
+{
+    let mut pull_requests = Vec::new();
+    for page in 1.. {
+        let values = fetch_page(page).await?;
+        let done = values.len() < PR_PAGE_LIMIT;
+        pull_requests.extend(values);
+        if done || max.is_some_and(|max| pull_requests.len() >= max) {
+            break;
+        }
+    }
+    if let Some(max) = max {
+        pull_requests.truncate(max);
+    }
+    Ok(pull_requests)
+}
+
+pub struct GiteaClient {
+    client: Client,
+    token: String,
+    base_url: String,
+}
+
+impl GiteaClient {
+    /// Client for the instance at `base_url` (e.g. `https://codeberg.org`)
+    pub fn new(token: String, base_url: String) -> Self {
+        GiteaClient {
+            client: http::client(),
+            token,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Open pull requests for the repository, at most `max` of them (all for `None`)
+    pub async fn get_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        max: Option<usize>,
+    ) -> Result<Vec<GiteaPullRequest>> {
+        let url = format!("{}/api/v1/repos/{}/{}/pulls", self.base_url, owner, repo);
+        let not_found = format!(
+            "Repository not found: {}/{}. Please check the repository URL and giteaBaseUrl.",
+            owner, repo
+        );
+        collect_pull_request_pages(
+            |page| {
+                let page_url = format!("{}?state=open&page={}&limit={}", url, page, PR_PAGE_LIMIT);
+                let not_found = not_found.clone();
+                async move { self.api_get(&page_url, &not_found).await }
+            },
+            max,
+        )
+        .await
+    }
+
+    /// Verify the token by fetching the authenticated user
+    pub async fn test_connection(&self) -> Result<()> {
+        let url = format!("{}/api/v1/user", self.base_url);
+        let not_found = format!("No Gitea API at {}. Please check giteaBaseUrl.", self.base_url);
+        let user: GiteaUser = self.api_get(&url, &not_found).await?;
+        println!("✓ Gitea API connection successful (as {})", user.login);
+        Ok(())
+    }
+
+    fn api_request(&self, request: RequestBuilder) -> RequestBuilder {
+        request
+            .header("Authorization", format!("token {}", self.token))
+            .header("Accept", "application/json")
+    }
+
+    /// GET `url` and parse the JSON body, reporting a 404 as `not_found`
+    async fn api_get<T: DeserializeOwned>(&self, url: &str, not_found: &str) -> Result<T> {
+        let response = http::send_with_retry(|| self.api_request(self.client.get(url)))
+            .await
+            .map_err(|e| http::request_error("Failed to send request to Gitea API", e))?;
+        Self::parse_api_response(response, not_found).await
+    }
+
+    async fn parse_api_response<T: DeserializeOwned>(response: Response, not_found: &str) -> Result<T> {
+        let status = response.status();
+        if status == 401 {
+            return Err(Error::auth(
+                "Gitea authentication failed. Check the token with 'gwt auth gitea test'.",
+            ));
+        }
+        if status == 404 {
+            return Err(Error::provider(not_found));
+        }
+        if status.is_server_error() {
+            return Err(Error::network(format!(
+                "Gitea API request failed with status {} after retrying",
+                status
+            )));
+        }
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::provider(format!(
+                "Gitea API request failed with status {}: {}",
+                status, text
+            )));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| Error::provider(format!("Failed to parse Gitea API response: {}", e)))
+    }
+}
+
+/// Split a Gitea/Forgejo remote URL into instance URL, owner and repository name
+///
+/// For HTTP(S) remotes everything before `owner/repo` is the instance URL, so
+/// instances served from a subpath work too. SSH remotes map to `https://<host>`.
+pub fn parse_gitea_url(url: &str) -> Option<(String, String, String)> {
+    let url = url.trim_end_matches('/').trim_end_matches(".git");
+
+    let (base_url, path) = if url.starts_with("https://") || url.starts_with("http://") {
+        let mut parts = url.rsplitn(3, '/');
+        let repo = parts.next()?;
+        let owner = parts.next()?;
+        let base_url = parts.next()?;
+        if base_url.split_once("://")?.1.is_empty() {
+            return None;
+        }
+        return Some((base_url.to_string(), owner.to_string(), repo.to_string()));
+    } else if let Some(rest) = url.strip_prefix("ssh://") {
+        // ssh://git@host:2222/owner/repo (the port is SSH's, not the web UI's)
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?.split(':').next()?;
+        (format!("https://{}", host), path)
+    } else {
+        // git@host:owner/repo
+        let (authority, path) = url.split_once(':')?;
+        let host = authority.rsplit('@').next()?;
+        (format!("https://{}", host), path)
+    };
+
+    let (owner, repo) = path.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some((base_url, owner.to_string(), repo.to_string()))
+}
+
+/// Whether the URL points at a public Gitea/Forgejo instance such as Codeberg
+pub fn is_known_gitea_host(url: &str) -> bool {
+    parse_gitea_url(url).is_some_and(|(base_url, _, _)| {
+        KNOWN_GITEA_HOSTS
+            .iter()
+            .any(|host| base_url == format!("https://{}", host))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gitea_url() {
+        let parsed =
+            |base: &str, owner: &str, repo: &str| Some((base.to_string(), owner.to_string(), repo.to_string()));
+        let test_cases = vec![
+            (
+                "https://codeberg.org/owner/repo.git",
+                parsed("https://codeberg.org", "owner", "repo"),
+            ),
+            (
+                "https://git.example.com/owner/repo",
+                parsed("https://git.example.com", "owner", "repo"),
+            ),
+            (
+                "http://localhost:3000/gitea/owner/repo.git",
+                parsed("http://localhost:3000/gitea", "owner", "repo"),
+            ),
+            (
+                "git@git.example.com:owner/repo.git",
+                parsed("https://git.example.com", "owner", "repo"),
+            ),
+            (
+                "ssh://git@git.example.com:2222/owner/repo.git",
+                parsed("https://git.example.com", "owner", "repo"),
+            ),
+            ("https://git.example.com/repo", None),
+            ("not a url", None),
+        ];
+
+        for (url, expected) in test_cases {
+            assert_eq!(parse_gitea_url(url), expected, "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_is_known_gitea_host() {
+        assert!(is_known_gitea_host("https://codeberg.org/owner/repo.git"));
+        assert!(is_known_gitea_host("git@codeberg.org:owner/repo.git"));
+        assert!(!is_known_gitea_host("https://git.example.com/owner/repo"));
+        assert!(!is_known_gitea_host("https://github.com/owner/repo"));
+    }
+
+    #[test]
+    fn test_pull_request_draft_and_author() {
+        let json = r#"[
+            {"number": 1, "title": "Ready", "state": "open", "html_url": "u1", "head": {"ref": "feature/a"}, "base": {"ref": "main"},
+             "user": {"login": "dev", "full_name": "Dev Eloper"}},
+            {"number": 2, "title": "WIP: Not yet", "state": "open", "html_url": "u2", "head": {"ref": "feature/b"},
+             "user": {"login": "dev", "full_name": ""}},
+            {"number": 3, "title": "Draft", "state": "open", "html_url": "u3", "draft": true, "head": {"ref": "feature/c"}, "user": null}
+        ]"#;
+        let prs: Vec<GiteaPullRequest> = serde_json::from_str(json).unwrap();
+
+        assert!(!prs[0].is_draft());
+        assert_eq!(prs[0].author().as_deref(), Some("Dev Eloper"));
+        assert_eq!(prs[0].head.ref_name, "feature/a");
+        assert_eq!(prs[0].base.as_ref().map(|base| base.ref_name.as_str()), Some("main"));
+        assert!(prs[1].base.is_none());
+        assert!(prs[1].is_draft());
+        assert_eq!(prs[1].author().as_deref(), Some("dev"));
+        assert!(prs[2].is_draft());
+        assert_eq!(prs[2].author(), None);
+    }
+
+    fn pull_requests(count: usize) -> Vec<GiteaPullRequest> {
+        (0..count)
+            .map(|i| GiteaPullRequest {
+                number: i as u64,
+                title: format!("PR {}", i),
+                state: "open".to_string(),
+                html_url: String::new(),
+                draft: false,
+                head: GiteaBranchRef {
+                    ref_name: format!("b{}", i),
+                },
+                base: None,
+                user: None,
+                updated_at: None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_collect_pull_request_pages_stops_on_short_page() {
+        let mut requested_pages = Vec::new();
+        let prs = collect_pull_request_pages(
+            |page| {
+                requested_pages.push(page);
+                let count = if page < 3 { PR_PAGE_LIMIT } else { 7 };
+                async move { Ok(pull_requests(count)) }
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(requested_pages, vec![1, 2, 3]);
+        assert_eq!(prs.len(), 2 * PR_PAGE_LIMIT + 7);
+    }
+
+    #[tokio::test]
+    async fn test_collect_pull_request_pages_stops_at_max() {
+        let mut requested_pages = Vec::new();
+        let prs = collect_pull_request_pages(
+            |page| {
+                requested_pages.push(page);
+                async move { Ok(pull_requests(PR_PAGE_LIMIT)) }
+            },
+            Some(PR_PAGE_LIMIT + 1),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(requested_pages, vec![1, 2]);
+        assert_eq!(prs.len(), PR_PAGE_LIMIT + 1);
+    }
+}
@@ -0,0 +1,89 @@
+use std::env;
+
+use crate::error::{Error, Result};
+use crate::token_store::{TokenLocation, TokenStore};
+
+const SERVICE_NAME: &str = "git-worktree-cli-gitea";
+const KEYRING_USER: &str = "token";
+const TOKEN_ENV_VAR: &str = "GITEA_TOKEN";
+
+/// Gitea/Forgejo access token, from `GITEA_TOKEN` or the keyring
+pub struct GiteaAuth {
+    store: TokenStore,
+}
+
+impl GiteaAuth {
+    pub fn new() -> Result<Self> {
+        Ok(GiteaAuth {
+            store: TokenStore::new(SERVICE_NAME, KEYRING_USER),
+        })
+    }
+
+    pub fn get_token(&self) -> Result<String> {
+        // Check environment variable
+        if let Ok(token) = env::var(TOKEN_ENV_VAR) {
+            if !token.is_empty() {
+                return Ok(token);
+            }
+        }
+
+        // Then check the keyring (or token file)
+        self.store.get()?.map(|(token, _)| token).ok_or_else(|| {
+            Error::auth(format!(
+                "No Gitea token found. Set {} or run 'gwt auth gitea setup'.",
+                TOKEN_ENV_VAR
+            ))
+        })
+    }
+
+    /// Where the token comes from (environment, keyring, or token file), if there is one
+    pub fn token_source(&self) -> Option<String> {
+        if env::var(TOKEN_ENV_VAR).is_ok_and(|token| !token.is_empty()) {
+            return Some(format!("{} (env)", TOKEN_ENV_VAR));
+        }
+        self.store.location().map(|location| location.to_string())
+    }
+
+    /// Store the token in the keyring (or the token file without one), returning where it went
+    pub fn store_token(&self, token: &str) -> Result<TokenLocation> {
+        self.store.set(token)
+    }
+}
+
+/// Instance URL, owner and repository name for the current project
+///
+/// The instance URL is `giteaBaseUrl` when set, otherwise derived from the repository URL.
+pub fn get_auth_from_config() -> Result<(String, String, String)> {
+    use crate::config::GitWorktreeConfig;
+    use crate::gitea::parse_gitea_url;
+
+    let (_, config) = GitWorktreeConfig::find_config()?
+        .ok_or_else(|| Error::config(format!("No {} found", crate::config::config_filename())))?;
+
+    if config.source_control != "gitea" {
+        return Err(Error::provider(format!(
+            "Repository is not configured for Gitea (sourceControl: {})",
+            config.source_control
+        )));
+    }
+
+    let (base_url, owner, repo) = parse_gitea_url(&config.repository_url)
+        .ok_or_else(|| Error::provider(format!("Failed to parse repository URL: {}", config.repository_url)))?;
+    Ok((config.gitea_base_url.unwrap_or(base_url), owner, repo))
+}
+
+pub fn display_setup_instructions() {
+    println!("Setting up Gitea / Forgejo authentication\n");
+    println!("1. Create an access token on your instance:");
+    println!("   Settings > Applications > Generate New Token");
+    println!("   (scopes: read:repository, read:user)\n");
+    println!("2. Store it in the system keyring (prompts for the token):");
+    println!("   gwt auth gitea setup");
+    println!("   or pipe it in: gwt auth gitea setup --token-stdin < token.txt\n");
+    println!("   or set an environment variable:");
+    println!("   export {}=YOUR_TOKEN", TOKEN_ENV_VAR);
+    println!("\nAPI requests go to the repository's host. If your instance is served");
+    println!("from a different URL, set it with:");
+    println!("   gwt config set giteaBaseUrl https://git.example.com");
+    println!("\nVerify with: gwt auth gitea test");
+}
@@ -1,6 +1,9 @@
-use serde::{Deserialize, Serialize};
+use reqwest::{Client, RequestBuilder, Response, Url};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::error::{Error, Result};
+use crate::github_auth::GitHubAuth;
+use crate::http;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PullRequest {
@@ -9,6 +12,15 @@ pub struct PullRequest {
     pub state: String,
     pub html_url: String,
     pub draft: bool,
+    /// Login of the user who opened the PR
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Branch the PR merges into
+    #[serde(default)]
+    pub base: Option<String>,
+    /// When the PR was last updated (RFC 3339)
+    #[serde(default)]
+    pub updated_at: Option<String>,
 }
 
 // Structs for gh CLI JSON output
@@ -20,6 +32,17 @@ struct GhPrResponse {
     url: String,
     #[serde(rename = "isDraft")]
     is_draft: bool,
+    #[serde(rename = "baseRefName", default)]
+    base_ref_name: Option<String>,
+    #[serde(rename = "updatedAt", default)]
+    updated_at: Option<String>,
+    author: Option<GhUser>,
+}
+
+/// PR author as reported by both gh (`author`) and the REST API (`user`)
+#[derive(Debug, Deserialize)]
+struct GhUser {
+    login: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -32,9 +55,75 @@ struct GhPrWithBranchResponse {
     is_draft: bool,
     #[serde(rename = "headRefName")]
     head_ref_name: String,
+    #[serde(rename = "baseRefName", default)]
+    base_ref_name: Option<String>,
+    #[serde(rename = "updatedAt", default)]
+    updated_at: Option<String>,
+    author: Option<GhUser>,
+}
+
+// Structs for GitHub REST API responses (used with a token when gh isn't available)
+#[derive(Debug, Deserialize)]
+struct ApiPullRequest {
+    number: u32,
+    title: String,
+    state: String,
+    html_url: String,
+    #[serde(default)]
+    draft: bool,
+    merged_at: Option<String>,
+    head: ApiPullRequestRef,
+    base: Option<ApiPullRequestRef>,
+    user: Option<GhUser>,
+    #[serde(default)]
+    updated_at: Option<String>,
 }
 
-pub struct GitHubClient;
+#[derive(Debug, Deserialize)]
+struct ApiPullRequestRef {
+    #[serde(rename = "ref")]
+    ref_name: String,
+}
+
+impl From<ApiPullRequest> for PullRequest {
+    fn from(pr: ApiPullRequest) -> Self {
+        // The REST API reports merged PRs as closed; match gh's MERGED state
+        let state = if pr.merged_at.is_some() {
+            "MERGED".to_string()
+        } else {
+            pr.state.to_uppercase()
+        };
+        PullRequest {
+            number: pr.number,
+            title: pr.title,
+            state,
+            html_url: pr.html_url,
+            draft: pr.draft,
+            author: pr.user.map(|user| user.login),
+            base: pr.base.map(|base| base.ref_name),
+            updated_at: pr.updated_at,
+        }
+    }
+}
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+
+/// `gh pr list --limit` when every open pull request is wanted (gh requires a number)
+const GH_NO_LIMIT: usize = i32::MAX as usize;
+
+/// How the client talks to GitHub: the gh CLI if it's authenticated, otherwise the
+/// REST API with a stored token
+#[derive(Clone)]
+enum Backend {
+    Gh,
+    Api { client: Client, token: String },
+    Unconfigured,
+}
+
+#[derive(Clone)]
+pub struct GitHubClient {
+    backend: Backend,
+}
 
 impl Default for GitHubClient {
     fn default() -> Self {
@@ -43,8 +132,220 @@ impl Default for GitHubClient {
 }
 
 impl GitHubClient {
+    /// Use gh when it's authenticated, falling back to a keyring/`GITHUB_TOKEN` token
     pub fn new() -> Self {
-        Self
+        let backend = if Self::get_gh_token().is_some() {
+            Backend::Gh
+        } else {
+            match GitHubAuth::new().and_then(|auth| auth.get_token()) {
+                Ok(token) => Backend::Api {
+                    client: http::client(),
+                    token,
+                },
+                Err(_) => Backend::Unconfigured,
+            }
+        };
+        Self { backend }
+    }
+
+    /// Client that always uses the REST API with the given token
+    pub fn with_token(token: String) -> Self {
+        Self {
+            backend: Backend::Api {
+                client: http::client(),
+                token,
+            },
+        }
+    }
+
+    fn not_configured() -> Error {
+        Error::auth(
+            "GitHub authentication is not configured. Run 'gh auth login', set GITHUB_TOKEN, \
+             or run 'gwt auth github setup'.",
+        )
+    }
+
+    /// Whether the client uses the gh CLI (rather than a token)
+    pub fn uses_gh(&self) -> bool {
+        matches!(self.backend, Backend::Gh)
+    }
+
+    pub async fn get_pull_requests(&self, owner: &str, repo: &str, branch: &str) -> Result<Vec<PullRequest>> {
+        match &self.backend {
+            Backend::Gh => {
+                // gh is a blocking subprocess; run it on the blocking pool so lookups overlap
+                let (owner, repo, branch) = (owner.to_string(), repo.to_string(), branch.to_string());
+                tokio::task::spawn_blocking(move || Self::gh_get_pull_requests(&owner, &repo, &branch))
+                    .await
+                    .map_err(|e| Error::provider(format!("GitHub PR lookup task failed: {}", e)))?
+            }
+            Backend::Api { client, token } => {
+                let url = format!("{}/repos/{}/{}/pulls", GITHUB_API_BASE, owner, repo);
+                let head = format!("{}:{}", owner, branch);
+                let query = [("state", "all"), ("head", head.as_str())];
+                let prs: Vec<ApiPullRequest> = Self::api_get(client, token, &url, &query).await?;
+                Ok(prs.into_iter().map(PullRequest::from).collect())
+            }
+            Backend::Unconfigured => Err(Self::not_configured()),
+        }
+    }
+
+    /// Open pull requests with their head branch, at most `max` of them (all for `None`)
+    pub async fn get_all_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        max: Option<usize>,
+    ) -> Result<Vec<(PullRequest, String)>> {
+        match &self.backend {
+            Backend::Gh => {
+                let (owner, repo) = (owner.to_string(), repo.to_string());
+                tokio::task::spawn_blocking(move || Self::gh_get_all_pull_requests(&owner, &repo, max))
+                    .await
+                    .map_err(|e| Error::provider(format!("GitHub PR lookup task failed: {}", e)))?
+            }
+            Backend::Api { client, token } => {
+                let url = format!("{}/repos/{}/{}/pulls", GITHUB_API_BASE, owner, repo);
+                let query = [("state", "open"), ("per_page", "100")];
+                let prs: Vec<ApiPullRequest> = Self::api_get_pages(client, token, &url, &query, max).await?;
+                Ok(prs
+                    .into_iter()
+                    .map(|pr| {
+                        let branch = pr.head.ref_name.clone();
+                        (PullRequest::from(pr), branch)
+                    })
+                    .collect())
+            }
+            Backend::Unconfigured => Err(Self::not_configured()),
+        }
+    }
+
+    /// Create a pull request and return its URL
+    pub async fn create_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        head: &str,
+        base: &str,
+        title: &str,
+        draft: bool,
+    ) -> Result<String> {
+        match &self.backend {
+            Backend::Gh => Self::gh_create_pull_request(owner, repo, head, base, title, draft),
+            Backend::Api { client, token } => {
+                let url = format!("{}/repos/{}/{}/pulls", GITHUB_API_BASE, owner, repo);
+                let body = serde_json::json!({ "title": title, "head": head, "base": base, "draft": draft });
+                // Not retried: a create that succeeded before a timeout would fail as a duplicate
+                let response = Self::api_request(client.post(&url), token)
+                    .json(&body)
+                    .send()
+                    .await
+                    .map_err(|e| http::request_error("Failed to send request to GitHub API", e))?;
+                let pr: ApiPullRequest = Self::parse_api_response(response).await?;
+                Ok(pr.html_url)
+            }
+            Backend::Unconfigured => Err(Self::not_configured()),
+        }
+    }
+
+    /// Verify the credentials by fetching the authenticated user
+    pub async fn test_connection(&self) -> Result<()> {
+        match &self.backend {
+            Backend::Gh => {
+                println!("✓ GitHub CLI (gh) is authenticated");
+                Ok(())
+            }
+            Backend::Api { client, token } => {
+                let url = format!("{}/user", GITHUB_API_BASE);
+                let user: serde_json::Value = Self::api_get(client, token, &url, &[]).await?;
+                println!(
+                    "✓ GitHub API connection successful (as {})",
+                    user.get("login").and_then(|l| l.as_str()).unwrap_or("unknown user")
+                );
+                Ok(())
+            }
+            Backend::Unconfigured => Err(Self::not_configured()),
+        }
+    }
+
+    fn api_request(request: RequestBuilder, token: &str) -> RequestBuilder {
+        request
+            .bearer_auth(token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "git-worktree-cli")
+    }
+
+    async fn api_get<T: DeserializeOwned>(
+        client: &Client,
+        token: &str,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T> {
+        let response = Self::api_send(client, token, url, query).await?;
+        Self::parse_api_response(response).await
+    }
+
+    /// GET a list endpoint and follow its `Link: rel="next"` headers until the last page
+    /// or until `max` values are collected
+    async fn api_get_pages<T: DeserializeOwned>(
+        client: &Client,
+        token: &str,
+        url: &str,
+        query: &[(&str, &str)],
+        max: Option<usize>,
+    ) -> Result<Vec<T>> {
+        let mut values = Vec::new();
+        let mut response = Self::api_send(client, token, url, query).await?;
+        loop {
+            let next = response
+                .headers()
+                .get(reqwest::header::LINK)
+                .and_then(|link| link.to_str().ok())
+                .and_then(next_page_link);
+            values.extend(Self::parse_api_response::<Vec<T>>(response).await?);
+            let next = match next {
+                Some(next) if max.is_none_or(|max| values.len() < max) => next,
+                _ => break,
+            };
+            // The next link already carries the full query string
+            response = Self::api_send(client, token, &next, &[]).await?;
+        }
+        if let Some(max) = max {
+            values.truncate(max);
+        }
+        Ok(values)
+    }
+
+    async fn api_send(client: &Client, token: &str, url: &str, query: &[(&str, &str)]) -> Result<Response> {
+        let mut url =
+            Url::parse(url).map_err(|e| Error::provider(format!("Invalid GitHub API URL '{}': {}", url, e)))?;
+        if !query.is_empty() {
+            // Percent-encodes values such as the `owner:branch` head filter
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        http::send_with_retry(|| Self::api_request(client.get(url.clone()), token))
+            .await
+            .map_err(|e| http::request_error("Failed to send request to GitHub API", e))
+    }
+
+    async fn parse_api_response<T: DeserializeOwned>(response: Response) -> Result<T> {
+        let status = response.status();
+        if status == 401 {
+            return Err(Error::auth(
+                "GitHub authentication failed. Check the token with 'gwt auth github test'.",
+            ));
+        }
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::provider(format!(
+                "GitHub API request failed with status {}: {}",
+                status, text
+            )));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| Error::provider(format!("Failed to parse GitHub API response: {}", e)))
     }
 
     fn get_gh_token() -> Option<String> {
@@ -65,10 +366,10 @@ impl GitHubClient {
     }
 
     pub fn has_auth(&self) -> bool {
-        Self::get_gh_token().is_some()
+        !matches!(self.backend, Backend::Unconfigured)
     }
 
-    pub fn get_pull_requests(&self, owner: &str, repo: &str, branch: &str) -> Result<Vec<PullRequest>> {
+    fn gh_get_pull_requests(owner: &str, repo: &str, branch: &str) -> Result<Vec<PullRequest>> {
         // Use gh CLI instead of HTTP API
         let output = std::process::Command::new("gh")
             .args([
@@ -81,7 +382,7 @@ impl GitHubClient {
                 "--state",
                 "all",
                 "--json",
-                "number,title,state,url,isDraft",
+                "number,title,state,url,isDraft,baseRefName,author,updatedAt",
             ])
             .output()
             .map_err(|e| Error::provider(format!("Failed to execute gh command: {}", e)))?;
@@ -112,11 +413,14 @@ impl GitHubClient {
                 state: pr.state,
                 html_url: pr.url,
                 draft: pr.is_draft,
+                author: pr.author.map(|author| author.login),
+                base: pr.base_ref_name,
+                updated_at: pr.updated_at,
             })
             .collect())
     }
 
-    pub fn get_all_pull_requests(&self, owner: &str, repo: &str) -> Result<Vec<(PullRequest, String)>> {
+    fn gh_get_all_pull_requests(owner: &str, repo: &str, max: Option<usize>) -> Result<Vec<(PullRequest, String)>> {
         // Fetch all open pull requests with branch information
         let output = std::process::Command::new("gh")
             .args([
@@ -127,9 +431,9 @@ impl GitHubClient {
                 "--state",
                 "open",
                 "--json",
-                "number,title,state,url,isDraft,headRefName",
+                "number,title,state,url,isDraft,headRefName,baseRefName,author,updatedAt",
                 "--limit",
-                "100",
+                &max.unwrap_or(GH_NO_LIMIT).to_string(),
             ])
             .output()
             .map_err(|e| Error::provider(format!("Failed to execute gh command: {}", e)))?;
@@ -161,12 +465,61 @@ impl GitHubClient {
                     state: pr.state,
                     html_url: pr.url,
                     draft: pr.is_draft,
+                    author: pr.author.map(|author| author.login),
+                    base: pr.base_ref_name,
+                    updated_at: pr.updated_at,
                 };
                 (pull_request, pr.head_ref_name)
             })
             .collect())
     }
 
+    /// Create a pull request with `gh pr create` and return its URL
+    fn gh_create_pull_request(
+        owner: &str,
+        repo: &str,
+        head: &str,
+        base: &str,
+        title: &str,
+        draft: bool,
+    ) -> Result<String> {
+        let repo_arg = format!("{}/{}", owner, repo);
+        let mut args = vec![
+            "pr", "create", "--repo", &repo_arg, "--head", head, "--base", base, "--title", title, "--body", "",
+        ];
+        if draft {
+            args.push("--draft");
+        }
+
+        let output = std::process::Command::new("gh")
+            .args(&args)
+            .output()
+            .map_err(|e| Error::provider(format!("Failed to execute gh command: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("not authenticated") || stderr.contains("authentication") {
+                return Err(Error::auth(
+                    "GitHub authentication failed. Run 'gh auth login' to authenticate.",
+                ));
+            }
+            return Err(Error::provider(format!(
+                "Failed to create pull request: {}",
+                stderr.trim()
+            )));
+        }
+
+        // gh prints the new PR's URL as the last line of stdout
+        let stdout = String::from_utf8(output.stdout)?;
+        stdout
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| line.starts_with("http"))
+            .map(str::to_string)
+            .ok_or_else(|| Error::provider("gh did not return a pull request URL"))
+    }
+
     pub fn parse_github_url(url: &str) -> Option<(String, String)> {
         // Parse both HTTPS and SSH URLs
         if let Some(captures) = url.strip_prefix("https://github.com/") {
@@ -184,10 +537,54 @@ impl GitHubClient {
     }
 }
 
+/// Extract the `rel="next"` URL from a GitHub `Link` response header
+fn next_page_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|entry| {
+        let (url, params) = entry.split_once(';')?;
+        let is_next = params.split(';').any(|param| param.trim() == r#"rel="next""#);
+        let url = url.trim().strip_prefix('<')?.strip_suffix('>')?;
+        is_next.then(|| url.to_string())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_next_page_link() {
+        let header = r#"<https://api.github.com/repositories/1/pulls?state=open&per_page=100&page=2>; rel="next", <https://api.github.com/repositories/1/pulls?state=open&per_page=100&page=5>; rel="last""#;
+        assert_eq!(
+            next_page_link(header).as_deref(),
+            Some("https://api.github.com/repositories/1/pulls?state=open&per_page=100&page=2")
+        );
+
+        let last = r#"<https://api.github.com/repositories/1/pulls?page=4>; rel="prev", <https://api.github.com/repositories/1/pulls?page=1>; rel="first""#;
+        assert_eq!(next_page_link(last), None);
+        assert_eq!(next_page_link(""), None);
+    }
+
+    #[test]
+    fn test_api_pull_request_states() {
+        let json = r#"[
+            {"number": 1, "title": "Open", "state": "open", "html_url": "u1", "draft": true, "merged_at": null, "head": {"ref": "feature/a"}, "base": {"ref": "main"}, "user": {"login": "octocat"}},
+            {"number": 2, "title": "Merged", "state": "closed", "html_url": "u2", "merged_at": "2025-01-01T00:00:00Z", "head": {"ref": "feature/b"}}
+        ]"#;
+        let prs: Vec<PullRequest> = serde_json::from_str::<Vec<ApiPullRequest>>(json)
+            .unwrap()
+            .into_iter()
+            .map(PullRequest::from)
+            .collect();
+        assert_eq!(prs[0].state, "OPEN");
+        assert!(prs[0].draft);
+        assert_eq!(prs[0].author.as_deref(), Some("octocat"));
+        assert_eq!(prs[0].base.as_deref(), Some("main"));
+        assert_eq!(prs[1].author, None);
+        assert_eq!(prs[1].base, None);
+        assert_eq!(prs[1].state, "MERGED");
+        assert!(!prs[1].draft);
+    }
+
     #[test]
     fn test_parse_github_url() {
         let test_cases = vec![
@@ -0,0 +1,66 @@
+use std::env;
+
+use crate::error::{Error, Result};
+use crate::token_store::{TokenLocation, TokenStore};
+
+const SERVICE_NAME: &str = "git-worktree-cli-github";
+const KEYRING_USER: &str = "token";
+const TOKEN_ENV_VAR: &str = "GITHUB_TOKEN";
+
+/// GitHub token used when the `gh` CLI isn't available
+pub struct GitHubAuth {
+    store: TokenStore,
+}
+
+impl GitHubAuth {
+    pub fn new() -> Result<Self> {
+        Ok(GitHubAuth {
+            store: TokenStore::new(SERVICE_NAME, KEYRING_USER),
+        })
+    }
+
+    pub fn get_token(&self) -> Result<String> {
+        // Check environment variable
+        if let Ok(token) = env::var(TOKEN_ENV_VAR) {
+            if !token.is_empty() {
+                return Ok(token);
+            }
+        }
+
+        // Then check the keyring (or token file)
+        self.store.get()?.map(|(token, _)| token).ok_or_else(|| {
+            Error::auth(format!(
+                "No GitHub token found. Run 'gh auth login', set {}, or run 'gwt auth github setup'.",
+                TOKEN_ENV_VAR
+            ))
+        })
+    }
+
+    /// Where the token comes from (environment, keyring, or token file), if there is one
+    pub fn token_source(&self) -> Option<String> {
+        if env::var(TOKEN_ENV_VAR).is_ok_and(|token| !token.is_empty()) {
+            return Some(format!("{} (env)", TOKEN_ENV_VAR));
+        }
+        self.store.location().map(|location| location.to_string())
+    }
+
+    /// Store the token in the keyring (or the token file without one), returning where it went
+    pub fn store_token(&self, token: &str) -> Result<TokenLocation> {
+        self.store.set(token)
+    }
+}
+
+pub fn display_setup_instructions() {
+    println!("Setting up GitHub authentication\n");
+    println!("Option 1 (recommended): use the gh CLI");
+    println!("   gh auth login\n");
+    println!("Option 2: use a personal access token (for machines without gh)");
+    println!("1. Create a token at https://github.com/settings/tokens");
+    println!("   (fine-grained: Pull requests read/write, Metadata read)\n");
+    println!("2. Store it in the system keyring (prompts for the token):");
+    println!("   gwt auth github setup");
+    println!("   or pipe it in: gwt auth github setup --token-stdin < token.txt\n");
+    println!("   or set an environment variable:");
+    println!("   export {}=YOUR_TOKEN", TOKEN_ENV_VAR);
+    println!("\nVerify with: gwt auth github test");
+}
@@ -1,10 +1,41 @@
 use colored::Colorize;
+use std::fs;
+use std::io::ErrorKind;
 use std::path::Path;
-use std::process::{Command, Stdio};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::config::GitWorktreeConfig;
 use crate::error::{Error, Result};
+use crate::git;
+use crate::{message, output, status};
 
+/// Per-worktree env file whose values override those from `hookEnvFile`
+///
+/// Only read while git doesn't track it: a committed copy would let anyone who can push
+/// a branch set `PATH` or `LD_PRELOAD` for the hooks of whoever checks it out.
+const WORKTREE_ENV_FILE: &str = ".gwt.env";
+
+/// Process-wide switch set from `--no-hooks`
+static HOOKS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Disable (or re-enable) hook execution for the rest of the process
+pub fn set_disabled(disabled: bool) {
+    HOOKS_DISABLED.store(disabled, Ordering::Relaxed);
+}
+
+fn hooks_disabled() -> bool {
+    HOOKS_DISABLED.load(Ordering::Relaxed)
+}
+
+/// Run the configured hooks of `hook_type` in `working_directory`.
+///
+/// `variables` are supplied by the caller (`branchName`, `worktreePath`, `projectRoot`).
+/// `mainBranch`, `repositoryUrl` and `sourceControl` are added from the loaded config;
+/// caller-supplied values take precedence. Hook commands also get the environment from
+/// `hookEnvFile` and the worktree's `.gwt.env` (see [`hook_environment`]).
 pub fn execute_hooks(hook_type: &str, working_directory: &Path, variables: &[(&str, &str)]) -> Result<()> {
     // Find the config file
     let config = match GitWorktreeConfig::find_config()? {
@@ -15,46 +46,41 @@ pub fn execute_hooks(hook_type: &str, working_directory: &Path, variables: &[(&s
         }
     };
 
-    let hooks = match &config.hooks {
-        Some(hooks) => hooks,
-        None => return Ok(()),
-    };
-
-    let hook_commands = match hook_type {
-        "postAdd" => &hooks.post_add,
-        "preRemove" => &hooks.pre_remove,
-        "postRemove" => &hooks.post_remove,
+    let hook_commands = match configured_hooks(&config, hook_type) {
+        Some(commands) if !commands.is_empty() => commands,
         _ => return Ok(()),
     };
 
-    let hook_commands = match hook_commands {
-        Some(commands) => commands,
-        None => return Ok(()),
-    };
-
-    if hook_commands.is_empty() {
+    if hooks_disabled() {
+        status!("{}", format!("Skipping {} hooks (--no-hooks)", hook_type).dimmed());
         return Ok(());
     }
 
-    println!("{}", format!("🪝 Running {} hooks...", hook_type).cyan());
+    let all_variables = with_config_variables(&config, variables);
+    let env = hook_environment(&config, variables);
+
+    let timeout = config.hooks_timeout_secs.map(Duration::from_secs);
+    let fail_fast = config.hooks_fail_fast.unwrap_or(false);
+
+    status!("{}", format!("🪝 Running {} hooks...", hook_type).cyan());
 
     for hook in hook_commands {
-        // Replace variables in the hook command
-        let mut command = hook.clone();
-        for (var_name, var_value) in variables {
-            let placeholder = format!("${{{}}}", var_name);
-            command = command.replace(&placeholder, var_value);
-        }
+        let command = substitute_variables(hook, &all_variables);
 
-        println!("   {}", format!("Executing: {}", command).blue());
+        status!("   {}", format!("Executing: {}", command).blue());
 
         // Execute with streaming output - this is the key improvement!
-        match execute_command_streaming(&command, working_directory) {
+        match execute_command_streaming(&command, working_directory, &env, timeout) {
             Ok(()) => {
-                println!("   {}", "✓ Hook completed successfully".green());
+                status!("   {}", "✓ Hook completed successfully".green());
+            }
+            Err(e) if fail_fast || is_pre_hook(hook_type) => {
+                // Pre hooks act as guards (as does every hook with hooksFailFast): a failure aborts the operation
+                message!("   {}", format!("❌ Hook failed: {}", e).red());
+                return Err(Error::hook(format!("{} hook '{}' failed: {}", hook_type, command, e)));
             }
             Err(e) => {
-                println!("   {}", format!("⚠️  Hook failed: {}", e).yellow());
+                message!("   {}", format!("⚠️  Hook failed: {}", e).yellow());
                 // Continue with other hooks even if one fails
             }
         }
@@ -63,19 +89,202 @@ pub fn execute_hooks(hook_type: &str, working_directory: &Path, variables: &[(&s
     Ok(())
 }
 
-fn execute_command_streaming(command: &str, working_directory: &Path) -> Result<()> {
+/// Print the hooks of `hook_type` that would run, with variables substituted, without
+/// running them (for `--dry-run`)
+pub fn print_planned_hooks(hook_type: &str, variables: &[(&str, &str)]) -> Result<()> {
+    let config = GitWorktreeConfig::find_config()?.map(|(_, config)| config);
+    let commands = config
+        .as_ref()
+        .and_then(|config| configured_hooks(config, hook_type))
+        .filter(|commands| !commands.is_empty());
+
+    match (config.as_ref(), commands) {
+        (Some(config), Some(commands)) if !hooks_disabled() => {
+            let all_variables = with_config_variables(config, variables);
+            println!("  {}:", format!("{} hooks", hook_type).dimmed());
+            for hook in commands {
+                println!("    {}", substitute_variables(hook, &all_variables));
+            }
+        }
+        (_, Some(_)) => println!("  {}: skipped (--no-hooks)", format!("{} hooks", hook_type).dimmed()),
+        _ => println!("  {}: none", format!("{} hooks", hook_type).dimmed()),
+    }
+    Ok(())
+}
+
+/// Variables to export to hook commands: those in `hookEnvFile` (relative to `projectRoot`),
+/// then those in `<worktreePath>/.gwt.env`, which win when both set the same key
+///
+/// A `.gwt.env` tracked by git is skipped with a warning.
+fn hook_environment(config: &GitWorktreeConfig, variables: &[(&str, &str)]) -> Vec<(String, String)> {
+    let variable = |name: &str| {
+        variables
+            .iter()
+            .find(|(existing, _)| *existing == name)
+            .map(|(_, value)| Path::new(*value))
+    };
+
+    let mut files = Vec::new();
+    if let (Some(file), Some(root)) = (config.hook_env_file.as_deref(), variable("projectRoot")) {
+        files.push((root.join(file), true));
+    }
+    if let Some(worktree) = variable("worktreePath") {
+        if worktree.join(WORKTREE_ENV_FILE).is_file() && git::is_tracked(worktree, WORKTREE_ENV_FILE) {
+            message!(
+                "{}",
+                format!(
+                    "⚠️  Ignoring {}: it is tracked by git; keep it untracked (list it in .gitignore)",
+                    worktree.join(WORKTREE_ENV_FILE).display()
+                )
+                .yellow()
+            );
+        } else {
+            files.push((worktree.join(WORKTREE_ENV_FILE), false));
+        }
+    }
+
+    let mut env: Vec<(String, String)> = Vec::new();
+    for (path, configured) in files {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            // The per-worktree file is optional; a configured one that's missing is worth a warning
+            Err(e) if e.kind() == ErrorKind::NotFound && !configured => continue,
+            Err(e) => {
+                message!(
+                    "{}",
+                    format!("⚠️  Could not read hook env file {}: {}", path.display(), e).yellow()
+                );
+                continue;
+            }
+        };
+        for (key, value) in parse_env_file(&content) {
+            env.retain(|(existing, _)| *existing != key);
+            env.push((key, value));
+        }
+    }
+    env
+}
+
+/// Parse `KEY=VALUE` lines, skipping blank lines, `#` comments and lines without a key
+///
+/// Keys and values are trimmed, an `export ` prefix is allowed, and a value wrapped in
+/// matching single or double quotes is unquoted.
+fn parse_env_file(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty() {
+                return None;
+            }
+            let value = value.trim();
+            let value = ['"', '\'']
+                .iter()
+                .find_map(|quote| value.strip_prefix(*quote)?.strip_suffix(*quote))
+                .unwrap_or(value);
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn configured_hooks<'a>(config: &'a GitWorktreeConfig, hook_type: &str) -> Option<&'a Vec<String>> {
+    config.hooks.as_ref()?.get(hook_type)
+}
+
+/// Caller-supplied variables plus `mainBranch`, `repositoryUrl` and `sourceControl`
+/// from the config (caller values take precedence)
+fn with_config_variables<'a>(
+    config: &'a GitWorktreeConfig,
+    variables: &[(&'a str, &'a str)],
+) -> Vec<(&'a str, &'a str)> {
+    let config_variables = [
+        ("mainBranch", config.main_branch.as_str()),
+        ("repositoryUrl", config.repository_url.as_str()),
+        ("sourceControl", config.source_control.as_str()),
+    ];
+    variables
+        .iter()
+        .copied()
+        .chain(
+            config_variables
+                .into_iter()
+                .filter(|(name, _)| !variables.iter().any(|(existing, _)| existing == name)),
+        )
+        .collect()
+}
+
+/// Replace every `${name}` placeholder in a hook command with its value.
+///
+/// All occurrences are replaced, so a variable may appear several times in one command.
+/// Unknown placeholders are left untouched for the shell to interpret.
+fn substitute_variables(hook: &str, variables: &[(&str, &str)]) -> String {
+    let mut command = hook.to_string();
+    for (var_name, var_value) in variables {
+        let placeholder = format!("${{{}}}", var_name);
+        command = command.replace(&placeholder, var_value);
+    }
+    command
+}
+
+fn is_pre_hook(hook_type: &str) -> bool {
+    matches!(hook_type, "preAdd" | "preRemove")
+}
+
+/// How often to check whether a hook with a timeout has exited
+const HOOK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+fn execute_command_streaming(
+    command: &str,
+    working_directory: &Path,
+    env: &[(String, String)],
+    timeout: Option<Duration>,
+) -> Result<()> {
     let mut cmd = Command::new("sh");
     cmd.arg("-c")
         .arg(command)
         .current_dir(working_directory)
-        .stdout(Stdio::inherit())
+        .stdout(output::child_stdout())
         .stderr(Stdio::inherit())
-        .env("FORCE_COLOR", "1");
+        .env("FORCE_COLOR", "1")
+        .envs(env.iter().map(|(key, value)| (key, value)));
+    // A hook with a timeout gets its own process group, so the whole group (not just
+    // `sh`) can be killed: otherwise a `npm install` it started would keep running
+    #[cfg(unix)]
+    if timeout.is_some() {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
 
-    let status = cmd
-        .status()
+    let mut child = cmd
+        .spawn()
         .map_err(|e| Error::hook(format!("Failed to execute hook command: {}", e)))?;
 
+    let status = match timeout {
+        None => child.wait(),
+        Some(timeout) => {
+            let started = Instant::now();
+            loop {
+                match child.try_wait() {
+                    Ok(Some(status)) => break Ok(status),
+                    Ok(None) if started.elapsed() >= timeout => {
+                        // Kill the hook and everything it started, then reap it so it
+                        // doesn't linger as a zombie
+                        kill_hook(&mut child);
+                        let _ = child.wait();
+                        return Err(Error::hook(format!("Command timed out after {}s", timeout.as_secs())));
+                    }
+                    Ok(None) => thread::sleep(HOOK_POLL_INTERVAL),
+                    Err(e) => break Err(e),
+                }
+            }
+        }
+    }
+    .map_err(|e| Error::hook(format!("Failed to wait for hook command: {}", e)))?;
+
     if !status.success() {
         return Err(Error::hook(format!(
             "Command failed with exit code: {:?}",
@@ -85,3 +294,151 @@ fn execute_command_streaming(command: &str, working_directory: &Path) -> Result<
 
     Ok(())
 }
+
+/// Kill a timed-out hook's process group (its pid, see `process_group(0)`)
+#[cfg(unix)]
+fn kill_hook(child: &mut Child) {
+    match libc::pid_t::try_from(child.id()) {
+        // SAFETY: killpg only sends a signal; the group id is the hook's own pid
+        Ok(pgid) => unsafe {
+            libc::killpg(pgid, libc::SIGKILL);
+        },
+        Err(_) => {
+            let _ = child.kill();
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_hook(child: &mut Child) {
+    let _ = child.kill();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_substitute_variables_repeated() {
+        let command = substitute_variables(
+            "echo ${branchName} > ${projectRoot}/${branchName}.txt ${unknown}",
+            &[("branchName", "feature"), ("projectRoot", "/repo")],
+        );
+        assert_eq!(command, "echo feature > /repo/feature.txt ${unknown}");
+    }
+
+    #[test]
+    fn test_hook_timeout_kills_command() {
+        let temp_dir = tempdir().unwrap();
+        let started = Instant::now();
+
+        let result = execute_command_streaming("sleep 10", temp_dir.path(), &[], Some(Duration::from_secs(1)));
+
+        assert!(result.unwrap_err().to_string().contains("timed out after 1s"));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_hook_timeout_kills_background_processes() {
+        let temp_dir = tempdir().unwrap();
+        let marker = temp_dir.path().join("still-running");
+
+        let result = execute_command_streaming(
+            "(sleep 2; touch still-running) & wait",
+            temp_dir.path(),
+            &[],
+            Some(Duration::from_secs(1)),
+        );
+
+        assert!(result.is_err());
+        thread::sleep(Duration::from_millis(2500));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn test_hook_within_timeout_succeeds() {
+        let temp_dir = tempdir().unwrap();
+        let result = execute_command_streaming("true", temp_dir.path(), &[], Some(Duration::from_secs(5)));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_parse_env_file() {
+        let env = parse_env_file(
+            "# secrets\n\nAPI_KEY = abc123 \nexport DB_URL=postgres://x?a=b\nQUOTED=\"two words\"\nSINGLE='x'\nnot a pair\n=orphan\n",
+        );
+        assert_eq!(
+            env,
+            vec![
+                ("API_KEY".to_string(), "abc123".to_string()),
+                ("DB_URL".to_string(), "postgres://x?a=b".to_string()),
+                ("QUOTED".to_string(), "two words".to_string()),
+                ("SINGLE".to_string(), "x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hook_sees_env_file_variables() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().join("project");
+        let worktree = temp_dir.path().join("feature");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(root.join("hooks.env"), "SECRET=shared\nREGION=eu\n").unwrap();
+        fs::write(worktree.join(WORKTREE_ENV_FILE), "SECRET=per-branch\n").unwrap();
+
+        let mut config = GitWorktreeConfig::new(
+            "git@github.com:test/repo.git".to_string(),
+            "main".to_string(),
+            crate::cli::Provider::Github,
+            None,
+            None,
+        );
+        config.hook_env_file = Some("hooks.env".to_string());
+        let env = hook_environment(
+            &config,
+            &[
+                ("worktreePath", worktree.to_str().unwrap()),
+                ("projectRoot", root.to_str().unwrap()),
+            ],
+        );
+
+        execute_command_streaming(
+            "printf '%s %s %s' \"$SECRET\" \"$REGION\" \"$FORCE_COLOR\" > seen.txt",
+            &worktree,
+            &env,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(worktree.join("seen.txt")).unwrap(),
+            "per-branch eu 1"
+        );
+    }
+
+    #[test]
+    fn test_tracked_worktree_env_file_is_ignored() {
+        let temp_dir = tempdir().unwrap();
+        let worktree = temp_dir.path();
+        let git = |args: &[&str]| {
+            let status = Command::new("git").args(args).current_dir(worktree).status().unwrap();
+            assert!(status.success(), "git {:?}", args);
+        };
+        git(&["init", "-q"]);
+        fs::write(worktree.join(WORKTREE_ENV_FILE), "PATH=/tmp/evil\n").unwrap();
+        let config = GitWorktreeConfig::new(
+            "git@github.com:test/repo.git".to_string(),
+            "main".to_string(),
+            crate::cli::Provider::Github,
+            None,
+            None,
+        );
+        let variables = [("worktreePath", worktree.to_str().unwrap())];
+
+        assert_eq!(hook_environment(&config, &variables).len(), 1);
+        git(&["add", WORKTREE_ENV_FILE]);
+        assert!(hook_environment(&config, &variables).is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+//! Client setup and retry helper for provider HTTP requests
+//!
+//! Every client gives up on a request after a timeout so a hung connection can't
+//! stall `gwt list`. Server errors (5xx), connection failures and timeouts are
+//! retried with exponential backoff; any other response, including 401/404, is
+//! returned as-is for the caller to handle.
+
+use reqwest::{Client, RequestBuilder, Response};
+use std::time::Duration;
+
+use crate::error::Error;
+
+/// Environment variable overriding how many times a failed request is retried
+pub const HTTP_RETRIES_ENV: &str = "GWT_HTTP_RETRIES";
+
+/// Environment variable overriding the per-request timeout, in seconds
+pub const HTTP_TIMEOUT_ENV: &str = "GWT_HTTP_TIMEOUT";
+
+/// Retries after the first attempt (3 attempts in total)
+const DEFAULT_RETRIES: u32 = 2;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Upper bound for establishing the connection (the request timeout applies when lower)
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+const BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// HTTP client for provider APIs, with the timeout from `GWT_HTTP_TIMEOUT`
+pub fn client() -> Client {
+    build_client(timeout())
+}
+
+fn build_client(timeout: Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .connect_timeout(timeout.min(CONNECT_TIMEOUT))
+        .build()
+        .unwrap_or_else(|_| Client::new())
+}
+
+/// Per-request timeout, from `GWT_HTTP_TIMEOUT` or the default
+pub fn timeout() -> Duration {
+    parse_timeout(std::env::var(HTTP_TIMEOUT_ENV).ok().as_deref())
+}
+
+fn parse_timeout(value: Option<&str>) -> Duration {
+    value
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&secs: &u64| secs > 0)
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TIMEOUT)
+}
+
+/// A network error for a request that couldn't be sent, e.g. "Failed to send request
+/// to Gitea API", calling out timeouts since their reqwest message is terse
+pub fn request_error(context: &str, e: reqwest::Error) -> Error {
+    if e.is_timeout() {
+        Error::network(format!(
+            "{}: request timed out after {}s (set {} to wait longer)",
+            context,
+            timeout().as_secs(),
+            HTTP_TIMEOUT_ENV
+        ))
+    } else {
+        Error::network(format!("{}: {}", context, e))
+    }
+}
+
+/// Number of retries, from `GWT_HTTP_RETRIES` or the default
+pub fn retries() -> u32 {
+    parse_retries(std::env::var(HTTP_RETRIES_ENV).ok().as_deref())
+}
+
+fn parse_retries(value: Option<&str>) -> u32 {
+    value.and_then(|v| v.trim().parse().ok()).unwrap_or(DEFAULT_RETRIES)
+}
+
+/// Send a request, retrying transient failures
+///
+/// `build` is called once per attempt since a `RequestBuilder` can only be sent once.
+pub async fn send_with_retry<F>(build: F) -> reqwest::Result<Response>
+where
+    F: Fn() -> RequestBuilder,
+{
+    send_with_backoff(build, retries(), BASE_DELAY).await
+}
+
+async fn send_with_backoff<F>(build: F, retries: u32, base_delay: Duration) -> reqwest::Result<Response>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut attempt = 0;
+    loop {
+        let result = build().send().await;
+        let transient = match &result {
+            Ok(response) => response.status().is_server_error(),
+            Err(e) => e.is_connect() || e.is_timeout(),
+        };
+        if !transient || attempt >= retries {
+            return result;
+        }
+        tokio::time::sleep(base_delay * 2u32.saturating_pow(attempt)).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve one canned status per connection, in order
+    fn serve(statuses: Vec<u16>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_parse_retries() {
+        assert_eq!(parse_retries(None), DEFAULT_RETRIES);
+        assert_eq!(parse_retries(Some("5")), 5);
+        assert_eq!(parse_retries(Some("0")), 0);
+        assert_eq!(parse_retries(Some("lots")), DEFAULT_RETRIES);
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors() {
+        let url = serve(vec![502, 503, 200]);
+        let client = reqwest::Client::new();
+        let response = send_with_backoff(|| client.get(&url), 2, Duration::from_millis(1))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_retries() {
+        let url = serve(vec![502, 502]);
+        let client = reqwest::Client::new();
+        let response = send_with_backoff(|| client.get(&url), 1, Duration::from_millis(1))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 502);
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout(None), DEFAULT_TIMEOUT);
+        assert_eq!(parse_timeout(Some("3")), Duration::from_secs(3));
+        assert_eq!(parse_timeout(Some("0")), DEFAULT_TIMEOUT);
+        assert_eq!(parse_timeout(Some("soon")), DEFAULT_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn test_non_routable_address_fails_fast() {
+        // Depending on the network this times out on connect or is refused outright;
+        // either way it must not hang
+        let client = build_client(Duration::from_secs(1));
+        let started = std::time::Instant::now();
+        let result = send_with_backoff(|| client.get("http://10.255.255.1:81/"), 0, Duration::from_millis(1)).await;
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_silent_server_times_out() {
+        // Accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let _connections: Vec<_> = listener.incoming().collect();
+        });
+
+        let client = build_client(Duration::from_millis(300));
+        let e = send_with_backoff(|| client.get(&url), 0, Duration::from_millis(1))
+            .await
+            .unwrap_err();
+        assert!(e.is_timeout());
+        assert!(request_error("Failed to send request to Gitea API", e)
+            .to_string()
+            .contains("Failed to send request to Gitea API: request timed out after"));
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_client_errors() {
+        let url = serve(vec![404, 200]);
+        let client = reqwest::Client::new();
+        let response = send_with_backoff(|| client.get(&url), 2, Duration::from_millis(1))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+    }
+}
@@ -7,6 +7,7 @@ pub mod bitbucket_api;
 pub mod bitbucket_auth;
 pub mod bitbucket_data_center_api;
 pub mod bitbucket_data_center_auth;
+pub mod cache;
 pub mod cli;
 pub mod commands;
 pub mod completions;
@@ -15,8 +16,14 @@ pub mod constants;
 pub mod core;
 pub mod error;
 pub mod git;
+pub mod gitea;
+pub mod gitea_auth;
 pub mod github;
+pub mod github_auth;
 pub mod hooks;
+pub mod http;
+pub mod output;
+pub mod token_store;
 
 // Re-export commonly used types
 pub use cli::{Cli, Commands};
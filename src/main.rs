@@ -2,14 +2,50 @@ use clap::{CommandFactory, Parser};
 use colored::Colorize;
 
 use git_worktree_cli::{
-    cli::{AuthAction, Cli, Commands, CompletionAction},
-    commands::{add, auth, config, init, list, remove},
+    cli::{AuthAction, Cli, ColorMode, Commands, CompletionAction},
+    commands::{
+        add, auth, config, doctor, fetch, init, list, lock, move_worktree, pr, projects, prune, remove, rename, status,
+        sync,
+    },
     completions,
     error::Result,
+    hooks,
+    output::{self, Verbosity},
 };
 
-fn main() -> Result<()> {
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("Error: {}", e);
+        std::process::exit(e.exit_code());
+    }
+}
+
+fn run() -> Result<()> {
+    // Hidden helper for dynamic shell completion; handled before clap so it stays out of
+    // the generated completion scripts and --help (clap's bash generator can't handle "__" names)
+    if std::env::args().nth(1).as_deref() == Some(completions::COMPLETE_BRANCHES_COMMAND) {
+        completions::print_worktree_branches();
+        return Ok(());
+    }
+
+    // Diagnostic logs for bug reports (RUST_LOG=debug), separate from --verbose
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("off")).init();
+
     let cli = Cli::parse();
+    apply_color_mode(cli.color);
+    hooks::set_disabled(cli.no_hooks);
+    output::set_verbosity(if cli.quiet {
+        Verbosity::Quiet
+    } else if cli.verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    });
+
+    if cli.version {
+        print_version(cli.verbose);
+        return Ok(());
+    }
 
     let command = match cli.command {
         Some(cmd) => cmd,
@@ -20,21 +56,193 @@ fn main() -> Result<()> {
     };
 
     match command {
-        Commands::Init { local } => {
-            init::run(local)?;
+        Commands::Init {
+            repo_url,
+            local,
+            remote,
+            bare,
+            provider,
+            branch,
+            force,
+            nested,
+        } => {
+            init::run(
+                local,
+                repo_url.as_deref(),
+                remote.as_deref(),
+                bare,
+                provider,
+                branch.as_deref(),
+                match (force, nested) {
+                    (_, true) => init::ExistingProject::Nest,
+                    (true, _) => init::ExistingProject::Reinitialize,
+                    _ => init::ExistingProject::Refuse,
+                },
+            )?;
+        }
+        Commands::Clone {
+            repo_url,
+            dir,
+            local,
+            remote,
+            bare,
+            provider,
+            branch,
+        } => {
+            init::run_clone(
+                &repo_url,
+                dir.as_deref(),
+                local,
+                remote.as_deref(),
+                bare,
+                provider,
+                branch.as_deref(),
+            )?;
+        }
+        Commands::Add {
+            branch_name,
+            from,
+            track,
+            no_track,
+            path,
+            dry_run,
+            print_path,
+            detach,
+            name,
+            checkout_pr,
+            merge_ref,
+            install_deps,
+            push,
+            no_push,
+        } => {
+            let track = match (track, no_track) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            let push = match (push, no_push) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            let options = add::AddOptions {
+                from,
+                track,
+                path,
+                dry_run,
+                print_path,
+                install_deps,
+                push,
+            };
+            if let Some(number) = checkout_pr {
+                add::run_pull_request(number, merge_ref, &options)?;
+            } else if let Some(commit) = detach {
+                add::run_detached(&commit, name.as_deref(), &options)?;
+            } else {
+                add::run(branch_name.as_deref().unwrap_or_default(), &options)?;
+            }
+        }
+        Commands::List {
+            local,
+            remote_only,
+            json,
+            refresh,
+            sort,
+            filter,
+            group_by,
+            stale,
+            with_authors,
+            hide_drafts,
+            offline,
+            limit,
+            urls_only,
+            show_commit,
+            no_pr,
+            format,
+            since,
+        } => {
+            list::run(list::ListOptions {
+                local_only: local,
+                remote_only,
+                json,
+                refresh,
+                sort,
+                filter,
+                group_by,
+                stale,
+                with_authors,
+                hide_drafts,
+                offline,
+                limit,
+                urls_only,
+                show_commit,
+                no_pr,
+                format,
+                since,
+            })?;
+        }
+        Commands::Remove {
+            branch_names,
+            force,
+            yes,
+            all_merged,
+            dry_run,
+            keep_branch,
+            delete_branch,
+            prune_dir,
+        } => {
+            let delete_branch = match (keep_branch, delete_branch) {
+                (true, _) => Some(false),
+                (_, true) => Some(true),
+                _ => None,
+            };
+            let confirm = remove::Confirmation {
+                yes: yes || force,
+                force,
+            };
+            match branch_names.as_slice() {
+                _ if all_merged => remove::run_all_merged(confirm, dry_run, delete_branch, prune_dir)?,
+                [] => remove::run(None, confirm, dry_run, delete_branch, prune_dir)?,
+                [branch_name] => remove::run(Some(branch_name), confirm, dry_run, delete_branch, prune_dir)?,
+                branch_names => remove::run_many(branch_names, confirm, dry_run, delete_branch, prune_dir)?,
+            }
+        }
+        Commands::Lock { branch_name, reason } => {
+            lock::run_lock(branch_name.as_deref(), reason.as_deref())?;
+        }
+        Commands::Unlock { branch_name } => {
+            lock::run_unlock(branch_name.as_deref())?;
+        }
+        Commands::Rename { old_branch, new_branch } => {
+            rename::run(&old_branch, &new_branch)?;
         }
-        Commands::Add { branch_name } => {
-            add::run(&branch_name)?;
+        Commands::Move { branch_name, new_path } => {
+            move_worktree::run(&branch_name, &new_path)?;
         }
-        Commands::List { local } => {
-            list::run(local)?;
+        Commands::Status { fetch } => {
+            status::run(fetch)?;
         }
-        Commands::Remove { branch_name, force } => {
-            remove::run(branch_name.as_deref(), force)?;
+        Commands::Fetch { prune } => {
+            fetch::run(prune)?;
+        }
+        Commands::Prune { dry_run, yes, force } => {
+            prune::run(dry_run, yes, force)?;
+        }
+        Commands::Sync { strategy, autostash } => {
+            sync::run(strategy, autostash)?;
+        }
+        Commands::Pr { action } => {
+            pr::run(action)?;
+        }
+        Commands::Doctor => {
+            doctor::run()?;
+        }
+        Commands::Version => {
+            print_version(cli.verbose);
         }
         Commands::Auth { action } => match action {
-            AuthAction::Github => {
-                auth::run()?;
+            AuthAction::Github { action } => {
+                auth::run_github(action)?;
             }
             AuthAction::BitbucketCloud { action } => {
                 auth::run_bitbucket_cloud(action)?;
@@ -42,9 +250,18 @@ fn main() -> Result<()> {
             AuthAction::BitbucketDataCenter { action } => {
                 auth::run_bitbucket_data_center(action)?;
             }
+            AuthAction::Gitea { action } => {
+                auth::run_gitea(action)?;
+            }
+            AuthAction::Status => {
+                auth::run_status()?;
+            }
         },
-        Commands::Config => {
-            config::run()?;
+        Commands::Projects { prune, yes } => {
+            projects::run(prune, yes)?;
+        }
+        Commands::Config { action } => {
+            config::run(action)?;
         }
         Commands::Completions { action } => {
             handle_completions(action)?;
@@ -54,6 +271,32 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// `gwt <version>`, plus the build details from build.rs with `--verbose`
+fn print_version(verbose: bool) {
+    println!("gwt {}", env!("CARGO_PKG_VERSION"));
+    if verbose {
+        println!("commit: {}", env!("GIT_HASH"));
+        println!("built:  {}", env!("BUILD_DATE"));
+        println!("rustc:  {}", env!("RUSTC_VERSION"));
+    }
+}
+
+/// Apply `--color` before anything is printed
+///
+/// `auto` leaves the decision to `colored` (terminal detection, `CLICOLOR_FORCE`)
+/// except that a non-empty `NO_COLOR` always turns colors off.
+fn apply_color_mode(mode: ColorMode) {
+    match mode {
+        ColorMode::Always => colored::control::set_override(true),
+        ColorMode::Never => colored::control::set_override(false),
+        ColorMode::Auto => {
+            if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+                colored::control::set_override(false);
+            }
+        }
+    }
+}
+
 fn handle_completions(action: Option<CompletionAction>) -> Result<()> {
     match action {
         None => {
@@ -68,6 +311,10 @@ fn handle_completions(action: Option<CompletionAction>) -> Result<()> {
             let shell = shell.unwrap_or_else(|| completions::detect_shell().unwrap_or(clap_complete::Shell::Bash));
             completions::install_completions_for_shell(shell)?;
         }
+        Some(CompletionAction::Uninstall { shell }) => {
+            let shell = shell.unwrap_or_else(|| completions::detect_shell().unwrap_or(clap_complete::Shell::Bash));
+            completions::uninstall_completions_for_shell(shell)?;
+        }
     }
     Ok(())
 }
@@ -0,0 +1,136 @@
+//! Output verbosity shared by all commands
+//!
+//! Set once from the global `--quiet` / `--verbose` flags in `main.rs`.
+
+use std::io::{self, IsTerminal, Write};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Only errors, prompts, and warnings
+    Quiet,
+    Normal,
+    /// Also echo every git command before running it
+    Verbose,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Normal,
+    }
+}
+
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+pub fn is_verbose() -> bool {
+    verbosity() == Verbosity::Verbose
+}
+
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+
+/// Keep stdout for a single machine-readable result (e.g. `gwt add --print-path`);
+/// messages, git output and hook output go to stderr instead
+pub fn reserve_stdout() {
+    STDOUT_RESERVED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_stdout_reserved() -> bool {
+    STDOUT_RESERVED.load(Ordering::Relaxed)
+}
+
+/// Where a streamed child process (git, hooks) should write its stdout
+pub fn child_stdout() -> Stdio {
+    if is_stdout_reserved() {
+        Stdio::from(io::stderr())
+    } else {
+        Stdio::inherit()
+    }
+}
+
+const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Progress indicator drawn on stderr while slow work (e.g. PR lookups) runs
+///
+/// Only animates when stderr is a terminal and output isn't `--quiet`; otherwise it
+/// does nothing. Dropping it stops the thread and clears the line, so early returns
+/// and `?` never leave it behind.
+pub struct Spinner {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Spinner {
+    pub fn start(message: &str) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        if is_quiet() || !io::stderr().is_terminal() {
+            return Self { stop, handle: None };
+        }
+
+        let message = message.to_string();
+        let thread_stop = Arc::clone(&stop);
+        let handle = thread::spawn(move || {
+            let mut stderr = io::stderr();
+            for frame in SPINNER_FRAMES.iter().cycle() {
+                if thread_stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                let _ = write!(stderr, "\r{} {}", frame, message);
+                let _ = stderr.flush();
+                thread::sleep(SPINNER_INTERVAL);
+            }
+            // Erase the spinner line so the results start on a clean line
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        });
+        Self {
+            stop,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// `println!` that moves to stderr while stdout is reserved
+#[macro_export]
+macro_rules! message {
+    ($($arg:tt)*) => {
+        if $crate::output::is_stdout_reserved() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// `println!` for progress and success messages; suppressed by `--quiet`
+#[macro_export]
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_quiet() {
+            $crate::message!($($arg)*);
+        }
+    };
+}
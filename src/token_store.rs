@@ -0,0 +1,250 @@
+//! Where stored provider tokens live: the system keyring, or a file under the config dir
+//!
+//! Headless machines (CI, containers) often have no secret service, so every keyring
+//! call fails there. By default a token is read from and written to the keyring, with
+//! a file as the fallback when the keyring is unavailable. `GWT_TOKEN_STORE=file` or
+//! `GWT_TOKEN_STORE=keyring` picks one store and never touches the other.
+
+use colored::Colorize;
+use keyring::Entry;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::GitWorktreeConfig;
+use crate::error::{Error, Result};
+use crate::message;
+
+/// Environment variable selecting the token store (`keyring` or `file`)
+pub const TOKEN_STORE_ENV: &str = "GWT_TOKEN_STORE";
+
+/// Which stores `GWT_TOKEN_STORE` allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Keyring first, file when the keyring is unavailable (the default)
+    Auto,
+    Keyring,
+    File,
+}
+
+fn mode_from_env(value: Option<&str>) -> Result<Mode> {
+    match value.map(str::trim) {
+        None | Some("") => Ok(Mode::Auto),
+        Some("keyring") => Ok(Mode::Keyring),
+        Some("file") => Ok(Mode::File),
+        Some(other) => Err(Error::auth(format!(
+            "Invalid {} '{}' (expected 'keyring' or 'file')",
+            TOKEN_STORE_ENV, other
+        ))),
+    }
+}
+
+/// Where a stored token was found or written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenLocation {
+    Keyring,
+    File(PathBuf),
+}
+
+impl fmt::Display for TokenLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenLocation::Keyring => write!(f, "keyring"),
+            TokenLocation::File(path) => write!(f, "token file {}", path.display()),
+        }
+    }
+}
+
+/// One stored token, identified like a keyring entry by service and user
+pub struct TokenStore {
+    service: &'static str,
+    user: String,
+}
+
+impl TokenStore {
+    pub fn new(service: &'static str, user: impl Into<String>) -> Self {
+        Self {
+            service,
+            user: user.into(),
+        }
+    }
+
+    /// The stored token and where it came from, if there is one
+    pub fn get(&self) -> Result<Option<(String, TokenLocation)>> {
+        let mode = mode_from_env(std::env::var(TOKEN_STORE_ENV).ok().as_deref())?;
+        if mode != Mode::File {
+            if let Ok(token) = self.entry().and_then(|entry| entry.get_password()) {
+                return Ok(Some((token, TokenLocation::Keyring)));
+            }
+        }
+        if mode != Mode::Keyring {
+            let path = self.file_path()?;
+            if let Some(token) = read_token_file(&path) {
+                return Ok(Some((token, TokenLocation::File(path))));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Where the stored token comes from, if there is one
+    pub fn location(&self) -> Option<TokenLocation> {
+        self.get().ok().flatten().map(|(_, location)| location)
+    }
+
+    /// Store `token`, returning where it went
+    pub fn set(&self, token: &str) -> Result<TokenLocation> {
+        let mode = mode_from_env(std::env::var(TOKEN_STORE_ENV).ok().as_deref())?;
+        if mode != Mode::File {
+            match self.entry().and_then(|entry| entry.set_password(token)) {
+                Ok(()) => {
+                    // A token file left from an earlier fallback would otherwise outlive this one
+                    if mode == Mode::Auto {
+                        let path = self.file_path()?;
+                        if path.exists() {
+                            fs::remove_file(&path).map_err(|e| {
+                                Error::auth(format!("Failed to remove old token file {}: {}", path.display(), e))
+                            })?;
+                        }
+                    }
+                    return Ok(TokenLocation::Keyring);
+                }
+                // No secret service (e.g. headless CI): fall back to the file
+                Err(e @ (keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_)))
+                    if mode == Mode::Auto =>
+                {
+                    message!(
+                        "{}",
+                        format!("⚠️  Keyring unavailable ({}); falling back to a token file", e).yellow()
+                    );
+                }
+                Err(e) => return Err(Error::auth(format!("Failed to store the token in the keyring: {}", e))),
+            }
+        }
+        let path = self.file_path()?;
+        write_token_file(&path, token)
+            .map_err(|e| Error::auth(format!("Failed to write token file {}: {}", path.display(), e)))?;
+        Ok(TokenLocation::File(path))
+    }
+
+    fn entry(&self) -> keyring::Result<Entry> {
+        Entry::new(self.service, &self.user)
+    }
+
+    fn file_path(&self) -> Result<PathBuf> {
+        Ok(token_file_path(
+            &GitWorktreeConfig::global_config_dir()?,
+            self.service,
+            &self.user,
+        ))
+    }
+}
+
+/// `<config dir>/tokens/<service>/<user>`, with each `/`-separated part of the user
+/// (e.g. `workspace/repo`) as a directory level and anything unusual replaced
+fn token_file_path(config_dir: &Path, service: &str, user: &str) -> PathBuf {
+    let sanitize = |part: &str| -> String {
+        let part: String = part
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || "-_.".contains(c) {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if part.is_empty() || part.chars().all(|c| c == '.') {
+            "_".to_string()
+        } else {
+            part
+        }
+    };
+    let mut path = config_dir.join("tokens").join(sanitize(service));
+    for part in user.split('/') {
+        path.push(sanitize(part));
+    }
+    path
+}
+
+fn read_token_file(path: &Path) -> Option<String> {
+    let token = fs::read_to_string(path).ok()?.trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+/// Write the token readable by the owner only (0600, in 0700 directories on Unix)
+fn write_token_file(path: &Path, token: &str) -> std::io::Result<()> {
+    let mut dir_builder = fs::DirBuilder::new();
+    dir_builder.recursive(true);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+        dir_builder.mode(0o700);
+        options.mode(0o600);
+    }
+
+    if let Some(parent) = path.parent() {
+        dir_builder.create(parent)?;
+    }
+    let mut file = options.open(path)?;
+    // The mode only applies to new files; tighten one that already existed
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(token.as_bytes())?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_from_env() {
+        assert_eq!(mode_from_env(None).unwrap(), Mode::Auto);
+        assert_eq!(mode_from_env(Some("")).unwrap(), Mode::Auto);
+        assert_eq!(mode_from_env(Some("file")).unwrap(), Mode::File);
+        assert_eq!(mode_from_env(Some(" keyring ")).unwrap(), Mode::Keyring);
+        assert!(matches!(mode_from_env(Some("vault")), Err(Error::Auth(_))));
+    }
+
+    #[test]
+    fn test_token_file_path() {
+        let dir = Path::new("/config");
+        assert_eq!(
+            token_file_path(dir, "git-worktree-cli-bitbucket", "workspace/repo"),
+            PathBuf::from("/config/tokens/git-worktree-cli-bitbucket/workspace/repo")
+        );
+        assert_eq!(
+            token_file_path(dir, "svc", "https://git.example.com"),
+            PathBuf::from("/config/tokens/svc/https_/_/git.example.com")
+        );
+        // Path traversal in a parsed workspace name stays inside the tokens dir
+        assert_eq!(
+            token_file_path(dir, "svc", "../repo"),
+            PathBuf::from("/config/tokens/svc/_/repo")
+        );
+    }
+
+    #[test]
+    fn test_token_file_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = token_file_path(temp_dir.path(), "svc", "workspace/repo");
+        assert_eq!(read_token_file(&path), None);
+
+        write_token_file(&path, "first").unwrap();
+        write_token_file(&path, "second").unwrap();
+        assert_eq!(read_token_file(&path).as_deref(), Some("second"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
 use predicates::prelude::*;
 use serial_test::serial;
 use std::fs;
+use std::path::PathBuf;
 
 mod test_utils;
 use test_utils::*;
@@ -20,7 +21,7 @@ fn test_gwt_init_existing_repo() {
     create_test_git_repo(&repo_dir, "git@github.com:test/my-repo.git");
 
     // Test gwt init in an existing repo with --local
-    let mut cmd = Command::cargo_bin("gwt").unwrap();
+    let mut cmd = cargo_bin_cmd!("gwt");
     cmd.current_dir(&repo_dir).arg("init").arg("--local");
 
     let output = cmd.assert().success();
@@ -57,7 +58,7 @@ fn test_gwt_init_not_in_git_repo() {
     let temp_path = temp_dir.path();
 
     // Test gwt init outside a git repository - should fail
-    let mut cmd = Command::cargo_bin("gwt").unwrap();
+    let mut cmd = cargo_bin_cmd!("gwt");
     cmd.current_dir(temp_path).arg("init");
 
     cmd.assert()
@@ -67,6 +68,22 @@ fn test_gwt_init_not_in_git_repo() {
     cleanup_test_env(temp_dir);
 }
 
+#[test]
+#[serial]
+fn test_gwt_list_outside_project_exits_with_code_3() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(temp_path).arg("list");
+
+    cmd.assert()
+        .code(3)
+        .stderr(predicate::str::contains("Error: Not in a git-worktree-cli project"));
+
+    cleanup_test_env(temp_dir);
+}
+
 #[test]
 #[serial]
 fn test_gwt_init_no_remote() {
@@ -81,7 +98,7 @@ fn test_gwt_init_no_remote() {
         .expect("Failed to init git repo");
 
     // Test gwt init - should fail because no remote
-    let mut cmd = Command::cargo_bin("gwt").unwrap();
+    let mut cmd = cargo_bin_cmd!("gwt");
     cmd.current_dir(temp_path).arg("init");
 
     cmd.assert()
@@ -93,7 +110,7 @@ fn test_gwt_init_no_remote() {
 
 #[test]
 fn test_gwt_no_args_shows_long_help() {
-    let mut cmd = Command::cargo_bin("gwt").unwrap();
+    let mut cmd = cargo_bin_cmd!("gwt");
 
     cmd.assert()
         .success()
@@ -105,7 +122,7 @@ fn test_gwt_no_args_shows_long_help() {
 
 #[test]
 fn test_gwt_help() {
-    let mut cmd = Command::cargo_bin("gwt").unwrap();
+    let mut cmd = cargo_bin_cmd!("gwt");
     cmd.arg("--help");
 
     cmd.assert()
@@ -120,12 +137,33 @@ fn test_gwt_help() {
 
 #[test]
 fn test_gwt_version() {
-    let mut cmd = Command::cargo_bin("gwt").unwrap();
+    let mut cmd = cargo_bin_cmd!("gwt");
     cmd.arg("--version");
 
     cmd.assert().success().stdout(predicate::str::contains("gwt"));
 }
 
+#[test]
+fn test_gwt_version_verbose() {
+    let version_line = format!("gwt {}\n", env!("CARGO_PKG_VERSION"));
+    cargo_bin_cmd!("gwt")
+        .arg("--version")
+        .assert()
+        .success()
+        .stdout(version_line.clone());
+
+    for args in [["--version", "--verbose"], ["version", "--verbose"]] {
+        cargo_bin_cmd!("gwt")
+            .args(args)
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with(version_line.clone()))
+            .stdout(predicate::str::contains("commit: "))
+            .stdout(predicate::str::contains("built: "))
+            .stdout(predicate::str::contains("rustc:  rustc "));
+    }
+}
+
 #[test]
 #[serial]
 fn test_gwt_init_bitbucket_repo() {
@@ -140,7 +178,7 @@ fn test_gwt_init_bitbucket_repo() {
     create_test_git_repo(&repo_dir, "git@bitbucket.org:workspace/my-bb-repo.git");
 
     // Test gwt init with --local
-    let mut cmd = Command::cargo_bin("gwt").unwrap();
+    let mut cmd = cargo_bin_cmd!("gwt");
     cmd.current_dir(&repo_dir).arg("init").arg("--local");
 
     let output = cmd.assert().success();
@@ -164,7 +202,7 @@ fn test_gwt_init_unsupported_provider() {
     create_test_git_repo(&repo_dir, "git@gitlab.com:user/repo.git");
 
     // Test gwt init - should fail with unsupported provider
-    let mut cmd = Command::cargo_bin("gwt").unwrap();
+    let mut cmd = cargo_bin_cmd!("gwt");
     cmd.current_dir(&repo_dir).arg("init");
 
     cmd.assert()
@@ -174,6 +212,42 @@ fn test_gwt_init_unsupported_provider() {
     cleanup_test_env(temp_dir);
 }
 
+#[test]
+#[serial]
+fn test_gwt_init_with_gitea_provider() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = temp_path.join("my-repo");
+    fs::create_dir(&repo_dir).unwrap();
+
+    // A self-hosted instance can't be recognized from its URL
+    create_test_git_repo(&repo_dir, "git@git.example.com:team/my-repo.git");
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir)
+        .args(["init", "--local", "--provider", "gitea"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Provider: Gitea"));
+
+    let config_content = fs::read_to_string(temp_path.join("git-worktree-config.jsonc")).unwrap();
+    assert!(config_content.contains("\"sourceControl\": \"gitea\""));
+
+    // Codeberg is a known Gitea/Forgejo host
+    let codeberg_dir = temp_path.join("codeberg-repo");
+    fs::create_dir(&codeberg_dir).unwrap();
+    create_test_git_repo(&codeberg_dir, "https://codeberg.org/team/codeberg-repo.git");
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&codeberg_dir).arg("init");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Detected provider: Gitea"));
+
+    cleanup_test_env(temp_dir);
+}
+
 #[test]
 #[serial]
 fn test_config_worktrees_path_derivation() {
@@ -187,7 +261,7 @@ fn test_config_worktrees_path_derivation() {
     create_test_git_repo(&repo_dir, "git@github.com:test/agent-tools.git");
 
     // Initialize
-    let mut cmd = Command::cargo_bin("gwt").unwrap();
+    let mut cmd = cargo_bin_cmd!("gwt");
     cmd.current_dir(&repo_dir).arg("init").arg("--local");
 
     cmd.assert().success();
@@ -204,3 +278,2798 @@ fn test_config_worktrees_path_derivation() {
 
     cleanup_test_env(temp_dir);
 }
+
+#[test]
+#[serial]
+fn test_gwt_add_uses_configured_main_branch() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+
+    // Create a develop branch that is ahead of main and push it
+    run_git(&repo_dir, &["checkout", "-b", "develop"]);
+    fs::write(repo_dir.join("develop.txt"), "develop").unwrap();
+    run_git(&repo_dir, &["add", "."]);
+    run_git(&repo_dir, &["commit", "-m", "Develop commit"]);
+    run_git(&repo_dir, &["push", "origin", "develop"]);
+    run_git(&repo_dir, &["checkout", "main"]);
+    let develop_sha = run_git(&repo_dir, &["rev-parse", "origin/develop"]);
+
+    // Configure develop as the main branch
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "develop");
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["add", "feature/from-develop"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("from 'origin/develop'"));
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/from-develop");
+    assert!(worktree_path.exists(), "Worktree should be created");
+    assert_eq!(run_git(&worktree_path, &["rev-parse", "HEAD"]), develop_sha);
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_from_ref() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+
+    // Tag a commit that main later moves past
+    run_git(&repo_dir, &["tag", "v1.0"]);
+    let tag_sha = run_git(&repo_dir, &["rev-parse", "v1.0"]);
+    fs::write(repo_dir.join("later.txt"), "later").unwrap();
+    run_git(&repo_dir, &["add", "."]);
+    run_git(&repo_dir, &["commit", "-m", "Later commit"]);
+    run_git(&repo_dir, &["push", "origin", "main"]);
+
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir)
+        .args(["add", "hotfix/from-tag", "--from", "v1.0"]);
+
+    cmd.assert().success().stdout(predicate::str::contains("from 'v1.0'"));
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("hotfix/from-tag");
+    assert_eq!(run_git(&worktree_path, &["rev-parse", "HEAD"]), tag_sha);
+
+    // An unknown base ref is rejected before any worktree is created
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir)
+        .args(["add", "hotfix/bad-base", "--from", "no-such-ref"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Base ref 'no-such-ref' does not exist"));
+    assert!(!temp_path.join("my-repo-worktrees").join("hotfix/bad-base").exists());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_no_hooks_skips_post_add() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config_with_hooks(
+        temp_path,
+        "git@github.com:test/my-repo.git",
+        "main",
+        r#"{ "postAdd": ["touch post-add-ran"] }"#,
+    );
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["--no-hooks", "add", "feature/quiet"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Skipping postAdd hooks"));
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/quiet");
+    assert!(worktree_path.exists(), "Worktree should be created");
+    assert!(
+        !worktree_path.join("post-add-ran").exists(),
+        "postAdd hook should not run with --no-hooks"
+    );
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_print_path() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config_with_hooks(
+        temp_path,
+        "git@github.com:test/my-repo.git",
+        "main",
+        r#"{ "postAdd": ["echo hook-says-hello"] }"#,
+    );
+
+    let output = cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/scripted", "--print-path"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // stdout is exactly one line: the path; progress and hook output go to stderr
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stdout.lines().count(), 1, "unexpected stdout: {}", stdout);
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/scripted");
+    assert_eq!(
+        PathBuf::from(stdout.trim()).canonicalize().unwrap(),
+        worktree_path.canonicalize().unwrap()
+    );
+    assert!(stderr.contains("Worktree created at"));
+    assert!(stderr.contains("hook-says-hello"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_checkout_pr_merge_ref() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    // Publish a merge preview the way GitHub does, under refs/pull/<n>/merge
+    run_git(&repo_dir, &["checkout", "-b", "preview"]);
+    fs::write(repo_dir.join("merged.txt"), "merged").unwrap();
+    run_git(&repo_dir, &["add", "."]);
+    run_git(&repo_dir, &["commit", "-m", "Merge preview"]);
+    run_git(&repo_dir, &["push", "origin", "preview:refs/pull/7/merge"]);
+    let merge_sha = run_git(&repo_dir, &["rev-parse", "HEAD"]);
+    run_git(&repo_dir, &["checkout", "main"]);
+    run_git(&repo_dir, &["branch", "-D", "preview"]);
+    let branches_before = run_git(&repo_dir, &["branch", "--list"]);
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "--checkout-pr", "7", "--merge-ref", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("git fetch origin pull/7/merge"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "--checkout-pr", "7", "--merge-ref"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Detached HEAD at"));
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("pr-7-merge");
+    assert_eq!(run_git(&worktree_path, &["rev-parse", "HEAD"]), merge_sha);
+    assert_eq!(run_git(&worktree_path, &["rev-parse", "--abbrev-ref", "HEAD"]), "HEAD");
+
+    // A PR without a merge ref fails with a hint
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "--checkout-pr", "8", "--merge-ref"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("check that PR #8 exists"));
+
+    // It's detached, so removing it deletes no branch
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "pr-7-merge", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no branch to delete"));
+    assert!(!worktree_path.exists());
+    assert_eq!(run_git(&repo_dir, &["branch", "--list"]), branches_before);
+
+    // Bitbucket has no merge ref
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["config", "set", "sourceControl", "bitbucket-cloud"])
+        .assert()
+        .success();
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "--checkout-pr", "7", "--merge-ref"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Bitbucket Cloud doesn't expose pull request refs",
+        ));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_detached() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+    let branches_before = run_git(&repo_dir, &["branch", "--list"]);
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "--detach", "main", "--name", "pinned"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Detached HEAD at"));
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("pinned");
+    assert_eq!(run_git(&worktree_path, &["rev-parse", "--abbrev-ref", "HEAD"]), "HEAD");
+
+    // Without --name the directory is the short SHA
+    let short_sha = run_git(&repo_dir, &["rev-parse", "--short", "main"]);
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "--detach", "main"])
+        .assert()
+        .success();
+    assert!(temp_path.join("my-repo-worktrees").join(&short_sha).exists());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/x", "--detach", "main"])
+        .assert()
+        .failure();
+
+    let output = cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--local", "--json"])
+        .output()
+        .unwrap();
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(entries.as_array().unwrap().len(), 3);
+
+    // Removing a detached worktree doesn't try to delete a branch
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "pinned", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no branch to delete"))
+        .stdout(predicate::str::contains("Failed to delete branch").not());
+    assert!(!worktree_path.exists());
+
+    assert_eq!(run_git(&repo_dir, &["branch", "--list"]), branches_before);
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_commands_from_nested_subdirectory() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/a"])
+        .assert()
+        .success();
+
+    let feature_dir = temp_path.join("my-repo-worktrees").join("feature").join("a");
+    let deep_dir = feature_dir.join("src").join("deep");
+    fs::create_dir_all(&deep_dir).unwrap();
+
+    let output = cargo_bin_cmd!("gwt")
+        .current_dir(&deep_dir)
+        .args(["list", "--local", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    let current: Vec<&str> = entries
+        .iter()
+        .filter(|entry| entry["current"] == true)
+        .map(|entry| entry["branch"].as_str().unwrap())
+        .collect();
+    assert_eq!(current, ["feature/a"]);
+
+    // New worktrees still land under the project's worktrees path
+    cargo_bin_cmd!("gwt")
+        .current_dir(&deep_dir)
+        .args(["add", "feature/b"])
+        .assert()
+        .success();
+    let sibling_dir = temp_path.join("my-repo-worktrees").join("feature").join("b");
+    assert!(sibling_dir.exists());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&deep_dir)
+        .args(["remove", "feature/b", "--force"])
+        .assert()
+        .success();
+    assert!(!sibling_dir.exists());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_remove_multiple_branches() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    for branch in ["feat-a", "feat-b", "feat-c"] {
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["add", branch])
+            .assert()
+            .success();
+    }
+    let worktrees_dir = temp_path.join("my-repo-worktrees");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "feat-a", "feat-b", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("About to remove 2 worktree(s)"))
+        .stdout(predicate::str::contains("✓ Removed 2 worktree(s)"));
+    assert!(!worktrees_dir.join("feat-a").exists());
+    assert!(!worktrees_dir.join("feat-b").exists());
+    assert!(worktrees_dir.join("feat-c").exists());
+
+    // Cancelling after an unknown name still exits non-zero
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "missing", "feat-c"])
+        .write_stdin("n\n")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Removal cancelled"))
+        .stderr(predicate::str::contains("Failed to resolve 1 of 2 worktree(s)"));
+    assert!(worktrees_dir.join("feat-c").exists());
+
+    // An unknown name is reported, the rest are still removed, and the exit code is non-zero
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "missing", "feat-c", "--force"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Skipping 'missing'"))
+        .stderr(predicate::str::contains("Failed to remove 1 of 2 worktree(s)"));
+    assert!(!worktrees_dir.join("feat-c").exists());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_hook_receives_project_root() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config_with_hooks(
+        temp_path,
+        "git@github.com:test/my-repo.git",
+        "main",
+        r#"{ "postAdd": ["echo ${projectRoot} ${mainBranch} > hook-vars.txt"] }"#,
+    );
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["add", "feature/vars"]);
+    cmd.assert().success();
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/vars");
+    let output = fs::read_to_string(worktree_path.join("hook-vars.txt")).unwrap();
+    let expected_root = repo_dir.canonicalize().unwrap();
+    assert_eq!(output.trim(), format!("{} main", expected_root.display()));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_failing_pre_add_hook_aborts() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config_with_hooks(
+        temp_path,
+        "git@github.com:test/my-repo.git",
+        "main",
+        r#"{ "preAdd": ["exit 1"], "postAdd": ["touch post-add-ran"] }"#,
+    );
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["add", "feature/blocked"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("preAdd hook 'exit 1' failed"));
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/blocked");
+    assert!(!worktree_path.exists(), "Worktree should not be created");
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_remove_failing_pre_remove_hook_aborts() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config_with_hooks(
+        temp_path,
+        "git@github.com:test/my-repo.git",
+        "main",
+        r#"{ "preRemove": ["exit 1"] }"#,
+    );
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/keep"])
+        .assert()
+        .success();
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["remove", "feature/keep", "--force"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("preRemove hook 'exit 1' failed"));
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/keep");
+    assert!(worktree_path.exists(), "Worktree should not be removed");
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_and_remove_dry_run() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config_with_hooks(
+        temp_path,
+        "git@github.com:test/my-repo.git",
+        "main",
+        r#"{ "preAdd": ["touch pre-${branchName}"], "postAdd": ["npm install"], "postRemove": ["echo bye ${branchName}"] }"#,
+    );
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/dry");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/dry", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "git worktree add --no-track {} -b feature/dry origin/main",
+            worktree_path.display()
+        )))
+        .stdout(predicate::str::contains("touch pre-feature/dry"))
+        .stdout(predicate::str::contains("npm install"));
+    assert!(!worktree_path.exists());
+    assert!(!temp_path.join("my-repo-worktrees").exists());
+    assert!(!repo_dir.join("pre-feature").exists());
+
+    // Read-only resolution still fails early
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/dry", "--from", "no-such-ref", "--dry-run"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Base ref 'no-such-ref' does not exist"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["--no-hooks", "add", "feature/dry"])
+        .assert()
+        .success();
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "feature/dry", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("git branch -d feature/dry"))
+        .stdout(predicate::str::contains("echo bye feature/dry"));
+    assert!(worktree_path.exists(), "Dry run should not remove the worktree");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "--all-merged", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature/dry"))
+        .stdout(predicate::str::contains("nothing was removed"));
+    assert!(worktree_path.exists());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_validates_branch_name() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature branch"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'feature branch' is not a valid branch name"));
+
+    // A pasted full ref is reduced to the branch name
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "refs/heads/feature/pasted"])
+        .assert()
+        .success();
+    assert!(temp_path.join("my-repo-worktrees").join("feature/pasted").exists());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_branch_already_checked_out() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/twice"])
+        .assert()
+        .success();
+
+    // The second add points at the worktree that already has the branch
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/twice");
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/twice"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'feature/twice' is already checked out at"))
+        .stderr(predicate::str::contains(format!(
+            "cd {}",
+            worktree_path.canonicalize().unwrap().display()
+        )));
+
+    // Same for the main branch, checked out in the repository itself
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "main"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'main' is already checked out at"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_remove_force_is_non_interactive() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/unmerged"])
+        .assert()
+        .success();
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/unmerged");
+    run_git(&worktree_path, &["commit", "--allow-empty", "-m", "Unmerged work"]);
+
+    // No stdin: both the confirmation and the force-delete prompt must be skipped
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "feature/unmerged", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Proceeding without confirmation"))
+        .stdout(predicate::str::contains("Are you sure").not());
+    assert!(!worktree_path.exists());
+    assert!(run_git(&repo_dir, &["branch", "--list", "feature/unmerged"]).is_empty());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_remove_prune_dir() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/leftover"])
+        .assert()
+        .success();
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/leftover");
+
+    // A .git file that no longer points back makes git refuse the removal, even with
+    // --force (standing in for directories git can't clean up)
+    fs::write(worktree_path.join("node_modules.txt"), "build output").unwrap();
+    let elsewhere = temp_path.join("elsewhere");
+    fs::create_dir(&elsewhere).unwrap();
+    fs::write(worktree_path.join(".git"), format!("gitdir: {}\n", elsewhere.display())).unwrap();
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "feature/leftover", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--prune-dir"));
+    assert!(worktree_path.exists());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "feature/leftover", "--force", "--prune-dir"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deleted leftover directory"));
+    assert!(!worktree_path.exists());
+    assert!(!run_git(&repo_dir, &["worktree", "list"]).contains("feature/leftover"));
+    assert!(repo_dir.join("README.md").exists());
+    assert!(elsewhere.exists(), "only the worktree directory itself is deleted");
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_remove_keep_branch() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    let config_path = create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+    let branch_exists = |branch: &str| {
+        std::process::Command::new("git")
+            .args(["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", branch)])
+            .current_dir(&repo_dir)
+            .status()
+            .unwrap()
+            .success()
+    };
+    let add_and_remove = |branch: &str, flags: &[&str]| {
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["add", branch])
+            .assert()
+            .success();
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["remove", branch, "--force"])
+            .args(flags)
+            .assert()
+            .success();
+    };
+
+    add_and_remove("feature/flag-keep", &["--keep-branch"]);
+    assert!(branch_exists("feature/flag-keep"));
+
+    // deleteBranchOnRemove: false keeps branches unless --delete-branch is given
+    let config = fs::read_to_string(&config_path).unwrap();
+    fs::write(
+        &config_path,
+        config.replace("\"mainBranch\"", "\"deleteBranchOnRemove\": false,\n  \"mainBranch\""),
+    )
+    .unwrap();
+
+    add_and_remove("feature/config-keep", &[]);
+    assert!(branch_exists("feature/config-keep"));
+
+    add_and_remove("feature/flag-delete", &["--delete-branch"]);
+    assert!(!branch_exists("feature/flag-delete"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_remove_keeps_branch_checked_out_elsewhere() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/shared"])
+        .assert()
+        .success();
+    // A second checkout of the same branch, only possible with --force
+    let copy_path = temp_path.join("shared-copy");
+    run_git(
+        &repo_dir,
+        &[
+            "worktree",
+            "add",
+            "--force",
+            copy_path.to_str().unwrap(),
+            "feature/shared",
+        ],
+    );
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "shared-copy", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("would be preserved (still checked out at"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "shared-copy", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("preserved - still checked out at"));
+    assert!(!copy_path.exists());
+    run_git(
+        &repo_dir,
+        &["rev-parse", "--verify", "--quiet", "refs/heads/feature/shared"],
+    );
+
+    // Once the last checkout goes, the branch goes with it
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "feature/shared", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Branch deleted: feature/shared"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_protected_branches_config() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    let config_path = create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+    let config = fs::read_to_string(&config_path).unwrap();
+    fs::write(
+        &config_path,
+        config.replace(
+            "\"mainBranch\"",
+            "\"protectedBranches\": [\"main\", \"release/*\"],\n  \"mainBranch\"",
+        ),
+    )
+    .unwrap();
+
+    for branch in ["release/1.0", "develop"] {
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["add", branch])
+            .assert()
+            .success();
+    }
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["rename", "release/1.0", "release/2.0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot rename protected branch 'release/1.0'"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "release/1.0", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("preserved - protected branch"));
+    run_git(
+        &repo_dir,
+        &["rev-parse", "--verify", "--quiet", "refs/heads/release/1.0"],
+    );
+
+    // The configured list replaces the defaults, so develop is no longer protected
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "develop", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Branch deleted: develop"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_sync() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/sync"])
+        .assert()
+        .success();
+    let worktree = temp_path.join("my-repo-worktrees").join("feature/sync");
+
+    // Move origin/main ahead of the feature branch
+    fs::write(repo_dir.join("main.txt"), "main").unwrap();
+    run_git(&repo_dir, &["add", "main.txt"]);
+    run_git(&repo_dir, &["commit", "-m", "Main change"]);
+    run_git(&repo_dir, &["push", "origin", "main"]);
+
+    // A dirty worktree is refused without --autostash
+    fs::write(worktree.join("README.md"), "# Local edit").unwrap();
+    cargo_bin_cmd!("gwt")
+        .current_dir(&worktree)
+        .arg("sync")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("uncommitted change"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&worktree)
+        .args(["sync", "--autostash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Rebased 'feature/sync' onto origin/main"));
+    assert!(worktree.join("main.txt").exists());
+    assert_eq!(fs::read_to_string(worktree.join("README.md")).unwrap(), "# Local edit");
+
+    // Conflicting changes stop the sync with guidance
+    run_git(
+        &worktree,
+        &[
+            "-c",
+            "user.email=t@t.com",
+            "-c",
+            "user.name=T",
+            "commit",
+            "-am",
+            "Feature edit",
+        ],
+    );
+    fs::write(repo_dir.join("README.md"), "# Main edit").unwrap();
+    run_git(&repo_dir, &["commit", "-am", "Main edit"]);
+    run_git(&repo_dir, &["push", "origin", "main"]);
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&worktree)
+        .args(["sync", "--strategy", "merge"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("stopped with conflicts"))
+        .stderr(predicate::str::contains("git merge --continue"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_lock_blocks_remove() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+    let gwt = |args: &[&str]| {
+        let mut cmd = cargo_bin_cmd!("gwt");
+        cmd.current_dir(&repo_dir).args(args);
+        cmd
+    };
+
+    for branch in ["feature/locked", "feature/unlocked"] {
+        gwt(&["add", branch]).assert().success();
+        gwt(&["lock", branch, "--reason", "shared demo"]).assert().success();
+    }
+    let locked_path = temp_path.join("my-repo-worktrees").join("feature/locked");
+
+    gwt(&["list", "--local"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("🔒 shared demo"));
+
+    gwt(&["remove", "feature/locked"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is locked (shared demo)"));
+    assert!(locked_path.exists());
+
+    gwt(&["remove", "feature/locked", "--force"]).assert().success();
+    assert!(!locked_path.exists());
+
+    gwt(&["unlock", "feature/unlocked"]).assert().success();
+    gwt(&["unlock", "feature/unlocked"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not locked"));
+    gwt(&["remove", "feature/unlocked", "--force"]).assert().success();
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_prune_stale_worktree() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/stale"])
+        .assert()
+        .success();
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/stale");
+    fs::remove_dir_all(&worktree_path).unwrap();
+
+    // Dry run reports but keeps the reference
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["prune", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature/stale"))
+        .stdout(predicate::str::contains("1 worktree reference(s) would be pruned"));
+    assert!(run_git(&repo_dir, &["worktree", "list"]).contains("feature/stale"));
+
+    // Real prune removes the reference; declining keeps the branch
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .arg("prune")
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pruned 1 worktree reference(s)"));
+    assert!(!run_git(&repo_dir, &["worktree", "list"]).contains("feature/stale"));
+    assert!(!run_git(&repo_dir, &["branch", "--list", "feature/stale"]).is_empty());
+
+    // --yes deletes the orphaned branch without reading stdin
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/stale-too"])
+        .assert()
+        .success();
+    fs::remove_dir_all(temp_path.join("my-repo-worktrees").join("feature/stale-too")).unwrap();
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["prune", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pruned 1 worktree reference(s)"));
+    assert!(run_git(&repo_dir, &["branch", "--list", "feature/stale-too"]).is_empty());
+
+    // --yes alone keeps a branch with unmerged work; --force deletes it
+    for (branch, flags) in [
+        ("feature/unmerged-kept", &["--yes"][..]),
+        ("feature/unmerged-gone", &["--yes", "--force"][..]),
+    ] {
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["add", branch])
+            .assert()
+            .success();
+        let worktree_path = temp_path.join("my-repo-worktrees").join(branch);
+        run_git(&worktree_path, &["commit", "--allow-empty", "-m", "Unmerged work"]);
+        fs::remove_dir_all(&worktree_path).unwrap();
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .arg("prune")
+            .args(flags)
+            .assert()
+            .success();
+    }
+    assert!(!run_git(&repo_dir, &["branch", "--list", "feature/unmerged-kept"]).is_empty());
+    assert!(run_git(&repo_dir, &["branch", "--list", "feature/unmerged-gone"]).is_empty());
+
+    // remove -y skips the prompts but neither overrides a lock nor force-deletes
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/assume-yes"])
+        .assert()
+        .success();
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/assume-yes");
+    run_git(&worktree_path, &["commit", "--allow-empty", "-m", "Unmerged work"]);
+    run_git(&repo_dir, &["worktree", "lock", worktree_path.to_str().unwrap()]);
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "feature/assume-yes", "-y"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is locked"));
+    run_git(&repo_dir, &["worktree", "unlock", worktree_path.to_str().unwrap()]);
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "feature/assume-yes", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pass --force to delete unmerged branches"));
+    assert!(!worktree_path.exists());
+    assert!(!run_git(&repo_dir, &["branch", "--list", "feature/assume-yes"]).is_empty());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_list_stale() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/old"])
+        .assert()
+        .success();
+    let old_worktree = temp_path.join("my-repo-worktrees").join("feature/old");
+    let status = std::process::Command::new("git")
+        .args(["commit", "--allow-empty", "-m", "Forgotten work"])
+        .env("GIT_COMMITTER_DATE", "2001-01-01T00:00:00Z")
+        .current_dir(&old_worktree)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--local", "--stale", "30"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("⏳ stale ("));
+
+    let output = cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--local", "--json", "--stale", "30"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = entries.as_array().unwrap();
+    // The fresh main worktree comes first and has no staleDays; the old one is last
+    assert_eq!(entries[0]["branch"], "main");
+    assert!(entries[0].get("staleDays").is_none());
+    let last = entries.last().unwrap();
+    assert_eq!(last["branch"], "feature/old");
+    assert!(last["staleDays"].as_u64().unwrap() > 30);
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_list_offline_makes_no_requests() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+
+    // A Bitbucket Cloud project with credentials, pointed at a local listener that
+    // would record any API request
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let api_base = format!("http://{}/2.0", listener.local_addr().unwrap());
+    fs::write(
+        temp_path.join("git-worktree-config.jsonc"),
+        format!(
+            r#"{{
+  "repositoryUrl": "git@bitbucket.org:test/my-repo.git",
+  "mainBranch": "main",
+  "sourceControl": "bitbucket-cloud",
+  "bitbucketEmail": "dev@example.com",
+  "bitbucketApiBase": "{}",
+  "prCacheTtlSecs": 0
+}}"#,
+            api_base
+        ),
+    )
+    .unwrap();
+
+    // The flag, then the environment variable
+    for (args, offline_env) in [(&["list", "--offline"][..], ""), (&["list"][..], "1")] {
+        let started = std::time::Instant::now();
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(args)
+            .env("GWT_OFFLINE", offline_env)
+            .env("BITBUCKET_CLOUD_API_TOKEN", "token")
+            .env("GWT_HTTP_RETRIES", "0")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("main"))
+            .stdout(predicate::str::contains("offline: skipping PR info"));
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+    }
+
+    let attempted = listener.accept();
+    assert!(
+        matches!(&attempted, Err(e) if e.kind() == std::io::ErrorKind::WouldBlock),
+        "gwt list --offline connected to the provider API"
+    );
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_list_without_pull_requests() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+
+    // As in the offline test: credentials plus a listener that records any API request
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let config_path = temp_path.join("git-worktree-config.jsonc");
+    let write_config = |show_pull_requests: bool| {
+        fs::write(
+            &config_path,
+            format!(
+                r#"{{
+  "repositoryUrl": "git@bitbucket.org:test/my-repo.git",
+  "mainBranch": "main",
+  "sourceControl": "bitbucket-cloud",
+  "bitbucketEmail": "dev@example.com",
+  "bitbucketApiBase": "http://{}/2.0",
+  "prCacheTtlSecs": 0,
+  "showPullRequests": {}
+}}"#,
+                listener.local_addr().unwrap(),
+                show_pull_requests
+            ),
+        )
+        .unwrap();
+    };
+
+    // The flag, then the config setting
+    for (args, show_pull_requests) in [(&["list", "--no-pr"][..], true), (&["list"][..], false)] {
+        write_config(show_pull_requests);
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(args)
+            .env("BITBUCKET_CLOUD_API_TOKEN", "token")
+            .env("GWT_HTTP_RETRIES", "0")
+            .env("GWT_HTTP_TIMEOUT", "2")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("main"))
+            .stdout(predicate::str::contains("Tip:").not())
+            .stdout(predicate::str::contains("offline").not());
+    }
+
+    let attempted = listener.accept();
+    assert!(
+        matches!(&attempted, Err(e) if e.kind() == std::io::ErrorKind::WouldBlock),
+        "gwt list without pull requests connected to the provider API"
+    );
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--no-pr", "--remote-only"])
+        .assert()
+        .failure();
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_list_json() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/json"])
+        .assert()
+        .success();
+
+    let output = cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--local", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["branch"], "main");
+    assert_eq!(entries[1]["branch"], "feature/json");
+    assert!(entries[1]["path"].as_str().unwrap().ends_with("feature/json"));
+    assert_eq!(entries[1]["bare"], false);
+    assert!(entries[1].get("pullRequest").is_some());
+    assert_eq!(entries[1]["draft"], false);
+    assert_eq!(entries[0]["current"], true);
+    assert_eq!(entries[1]["current"], false);
+
+    // From inside the feature worktree, that one is current instead
+    let feature_dir = PathBuf::from(entries[1]["path"].as_str().unwrap());
+    let output = cargo_bin_cmd!("gwt")
+        .current_dir(&feature_dir)
+        .args(["list", "--local", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(entries[0]["current"], false);
+    assert_eq!(entries[1]["current"], true);
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_list_group_by_base() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/grouped"])
+        .assert()
+        .success();
+
+    // Without PR info every worktree is ungrouped
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--offline", "--group-by", "base"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Local Worktrees by PR base branch:"))
+        .stdout(predicate::str::is_match(r"Ungrouped \(no pull request\)\n\n(?s).*feature/grouped").unwrap());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--group-by", "base", "--json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_list_show_commit() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/context"])
+        .assert()
+        .success();
+    let feature_dir = temp_path.join("my-repo-worktrees").join("feature/context");
+    run_git(
+        &feature_dir,
+        &["commit", "--allow-empty", "-m", "Teach the parser about tabs"],
+    );
+
+    // Read from each worktree, so the feature's own commit shows under it
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--local", "--show-commit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"feature/context\n  [0-9a-f]+ Teach the parser about tabs\n").unwrap());
+
+    let output = cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--local", "--json", "--show-commit"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(entries[1]["commitSubject"], "Teach the parser about tabs");
+
+    // Left out unless asked for
+    let output = cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--local", "--json"])
+        .output()
+        .unwrap();
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(entries[1].get("commitSubject").is_none());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_status_reports_dirty_and_ahead() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    // One local commit ahead of origin/main plus an untracked file
+    fs::write(repo_dir.join("local.txt"), "local").unwrap();
+    run_git(&repo_dir, &["add", "."]);
+    run_git(&repo_dir, &["commit", "-m", "Local commit"]);
+    fs::write(repo_dir.join("scratch.txt"), "scratch").unwrap();
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).arg("status");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("main"))
+        .stdout(predicate::str::contains("1 changed"))
+        .stdout(predicate::str::contains("↑1 ↓0"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_rename_moves_branch_and_worktree() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["add", "feature/old-name"]);
+    cmd.assert().success();
+
+    let worktrees_dir = temp_path.join("my-repo-worktrees");
+    fs::write(worktrees_dir.join("feature/old-name").join("wip.txt"), "wip").unwrap();
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir)
+        .args(["rename", "feature/old-name", "feature/new-name"]);
+    cmd.assert().success();
+
+    let new_path = worktrees_dir.join("feature/new-name");
+    assert!(!worktrees_dir.join("feature/old-name").exists());
+    assert!(
+        new_path.join("wip.txt").exists(),
+        "Local changes should move with the worktree"
+    );
+    assert_eq!(run_git(&new_path, &["branch", "--show-current"]), "feature/new-name");
+
+    // Protected branches are refused
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["rename", "main", "trunk"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot rename protected branch 'main'"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_move_worktree() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/big"])
+        .assert()
+        .success();
+    let old_path = temp_path.join("my-repo-worktrees/feature/big");
+    fs::write(old_path.join("wip.txt"), "wip").unwrap();
+
+    // Missing parent directories are created
+    let new_path = temp_path.join("big-disk/projects/big");
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["move", "feature/big", new_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Worktree moved to"));
+    assert!(!old_path.exists());
+    assert!(new_path.join("wip.txt").exists());
+    assert_eq!(run_git(&new_path, &["branch", "--show-current"]), "feature/big");
+    assert!(run_git(&repo_dir, &["worktree", "list"]).contains("big-disk/projects/big"));
+
+    // An existing directory receives the worktree inside it
+    let shelf = temp_path.join("shelf");
+    fs::create_dir(&shelf).unwrap();
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["move", "feature/big", shelf.to_str().unwrap()])
+        .assert()
+        .success();
+    assert!(shelf.join("big/wip.txt").exists());
+
+    // Locked worktrees and the main repository are refused with guidance
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["lock", "feature/big"])
+        .assert()
+        .success();
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["move", "feature/big", temp_path.join("elsewhere").to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("gwt unlock feature/big"));
+    assert!(shelf.join("big").exists());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["move", "main", temp_path.join("elsewhere").to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot move the main repository worktree"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_quiet_and_verbose_output() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["--quiet", "add", "feature/quiet"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Worktree created").not());
+
+    // The status table is still printed, only the progress line goes away
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["--quiet", "status", "--fetch"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Fetching latest changes").not())
+        .stdout(predicate::str::contains("feature/quiet"));
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["--verbose", "add", "feature/verbose"]);
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("$ git fetch origin"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_init_rejects_mismatched_url() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir)
+        .args(["init", "git@github.com:someone/else.git", "--local"]);
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "does not match git@github.com:someone/else.git",
+    ));
+    assert!(!temp_path.join("git-worktree-config.jsonc").exists());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_init_refuses_inside_initialized_project() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = temp_path.join("my-repo");
+    fs::create_dir(&repo_dir).unwrap();
+    create_test_git_repo(&repo_dir, "git@github.com:test/my-repo.git");
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["init", "--local"]);
+    cmd.assert().success();
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["init", "--local"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Already inside a gwt project"))
+        .stderr(predicate::str::contains("--force"))
+        .stderr(predicate::str::contains("--nested"));
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["init", "--local", "--force"]);
+    cmd.assert().success();
+
+    // --force re-initializes only the project itself, not a separate repository inside it
+    let inner_dir = repo_dir.join("vendor").join("inner");
+    fs::create_dir_all(&inner_dir).unwrap();
+    create_test_git_repo(&inner_dir, "git@github.com:test/inner.git");
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&inner_dir).args(["init", "--force"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("belongs to another gwt project"));
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&inner_dir).args(["init", "--nested"]);
+    cmd.assert().success();
+
+    // A global config covers its projectPath just like a local one
+    let global_dir = temp_path.join("global-repo");
+    fs::create_dir(&global_dir).unwrap();
+    create_test_git_repo(&global_dir, "git@github.com:test/global-repo.git");
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&global_dir).arg("init");
+    cmd.assert().success();
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&global_dir).arg("init");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Already inside a gwt project"));
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&global_dir).args(["init", "--force"]);
+    cmd.assert().success();
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_projects() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(temp_path)
+        .arg("projects")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No projects found"));
+
+    for name in ["kept", "gone"] {
+        let repo_dir = temp_path.join(name);
+        fs::create_dir(&repo_dir).unwrap();
+        create_test_git_repo(&repo_dir, &format!("git@github.com:test/{}.git", name));
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .arg("init")
+            .assert()
+            .success();
+    }
+    fs::remove_dir_all(temp_path.join("gone")).unwrap();
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(temp_path)
+        .arg("projects")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("git@github.com:test/kept.git"))
+        .stdout(predicate::str::contains("git@github.com:test/gone.git"))
+        .stdout(predicate::str::contains("✓ present"))
+        .stdout(predicate::str::contains("✗ missing"))
+        .stdout(predicate::str::contains("gwt projects --prune"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(temp_path)
+        .args(["projects", "--prune"])
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Prune cancelled"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(temp_path)
+        .args(["projects", "--prune"])
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Pruned 1 project config(s)"));
+    let backups: Vec<_> = fs::read_dir(temp_path.join(".gwt-config").join("projects"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".jsonc.bak"))
+        .collect();
+    assert_eq!(backups.len(), 1, "{:?}", backups);
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(temp_path)
+        .arg("projects")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("git@github.com:test/kept.git"))
+        .stdout(predicate::str::contains("test/gone.git").not());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_init_bare_layout() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    create_test_repo_with_origin(temp_path, "seed");
+    let workspace = temp_path.join("workspace");
+    fs::create_dir(&workspace).unwrap();
+
+    // Serve the GitHub URL from the local origin so provider detection still applies
+    let url = "https://github.com/test/my-app.git";
+    let rewrite = |cmd: &mut assert_cmd::Command| {
+        cmd.env("GIT_CONFIG_COUNT", "1")
+            .env(
+                "GIT_CONFIG_KEY_0",
+                format!("url.{}.insteadOf", temp_path.join("origin.git").display()),
+            )
+            .env("GIT_CONFIG_VALUE_0", url);
+    };
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    rewrite(&mut cmd);
+    cmd.current_dir(&workspace).args(["init", url, "--bare", "--local"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Main branch: main"));
+
+    let project = workspace.join("my-app");
+    assert!(project.join(".bare").is_dir());
+    assert_eq!(
+        fs::read_to_string(project.join(".git")).unwrap().trim(),
+        "gitdir: ./.bare"
+    );
+    assert!(project.join("main").join("README.md").exists());
+    assert!(project.join("git-worktree-config.jsonc").exists());
+    assert_eq!(
+        upstream_of(&project.join("main"), "main").as_deref(),
+        Some("origin/main")
+    );
+
+    // New worktrees go next to the main one
+    let mut cmd = cargo_bin_cmd!("gwt");
+    rewrite(&mut cmd);
+    cmd.current_dir(project.join("main")).args(["add", "feature/x"]);
+    cmd.assert().success();
+    assert!(project.join("feature").join("x").join("README.md").exists());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_clone_into_dir() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    create_test_repo_with_origin(temp_path, "seed");
+    let workspace = temp_path.join("workspace");
+    fs::create_dir(&workspace).unwrap();
+
+    // Serve the GitHub URL from the local origin so provider detection still applies
+    let url = "https://github.com/test/my-app.git";
+    let rewrite = |cmd: &mut assert_cmd::Command| {
+        cmd.env("GIT_CONFIG_COUNT", "1")
+            .env(
+                "GIT_CONFIG_KEY_0",
+                format!("url.{}.insteadOf", temp_path.join("origin.git").display()),
+            )
+            .env("GIT_CONFIG_VALUE_0", url);
+    };
+
+    // The parent directory doesn't exist yet and differs from the working directory
+    let mut cmd = cargo_bin_cmd!("gwt");
+    rewrite(&mut cmd);
+    cmd.current_dir(&workspace)
+        .args(["clone", url, "projects/thing", "--local"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Main branch: main"));
+
+    let parent = workspace.join("projects").join("thing");
+    let project = parent.join("my-app");
+    assert!(project.join("README.md").exists());
+    assert!(parent.join("git-worktree-config.jsonc").exists());
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    rewrite(&mut cmd);
+    cmd.current_dir(&project).args(["add", "feature/x"]);
+    cmd.assert().success();
+    assert!(parent
+        .join("my-app-worktrees")
+        .join("feature/x")
+        .join("README.md")
+        .exists());
+
+    // Cloning over an existing checkout is refused
+    let mut cmd = cargo_bin_cmd!("gwt");
+    rewrite(&mut cmd);
+    cmd.current_dir(&workspace).args(["clone", url, "projects/thing"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_color_flag() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+    let list = |args: &[&str], envs: &[(&str, &str)]| {
+        let output = cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["list", "--local"])
+            .args(args)
+            .envs(envs.iter().copied())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(list(&["--color", "always"], &[]).contains("\x1b["));
+    // CLICOLOR_FORCE would otherwise color piped output
+    assert!(!list(&["--color", "never"], &[("CLICOLOR_FORCE", "1")]).contains("\x1b["));
+    assert!(!list(&[], &[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")]).contains("\x1b["));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_config_get_set_path() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    let config_path = create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["config", "path"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(config_path.to_str().unwrap()));
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir)
+        .args(["config", "set", "mainBranch", "develop"]);
+    cmd.assert().success();
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["config", "get", "mainBranch"]);
+    cmd.assert().success().stdout("develop\n");
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir).args(["config", "set", "nope", "x"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown config key 'nope'"))
+        .stderr(predicate::str::contains("mainBranch"));
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir)
+        .args(["config", "set", "worktreeLayout", "diagonal"]);
+    cmd.assert().failure();
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_config_hooks() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    let config_path = create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    for command in ["npm install", "echo ${branchName}"] {
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["config", "add-hook", "postAdd", command])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(format!("✓ Added postAdd hook: {}", command)));
+    }
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["config", "hooks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1. npm install"))
+        .stdout(predicate::str::contains("2. echo ${branchName}"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["config", "remove-hook", "postAdd", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1. echo ${branchName}"));
+
+    let config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert_eq!(config["hooks"]["postAdd"], serde_json::json!(["echo ${branchName}"]));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["config", "remove-hook", "postAdd", "5"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No postAdd hook #5"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["config", "add-hook", "postClone", "echo hi"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'postClone'"));
+
+    cleanup_test_env(temp_dir);
+}
+
+fn upstream_of(dir: &std::path::Path, branch: &str) -> Option<String> {
+    std::process::Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", &format!("{}@{{upstream}}", branch)])
+        .current_dir(dir)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_tracking_defaults() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    // Remote-only branch: pushed, then deleted locally
+    run_git(&repo_dir, &["branch", "feature/remote"]);
+    run_git(&repo_dir, &["push", "origin", "feature/remote"]);
+    run_git(&repo_dir, &["branch", "-D", "feature/remote"]);
+
+    // Local-only branch
+    run_git(&repo_dir, &["branch", "feature/local"]);
+
+    for branch in ["feature/remote", "feature/local", "feature/new"] {
+        let mut cmd = cargo_bin_cmd!("gwt");
+        cmd.current_dir(&repo_dir).args(["add", branch]);
+        cmd.assert().success();
+    }
+
+    assert_eq!(
+        upstream_of(&repo_dir, "feature/remote").as_deref(),
+        Some("origin/feature/remote")
+    );
+    assert_eq!(upstream_of(&repo_dir, "feature/local"), None);
+    assert_eq!(upstream_of(&repo_dir, "feature/new"), None);
+
+    // --no-track opts out for remote branches
+    run_git(&repo_dir, &["branch", "feature/untracked"]);
+    run_git(&repo_dir, &["push", "origin", "feature/untracked"]);
+    run_git(&repo_dir, &["branch", "-D", "feature/untracked"]);
+
+    let mut cmd = cargo_bin_cmd!("gwt");
+    cmd.current_dir(&repo_dir)
+        .args(["add", "feature/untracked", "--no-track"]);
+    cmd.assert().success();
+    assert_eq!(upstream_of(&repo_dir, "feature/untracked"), None);
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_init_submodules() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let lib_dir = temp_path.join("lib");
+    fs::create_dir(&lib_dir).unwrap();
+    run_git(&lib_dir, &["init", "-b", "main"]);
+    run_git(&lib_dir, &["config", "user.email", "test@test.com"]);
+    run_git(&lib_dir, &["config", "user.name", "Test User"]);
+    fs::write(lib_dir.join("lib.txt"), "library").unwrap();
+    run_git(&lib_dir, &["add", "."]);
+    run_git(&lib_dir, &["commit", "-m", "Library"]);
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    run_git(
+        &repo_dir,
+        &[
+            "-c",
+            "protocol.file.allow=always",
+            "submodule",
+            "add",
+            lib_dir.to_str().unwrap(),
+            "libs/lib",
+        ],
+    );
+    run_git(&repo_dir, &["commit", "-m", "Add submodule"]);
+    run_git(&repo_dir, &["push", "origin", "main"]);
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    // Local submodule URLs need the file protocol, which git blocks by default
+    let add = |branch: &str| {
+        let mut cmd = cargo_bin_cmd!("gwt");
+        cmd.current_dir(&repo_dir)
+            .env("GIT_CONFIG_COUNT", "1")
+            .env("GIT_CONFIG_KEY_0", "protocol.file.allow")
+            .env("GIT_CONFIG_VALUE_0", "always")
+            .args(["add", branch]);
+        cmd
+    };
+    let worktrees = temp_path.join("my-repo-worktrees");
+
+    // Off by default: the submodule stays empty and a tip points at the setting
+    add("feature/plain")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("set initSubmodules to true"));
+    assert!(!worktrees.join("feature/plain/libs/lib/lib.txt").exists());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["config", "set", "initSubmodules", "true"])
+        .assert()
+        .success();
+    add("feature/with-lib")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Submodules initialized"));
+    assert_eq!(
+        fs::read_to_string(worktrees.join("feature/with-lib/libs/lib/lib.txt")).unwrap(),
+        "library"
+    );
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_push() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/pushed", "--push"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Pushed, tracking origin/feature/pushed"));
+    assert_eq!(
+        upstream_of(&repo_dir, "feature/pushed").as_deref(),
+        Some("origin/feature/pushed")
+    );
+
+    // Existing branches are left alone
+    run_git(&repo_dir, &["branch", "feature/local"]);
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/local", "--push"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("not pushing"));
+    assert_eq!(upstream_of(&repo_dir, "feature/local"), None);
+
+    // pushOnAdd makes it the default; --no-push opts out
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["config", "set", "pushOnAdd", "true"])
+        .assert()
+        .success();
+    for branch in ["feature/auto", "feature/skipped"] {
+        let mut cmd = cargo_bin_cmd!("gwt");
+        cmd.current_dir(&repo_dir).args(["add", branch]);
+        if branch == "feature/skipped" {
+            cmd.arg("--no-push");
+        }
+        cmd.assert().success();
+    }
+    assert_eq!(
+        upstream_of(&repo_dir, "feature/auto").as_deref(),
+        Some("origin/feature/auto")
+    );
+    assert_eq!(upstream_of(&repo_dir, "feature/skipped"), None);
+
+    // A failed push keeps the worktree and the exit status
+    let unreachable = temp_path.join("missing.git");
+    run_git(
+        &repo_dir,
+        &["remote", "set-url", "--push", "origin", unreachable.to_str().unwrap()],
+    );
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/offline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Push failed"));
+    assert!(temp_path
+        .join("my-repo-worktrees")
+        .join("feature/offline")
+        .join("README.md")
+        .exists());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_uses_default_remote() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    run_git(&repo_dir, &["remote", "rename", "origin", "upstream"]);
+
+    // A second remote holds a branch that isn't on upstream
+    let fork_dir = temp_path.join("fork.git");
+    fs::create_dir_all(&fork_dir).unwrap();
+    run_git(&fork_dir, &["init", "--bare", "-b", "main"]);
+    run_git(&repo_dir, &["remote", "add", "fork", fork_dir.to_str().unwrap()]);
+    run_git(&repo_dir, &["checkout", "-b", "fork-only"]);
+    run_git(&repo_dir, &["push", "fork", "fork-only"]);
+    run_git(&repo_dir, &["checkout", "main"]);
+    run_git(&repo_dir, &["branch", "-D", "fork-only"]);
+
+    let config_path = create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+    let config = fs::read_to_string(&config_path)
+        .unwrap()
+        .replace("\"mainBranch\"", "\"defaultRemote\": \"upstream\",\n  \"mainBranch\"");
+    fs::write(&config_path, config).unwrap();
+
+    let worktrees_dir = temp_path.join("my-repo-worktrees");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/new"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fetching latest changes from upstream"))
+        .stdout(predicate::str::contains("from 'upstream/main'"));
+    assert!(worktrees_dir.join("feature/new").exists());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "fork-only"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("exists on fork"));
+    assert_eq!(upstream_of(&repo_dir, "fork-only").as_deref(), Some("fork/fork-only"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_init_with_remote() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = temp_path.join("my-repo");
+    fs::create_dir(&repo_dir).unwrap();
+    create_test_git_repo(&repo_dir, "git@github.com:test/my-repo.git");
+    run_git(&repo_dir, &["remote", "rename", "origin", "upstream"]);
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["init", "--local"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No remote 'origin' found"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["init", "--local", "--remote", "upstream"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Detected provider: Github"))
+        .stdout(predicate::str::contains("✓ Remote: upstream"));
+
+    let config_content = fs::read_to_string(temp_path.join("git-worktree-config.jsonc")).unwrap();
+    assert!(config_content.contains("\"defaultRemote\": \"upstream\""));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_init_records_remote_default_branch() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    // The remote's default branch is develop, origin/HEAD isn't known locally,
+    // and a feature branch is checked out
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    let origin_dir = temp_path.join("origin.git");
+    run_git(&repo_dir, &["push", "origin", "main:develop"]);
+    run_git(&origin_dir, &["symbolic-ref", "HEAD", "refs/heads/develop"]);
+    run_git(&repo_dir, &["remote", "set-head", "origin", "--delete"]);
+    run_git(&repo_dir, &["checkout", "-b", "feature/wip"]);
+
+    // A local remote whose path still reads as a Bitbucket URL, so the provider is detected
+    let remote_dir = temp_path.join("bitbucket.org/test");
+    fs::create_dir_all(&remote_dir).unwrap();
+    fs::rename(&origin_dir, remote_dir.join("my-repo.git")).unwrap();
+    run_git(
+        &repo_dir,
+        &[
+            "remote",
+            "set-url",
+            "origin",
+            remote_dir.join("my-repo.git").to_str().unwrap(),
+        ],
+    );
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["init", "--local"])
+        .assert()
+        .success();
+
+    let config_content = fs::read_to_string(temp_path.join("git-worktree-config.jsonc")).unwrap();
+    assert!(
+        config_content.contains("\"mainBranch\": \"develop\""),
+        "{}",
+        config_content
+    );
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_init_and_clone_with_branch() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    run_git(&repo_dir, &["push", "origin", "main:release"]);
+
+    // A branch missing on the remote is refused before anything is saved
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["init", "--local", "--provider", "github", "--branch", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Branch 'nope' does not exist on origin"));
+    assert!(!temp_path.join("git-worktree-config.jsonc").exists());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["init", "--local", "--provider", "github", "--branch", "release"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Main branch: release"));
+    let config_content = fs::read_to_string(temp_path.join("git-worktree-config.jsonc")).unwrap();
+    assert!(
+        config_content.contains("\"mainBranch\": \"release\""),
+        "{}",
+        config_content
+    );
+
+    // Cloning checks the branch out
+    let origin_url = temp_path.join("origin.git");
+    let workspace = temp_path.join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    cargo_bin_cmd!("gwt")
+        .current_dir(&workspace)
+        .args(["clone", origin_url.to_str().unwrap(), "--local", "--provider", "github"])
+        .args(["--branch", "release"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Main branch: release"));
+    let output = std::process::Command::new("git")
+        .args(["branch", "--show-current"])
+        .current_dir(workspace.join("origin"))
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "release");
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_fetch_reports_updated_refs() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    // Push a new branch to origin from a separate clone
+    let other_dir = temp_path.join("other");
+    run_git(
+        temp_path,
+        &[
+            "clone",
+            temp_path.join("origin.git").to_str().unwrap(),
+            other_dir.to_str().unwrap(),
+        ],
+    );
+    run_git(&other_dir, &["push", "origin", "HEAD:refs/heads/feature/remote"]);
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .arg("fetch")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Updated 1 remote ref(s)"));
+    run_git(&repo_dir, &["rev-parse", "--verify", "origin/feature/remote"]);
+
+    // Deleting it on the remote and fetching with --prune drops the ref
+    run_git(&other_dir, &["push", "origin", "--delete", "feature/remote"]);
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["fetch", "--prune"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pruned 1"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .arg("fetch")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Already up to date"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_with_path_override() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    let custom_path = temp_path.join("fast-disk").join("feature-x");
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/x", "--path", custom_path.to_str().unwrap()])
+        .assert()
+        .success();
+    assert!(custom_path.exists(), "Worktree should be created at --path");
+    assert!(!temp_path.join("my-repo-worktrees").join("feature/x").exists());
+
+    // An existing target path is rejected
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/y", "--path", custom_path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    // remove finds the worktree by its full path
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", custom_path.to_str().unwrap(), "--force"])
+        .assert()
+        .success();
+    assert!(!custom_path.exists(), "Worktree should be removed");
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_prefers_stored_project_path() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    let config_path = create_test_config_with_hooks(
+        temp_path,
+        "git@github.com:test/my-repo.git",
+        "main",
+        r#"{ "postAdd": ["echo ${projectRoot} > hook-root.txt"] }"#,
+    );
+    let config = fs::read_to_string(&config_path).unwrap().replace(
+        "\"mainBranch\"",
+        &format!("\"projectPath\": \"{}\",\n  \"mainBranch\"", repo_dir.display()),
+    );
+    fs::write(&config_path, config).unwrap();
+
+    // A worktree outside the -worktrees folder can't be traced back by its path
+    let outside = temp_path.join("elsewhere").join("feature-a");
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/a", "--path", outside.to_str().unwrap()])
+        .assert()
+        .success();
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&outside)
+        .args(["add", "feature/b"])
+        .assert()
+        .success();
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/b");
+    let output = fs::read_to_string(worktree_path.join("hook-root.txt")).unwrap();
+    assert_eq!(output.trim(), repo_dir.display().to_string());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_doctor() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+
+    // Without a config the critical config check fails
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .arg("doctor")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("✓ Git"))
+        .stdout(predicate::str::contains("✗ Config"))
+        .stdout(predicate::str::contains("gwt init"));
+
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/gone");
+    run_git(
+        &repo_dir,
+        &["worktree", "add", "-b", "feature/gone", worktree_path.to_str().unwrap()],
+    );
+    fs::remove_dir_all(&worktree_path).unwrap();
+
+    // A missing worktree directory is reported but isn't critical
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Config"))
+        .stdout(predicate::str::contains("✗ Worktrees"))
+        .stdout(predicate::str::contains("gwt prune"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_remove_all_merged() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+    let worktrees_dir = temp_path.join("my-repo-worktrees");
+
+    for branch in ["feature/merged", "feature/unmerged", "develop"] {
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["add", branch])
+            .assert()
+            .success();
+    }
+
+    // Give feature/unmerged a commit that main doesn't have
+    let unmerged_path = worktrees_dir.join("feature/unmerged");
+    fs::write(unmerged_path.join("new.txt"), "new").unwrap();
+    run_git(&unmerged_path, &["add", "."]);
+    run_git(&unmerged_path, &["commit", "-m", "Unmerged work"]);
+
+    // Dry run: the exact worktrees and branches, with no prompt (stdin is closed) or changes
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "--all-merged", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("About to remove 1 worktree(s)"))
+        .stdout(predicate::str::contains(
+            "Branches that would be deleted: feature/merged",
+        ))
+        .stdout(predicate::str::contains("(y/N)").not());
+    assert!(worktrees_dir.join("feature/merged").exists());
+    assert!(!run_git(&repo_dir, &["branch", "--list", "feature/merged"]).is_empty());
+
+    // The worktree the command runs in is never part of the batch
+    cargo_bin_cmd!("gwt")
+        .current_dir(worktrees_dir.join("feature/merged"))
+        .args(["remove", "--all-merged", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Skipping 'feature/merged' (the current worktree)",
+        ))
+        .stdout(predicate::str::contains("No worktrees with branches merged"));
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "--all-merged", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("About to remove 1 worktree(s)"));
+
+    assert!(!worktrees_dir.join("feature/merged").exists());
+    assert!(unmerged_path.exists(), "Unmerged worktree should be kept");
+    assert!(
+        worktrees_dir.join("develop").exists(),
+        "Protected branch should be kept"
+    );
+    assert!(run_git(&repo_dir, &["branch", "--list", "feature/merged"]).is_empty());
+
+    // A branch name can't be combined with --all-merged
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["remove", "feature/unmerged", "--all-merged"])
+        .assert()
+        .failure();
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_auth_setup_token_stdin() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    // An empty line is an error rather than a silently stored empty token
+    cargo_bin_cmd!("gwt")
+        .current_dir(temp_path)
+        .args(["auth", "gitea", "setup", "--token-stdin"])
+        .env("GWT_TOKEN_STORE", "file")
+        .write_stdin("\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No token on stdin"));
+
+    // Outside a Gitea project the token is stored without a connection test
+    cargo_bin_cmd!("gwt")
+        .current_dir(temp_path)
+        .args(["auth", "gitea", "setup", "--token-stdin"])
+        .env("GWT_TOKEN_STORE", "file")
+        .write_stdin("gitea-secret\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Gitea token stored in the"));
+
+    // tokens/<service>/<user> holds exactly the piped token, without the newline
+    let only_entry = |dir: PathBuf| {
+        let entries: Vec<PathBuf> = fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+        assert_eq!(entries.len(), 1, "{:?}", entries);
+        entries[0].clone()
+    };
+    let service_dir = only_entry(temp_path.join(".gwt-config").join("tokens"));
+    assert_eq!(fs::read_to_string(only_entry(service_dir)).unwrap(), "gitea-secret");
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_auth_status() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    let output = cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["--color", "never", "auth", "status"])
+        .env("GITHUB_TOKEN", "test-token")
+        .env_remove("BITBUCKET_CLOUD_API_TOKEN")
+        .env_remove("BITBUCKET_CLOUD_API_TOKEN_FILE")
+        .env_remove("BITBUCKET_DATA_CENTER_HTTP_ACCESS_TOKEN_FILE")
+        .env("BITBUCKET_DATA_CENTER_HTTP_ACCESS_TOKEN", "dc-token")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let line = |provider: &str| {
+        stdout
+            .lines()
+            .find(|line| line.starts_with(&format!("{} ", provider)))
+            .unwrap_or_else(|| panic!("no row for {} in:\n{}", provider, stdout))
+            .to_string()
+    };
+
+    // The GitHub token may come from gh instead when it's logged in on this machine
+    let github = line("GitHub");
+    assert!(github.contains('✓') && github.contains("← this project"), "{}", github);
+    let cloud = line("Bitbucket Cloud");
+    assert!(cloud.contains('✗') && !cloud.contains("this project"), "{}", cloud);
+    let data_center = line("Bitbucket Data Center");
+    assert!(
+        data_center.contains('✓') && data_center.contains("BITBUCKET_DATA_CENTER_HTTP_ACCESS_TOKEN (env)"),
+        "{}",
+        data_center
+    );
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_list_remote_only() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    let output = cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--remote-only", "--json"])
+        .env_remove("GITHUB_TOKEN")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert!(
+        entries.as_array().unwrap().iter().all(|entry| entry["path"].is_null()),
+        "--remote-only should not list local worktrees"
+    );
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--remote-only"])
+        .env_remove("GITHUB_TOKEN")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Local Worktrees:").not());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--remote-only", "--local"])
+        .assert()
+        .failure();
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_list_urls_only() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    // No PRs means no output at all, not headers or tips
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--urls-only", "--offline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--urls-only", "--json"])
+        .assert()
+        .failure();
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_list_format() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    // Offline there are no PRs, so their placeholders are empty
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--offline", "--format", "{branch}\\t{pr_status}|{pr_url}"])
+        .assert()
+        .success()
+        .stdout("main\t|\n");
+
+    for conflicting in ["--json", "--remote-only"] {
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["list", "--format", "{branch}", conflicting])
+            .assert()
+            .failure();
+    }
+
+    // --since only narrows the open PR list, and a bad value fails up front
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--offline", "--since", "7d", "--format", "{branch}"])
+        .assert()
+        .success()
+        .stdout("main\n");
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["list", "--offline", "--since", "last week"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid --since 'last week'"));
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_list_json_sort_and_filter() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    for branch in ["feature/b", "feature/a"] {
+        cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["add", branch])
+            .assert()
+            .success();
+    }
+
+    let list = |args: &[&str]| -> Vec<String> {
+        let output = cargo_bin_cmd!("gwt")
+            .current_dir(&repo_dir)
+            .args(["list", "--local", "--json"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        entries
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["branch"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    assert_eq!(list(&["--sort", "name"]), ["feature/a", "feature/b", "main"]);
+    assert_eq!(list(&["--filter", "no-pr", "--sort", "name"]).len(), 3);
+    assert!(list(&["--filter", "has-pr"]).is_empty());
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_add_copies_files_on_add() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    let config_path = create_test_config_with_hooks(
+        temp_path,
+        "git@github.com:test/my-repo.git",
+        "main",
+        r#"{ "postAdd": ["cat .env > hook-saw-env.txt"] }"#,
+    );
+    let config = fs::read_to_string(&config_path).unwrap().replace(
+        "\"mainBranch\"",
+        "\"copyOnAdd\": [\".env\", \".vscode/\", \"*.md\"],\n  \"mainBranch\"",
+    );
+    fs::write(&config_path, config).unwrap();
+
+    // Untracked local files in the main worktree
+    fs::write(repo_dir.join(".env"), "SECRET=1").unwrap();
+    fs::create_dir_all(repo_dir.join(".vscode")).unwrap();
+    fs::write(repo_dir.join(".vscode").join("settings.json"), "{}").unwrap();
+    fs::write(repo_dir.join("README.md"), "local edit").unwrap();
+    // A link back up the tree is copied as a link instead of recursed into
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("..", repo_dir.join(".vscode").join("parent")).unwrap();
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["add", "feature/copy"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Copied .env"));
+
+    let worktree_path = temp_path.join("my-repo-worktrees").join("feature/copy");
+    assert_eq!(fs::read_to_string(worktree_path.join(".env")).unwrap(), "SECRET=1");
+    assert!(worktree_path.join(".vscode").join("settings.json").exists());
+    #[cfg(unix)]
+    assert_eq!(
+        fs::read_link(worktree_path.join(".vscode").join("parent")).unwrap(),
+        PathBuf::from("..")
+    );
+    // postAdd hooks run after the copy
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("hook-saw-env.txt")).unwrap(),
+        "SECRET=1"
+    );
+    // Files that already exist in the new worktree are not overwritten
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("README.md")).unwrap(),
+        "# Test Repo"
+    );
+
+    cleanup_test_env(temp_dir);
+}
+
+#[test]
+#[serial]
+fn test_gwt_pr_create_requires_feature_branch() {
+    let temp_dir = setup_test_env();
+    let temp_path = temp_dir.path();
+
+    let repo_dir = create_test_repo_with_origin(temp_path, "my-repo");
+    create_test_config(temp_path, "git@github.com:test/my-repo.git", "main");
+
+    cargo_bin_cmd!("gwt")
+        .current_dir(&repo_dir)
+        .args(["pr", "create", "--title", "Nothing to merge"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is the base branch"));
+
+    cleanup_test_env(temp_dir);
+}
@@ -16,6 +16,9 @@ pub fn setup_test_env() -> TempDir {
         fs::create_dir_all(temp_path).expect("Failed to create temp directory");
     }
 
+    // Keep global config out of the real user directory; inherited by spawned gwt processes
+    std::env::set_var("GWT_CONFIG_DIR", temp_path.join(".gwt-config"));
+
     temp_dir
 }
 
@@ -29,18 +32,21 @@ pub fn cleanup_test_env(temp_dir: TempDir) {
 /// Create a test configuration file
 #[allow(dead_code)]
 pub fn create_test_config(dir: &std::path::Path, repo_url: &str, main_branch: &str) -> PathBuf {
+    create_test_config_with_hooks(dir, repo_url, main_branch, r#"{ "postAdd": [], "postRemove": [] }"#)
+}
+
+/// Create a test configuration file with a custom `hooks` object (raw JSON)
+#[allow(dead_code)]
+pub fn create_test_config_with_hooks(dir: &std::path::Path, repo_url: &str, main_branch: &str, hooks: &str) -> PathBuf {
     let config_content = format!(
         r#"{{
   "repositoryUrl": "{}",
   "mainBranch": "{}",
   "createdAt": "2025-06-25T17:25:28.766876Z",
   "sourceControl": "github",
-  "hooks": {{
-    "postAdd": [],
-    "postRemove": []
-  }}
+  "hooks": {}
 }}"#,
-        repo_url, main_branch
+        repo_url, main_branch, hooks
     );
 
     let config_path = dir.join("git-worktree-config.jsonc");
@@ -143,3 +149,48 @@ pub fn create_test_git_repo(dir: &std::path::Path, remote_url: &str) -> PathBuf
 
     repo_dir
 }
+
+/// Run a git command in a directory, panicking on failure
+#[allow(dead_code)]
+pub fn run_git(dir: &std::path::Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Create a test git repository backed by a local bare `origin`
+///
+/// The bare remote lives at `<dir>/origin.git` and the working repository at
+/// `<dir>/<name>`. The `main` branch is committed and pushed so that
+/// `origin/main` exists and fetches work offline.
+#[allow(dead_code)]
+pub fn create_test_repo_with_origin(dir: &std::path::Path, name: &str) -> PathBuf {
+    let origin_dir = dir.join("origin.git");
+    fs::create_dir_all(&origin_dir).expect("Failed to create origin directory");
+    run_git(&origin_dir, &["init", "--bare", "-b", "main"]);
+
+    let repo_dir = dir.join(name);
+    fs::create_dir_all(&repo_dir).expect("Failed to create repo directory");
+    run_git(&repo_dir, &["init", "-b", "main"]);
+    run_git(&repo_dir, &["config", "user.email", "test@test.com"]);
+    run_git(&repo_dir, &["config", "user.name", "Test User"]);
+
+    fs::write(repo_dir.join("README.md"), "# Test Repo").expect("Failed to write README");
+    run_git(&repo_dir, &["add", "."]);
+    run_git(&repo_dir, &["commit", "-m", "Initial commit"]);
+
+    run_git(&repo_dir, &["remote", "add", "origin", origin_dir.to_str().unwrap()]);
+    run_git(&repo_dir, &["push", "-u", "origin", "main"]);
+    run_git(&repo_dir, &["remote", "set-head", "origin", "main"]);
+
+    repo_dir
+}